@@ -0,0 +1,15 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS listens
+(
+    position INTEGER PRIMARY KEY NOT NULL,
+    artist_name TEXT NOT NULL,
+    track_name TEXT NOT NULL,
+    album_name TEXT,
+    end_time TEXT NOT NULL,
+    ms_played INTEGER NOT NULL,
+    service TEXT NOT NULL,
+    skipped INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS listens_end_time ON listens (end_time);
+CREATE INDEX IF NOT EXISTS listens_artist_name ON listens (artist_name COLLATE NOCASE);
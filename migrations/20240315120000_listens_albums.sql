@@ -0,0 +1,2 @@
+-- Add migration script here
+CREATE INDEX IF NOT EXISTS listens_album_name ON listens (album_name COLLATE NOCASE);
@@ -0,0 +1,2 @@
+-- Add migration script here
+CREATE INDEX IF NOT EXISTS listens_track_name ON listens (track_name COLLATE NOCASE);
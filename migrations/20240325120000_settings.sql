@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS artist_aliases
+(
+    alias TEXT PRIMARY KEY NOT NULL COLLATE NOCASE,
+    artist_name TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS settings
+(
+    name TEXT PRIMARY KEY NOT NULL,
+    data TEXT NOT NULL
+);
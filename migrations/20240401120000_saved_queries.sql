@@ -0,0 +1,6 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS saved_queries
+(
+    name TEXT PRIMARY KEY NOT NULL,
+    command TEXT NOT NULL
+);
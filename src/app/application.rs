@@ -1,37 +1,59 @@
 use std::{
-    collections::{HashMap, HashSet},
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap},
     fs,
     path::PathBuf,
-    str::FromStr,
     sync::Arc,
 };
 
 use arboard::Clipboard;
-use chrono::{Local, NaiveDate, Weekday};
+use chrono::{Datelike, Local, Months, NaiveDate, Weekday};
 use log::{error, info};
+use rand::seq::SliceRandom;
 use strum::IntoEnumIterator;
 use tokio::sync::Mutex;
 
 use crate::{
     errors::InteractiveError,
-    persistence::{fs::FileWriter, EventStore, Format, OutputFolder, StateStore, Writer},
-    processing,
+    persistence::{
+        fs::FileWriter, EventStore, Format, OutputFolder, SettingsStore, StateStore, Writer,
+    },
+    processing::{self, TitleNormalizer},
     projections::{
-        statistics::{order_in_week, ArtistsCounts, EventProcessor, MonthCounts},
-        ListenTrackerRepository,
+        statistics::{
+            order_in_week, ArtistTimeline, ArtistsCounts, EventProcessor, Listen, ListenSpan,
+            ListenStreaks, Metric, MonthCounts, Period, RankComparison,
+        },
+        ListenTrackerRepository, ListensRepository,
     },
-    track_plays::ArtistName,
+    query,
+    track_plays::{AlbumName, ArtistName, TrackName},
+    utils::{best_matches, months_between},
 };
 
 use super::{
-    chart::{BarBreakdown, BarDataPoint},
-    CommandName, CommandParameters, MessageSet, Mode, Output, State,
+    artist_detail::artist_detail,
+    chart::{image_name, render_png, render_svg, BarBreakdown, BarDataPoint},
+    export::render_export,
+    song_detail::song_detail,
+    wrapped::wrapped_report,
+    CommandLine, CommandName, CommandParameterSpec, CommandParameters, Completion, HasId,
+    HistoryEntry, HistorySearch, MessageSet, Mode, Output, OutputDetails, OutputSearch,
+    ReportFormat, Scroll, Settings, State, Table,
 };
 
+const MAX_COMPLETIONS: usize = 8;
+
 pub struct Application {
     store: Arc<Mutex<dyn EventStore>>,
     repository: Arc<Mutex<dyn ListenTrackerRepository>>,
+    listens: Arc<Mutex<dyn ListensRepository>>,
     state_store: Arc<Mutex<dyn StateStore>>,
+    settings_store: Arc<Mutex<dyn SettingsStore>>,
+    /// Stored state or settings that couldn't be read are left as they are, rather than
+    /// overwritten with the defaults used in their place.
+    state_unreadable: bool,
+    settings_unreadable: bool,
     pub processor: EventProcessor,
     pub state: State,
 }
@@ -40,38 +62,87 @@ impl Application {
     pub fn new(
         store: Arc<Mutex<dyn EventStore>>,
         repository: Arc<Mutex<dyn ListenTrackerRepository>>,
+        listens: Arc<Mutex<dyn ListensRepository>>,
         state_store: Arc<Mutex<dyn StateStore>>,
+        settings_store: Arc<Mutex<dyn SettingsStore>>,
     ) -> Application {
         Application {
             store,
             repository,
+            listens,
             state_store,
+            settings_store,
+            state_unreadable: false,
+            settings_unreadable: false,
             processor: EventProcessor::default(),
             state: State::default(),
         }
     }
 
     pub async fn initialize(&mut self) -> Result<(), InteractiveError> {
-        let event_store = self.store.lock().await;
-        let event_stream = event_store
-            .get_events("listens")
-            .await
-            .map_err(|e| InteractiveError::GetEventsError { error: e })?;
-        for event in event_stream.events.iter() {
-            self.processor.process_event(event);
-        }
-
         let state_store = self.state_store.lock().await;
-        let state = match state_store.get().await {
+        let mut errors = Vec::new();
+        let mut state = match state_store.get().await {
             Ok(it) => it,
             Err(e) => {
                 error!("{e}");
+                self.state_unreadable = true;
+                errors.push(format!(
+                    "The saved state couldn't be read and won't be saved over: {e}"
+                ));
 
                 State::default()
             }
         };
+        drop(state_store);
+
+        match self.settings_store.lock().await.get().await {
+            Ok(Some(settings)) => state.set_settings(settings),
+            // Settings from before they had a store of their own are moved over, unless
+            // the state they'd come from couldn't be read.
+            Ok(None) if !self.state_unreadable => state.store_settings(),
+            Ok(None) => {}
+            Err(e) => {
+                error!("{e}");
+                self.settings_unreadable = true;
+                errors.push(format!(
+                    "The settings couldn't be read and won't be saved over: {e}"
+                ));
+                state.set_settings(Settings::default());
+            }
+        }
+        if !errors.is_empty() {
+            state.error_message = Some(errors.join(". "));
+        }
         self.state = state;
 
+        self.rebuild_projections().await
+    }
+
+    async fn rebuild_projections(&mut self) -> Result<(), InteractiveError> {
+        let event_store = self.store.lock().await;
+        let event_stream = event_store
+            .get_events("listens")
+            .await
+            .map_err(|e| InteractiveError::GetEventsError { error: e })?;
+
+        let mut processor = EventProcessor::new(
+            self.state.classification().clone(),
+            self.state.aliases().clone(),
+            self.state.titles().clone(),
+            self.state.credits().clone(),
+        );
+        for event in event_stream.events.iter() {
+            processor.process_event(event);
+        }
+        self.processor = processor;
+
+        self.listens
+            .lock()
+            .await
+            .sync(&event_stream.events, &self.state.listen_rules())
+            .await;
+
         Ok(())
     }
 
@@ -103,12 +174,110 @@ impl Application {
         self.state.previous_page();
     }
 
+    /// How many lines the current output is scrolled down by. The list of commands shown
+    /// while entering one is never scrolled.
+    pub fn scroll_offset(&self) -> usize {
+        if matches!(self.mode(), Mode::EnterCommand | Mode::CommandParameters) {
+            return 0;
+        }
+
+        self.state
+            .output()
+            .get(self.state.current_page)
+            .map(|output| self.state.scroll().offset(output.id()))
+            .unwrap_or_default()
+    }
+
+    /// The number of lines of output that fit on the screen, set as it's drawn.
+    pub fn set_page_height(&mut self, page_height: usize) {
+        self.state.scroll_mut().set_page_height(page_height);
+    }
+
+    pub fn scroll_output(&mut self, scroll: Scroll) {
+        let (id, lines) = match self.state.output().get(self.state.current_page) {
+            Some(output) => (output.id().to_string(), output.lines()),
+            None => return,
+        };
+        self.state.scroll_mut().scroll(&id, scroll, lines);
+    }
+
+    /// Sorts the table on the current page by the column, or reverses it if it's already
+    /// sorted by that column.
+    pub fn sort_current_output(&mut self, column: usize) {
+        let current_page = self.state.current_page;
+        if let Some(Output::Table(table)) = self.state.output_mut().into_iter().nth(current_page) {
+            table.sort_by(column);
+        }
+    }
+
     pub fn push_input_char(&mut self, c: char) {
         self.state.input.push(c);
+        self.update_search();
+        self.update_history_search();
     }
 
     pub fn pop_input_char(&mut self) {
         self.state.input.pop();
+        self.update_search();
+        self.update_history_search();
+    }
+
+    pub fn output_search(&self) -> Option<&OutputSearch> {
+        self.state.search.as_ref()
+    }
+
+    pub fn start_search(&mut self) {
+        self.state.input.clear();
+        self.state.search = Some(OutputSearch::default());
+        self.state.mode = Mode::Search;
+    }
+
+    /// Keeps the matches highlighted to jump between them.
+    pub fn search_entered(&mut self) {
+        self.state.input.clear();
+        self.state.mode = Mode::Normal;
+    }
+
+    pub fn cancel_search(&mut self) {
+        self.state.input.clear();
+        self.state.search = None;
+        self.state.mode = Mode::Normal;
+    }
+
+    pub fn next_match(&mut self) {
+        self.jump_to_match(OutputSearch::next);
+    }
+
+    pub fn previous_match(&mut self) {
+        self.jump_to_match(OutputSearch::previous);
+    }
+
+    /// Searches again as the query is typed, jumping to the first match.
+    fn update_search(&mut self) {
+        if !matches!(self.state.mode, Mode::Search) {
+            return;
+        }
+
+        let query = self.state.input.current().to_string();
+        if let Some(search) = self.state.search.as_mut() {
+            search.set_query(&query);
+        }
+        self.jump_to_match(OutputSearch::next);
+    }
+
+    fn jump_to_match(&mut self, jump: fn(&mut OutputSearch, &[String]) -> Option<usize>) {
+        let lines = match self.state.output().get(self.state.current_page) {
+            Some(output) => output.text_lines(),
+            None => return,
+        };
+        let line = self
+            .state
+            .search
+            .as_mut()
+            .and_then(|search| jump(search, &lines));
+        if let Some(line) = line {
+            self.scroll_output(Scroll::To(line));
+        }
     }
 
     pub fn current_input(&self) -> &str {
@@ -133,10 +302,15 @@ impl Application {
     pub fn current_output(&self) -> Option<Output> {
         match self.mode() {
             Mode::CommandParameters => None,
-            Mode::EnterCommand => Some(Output::MessageSet(self.state.command_message_set())),
-            Mode::Processing | Mode::Normal => {
-                self.state.output().get(self.state.current_page).cloned()
+            Mode::EnterCommand | Mode::HistorySearch => {
+                Some(Output::MessageSet(self.state.command_message_set()))
             }
+            Mode::Processing
+            | Mode::Normal
+            | Mode::Outputs
+            | Mode::RenameOutput
+            | Mode::Search
+            | Mode::History => self.state.output().get(self.state.current_page).cloned(),
         }
     }
 
@@ -150,31 +324,91 @@ impl Application {
         }
     }
 
+    /// Tab completes artist, song and album names, ignoring case and diacritics. When more
+    /// than one name matches they're listed, and pressing Tab again cycles through them.
     pub fn autocomplete_command_parameter(&mut self) {
-        if let Some(super::CommandParameterSpec::ArtistName { description: _ }) =
-            self.state.command_parameter_inputs.first()
-        {
-            let matches: Vec<&ArtistName> = self
-                .processor
-                .artist_names
-                .iter()
-                .filter(|artist_name| artist_name.starts_with(self.state.input.current()))
-                .collect();
-            if matches.len() == 1 {
-                self.state.input.set_text(&matches.first().unwrap().0);
+        if self.state.input.cycle_completion() {
+            return;
+        }
+
+        let candidates = match self.state.command_parameter_inputs.first() {
+            Some(CommandParameterSpec::ArtistName { description: _ }) => best_matches(
+                self.state.input.current(),
+                self.processor
+                    .listen_timeline
+                    .artist_names()
+                    .map(|artist_name| artist_name.0.as_str()),
+                MAX_COMPLETIONS,
+            ),
+            Some(CommandParameterSpec::TrackName { description: _ }) => {
+                let track_names = self.listened_names(|timeline| {
+                    timeline
+                        .tracks
+                        .keys()
+                        .map(|track_name| track_name.0.as_str())
+                });
+                best_matches(self.state.input.current(), track_names, MAX_COMPLETIONS)
             }
+            Some(CommandParameterSpec::AlbumName { description: _ }) => {
+                let album_names = self.listened_names(|timeline| {
+                    timeline
+                        .albums
+                        .keys()
+                        .map(|album_name| album_name.0.as_str())
+                });
+                best_matches(self.state.input.current(), album_names, MAX_COMPLETIONS)
+            }
+            _ => Vec::new(),
+        };
+
+        if let Some(first) = candidates.first() {
+            self.state.input.set_text(first);
+        }
+        if candidates.len() > 1 {
+            self.state.input.completion = Some(Completion::with_candidates(candidates));
         }
     }
 
+    /// Song or album names listened to, narrowed down to the artist already entered.
+    fn listened_names<'a, F, I>(&'a self, names: F) -> BTreeSet<&'a str>
+    where
+        F: Fn(&'a ArtistTimeline) -> I,
+        I: Iterator<Item = &'a str>,
+    {
+        let artist_name = self
+            .state
+            .command_parameters
+            .as_ref()
+            .and_then(|parameters| parameters.artist_name())
+            .map(|name| self.processor.aliases.resolve(&ArtistName(name.clone())));
+
+        self.processor
+            .listen_timeline
+            .artists()
+            .filter(|timeline| {
+                artist_name
+                    .as_ref()
+                    .map(|name| timeline.artist_name.0.eq_ignore_ascii_case(&name.0))
+                    .unwrap_or(true)
+            })
+            .flat_map(names)
+            .collect()
+    }
+
+    pub fn completion(&self) -> Option<&Completion> {
+        self.state.input.completion.as_ref()
+    }
+
     pub async fn tick(&mut self) -> Result<(), InteractiveError> {
         match self.state.mode {
             Mode::CommandParameters => {
                 if self.state.command_parameter_inputs.is_empty() {
+                    self.record_history();
                     self.state.mode = Mode::Processing;
                 }
             }
-            Mode::EnterCommand => {}
-            Mode::Normal => {}
+            Mode::EnterCommand | Mode::History | Mode::HistorySearch => {}
+            Mode::Normal | Mode::Outputs | Mode::RenameOutput | Mode::Search => {}
             Mode::Processing => {
                 self.run_command().await;
                 if self.state.command_parameters.is_none() {
@@ -184,7 +418,7 @@ impl Application {
             }
         }
 
-        if self.state.is_dirty() {
+        if self.state.is_dirty() && !self.state_unreadable {
             let mut state_store = self.state_store.lock().await;
             match state_store.push(&self.state).await {
                 Ok(_) => self.state.reset_dirty(),
@@ -194,6 +428,16 @@ impl Application {
             }
         }
 
+        if self.state.settings_dirty() && !self.settings_unreadable {
+            let mut settings_store = self.settings_store.lock().await;
+            match settings_store.push(&self.state.settings()).await {
+                Ok(_) => self.state.reset_settings_dirty(),
+                Err(e) => {
+                    error!("{e}");
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -201,7 +445,13 @@ impl Application {
         let text: String = self.state.input.drain();
         let spec = self.state.command_parameter_inputs.remove(0);
         match self.state.insert_command_parameter(&text, &spec) {
-            Ok(_) => {}
+            Ok(_) => {
+                if let Some(line) = self.state.command_line.as_mut() {
+                    if !text.trim().is_empty() {
+                        line.set_value(&spec, text.trim());
+                    }
+                }
+            }
             Err(e) => {
                 self.state.reset(true);
                 self.state.error_message = Some(e.to_string());
@@ -212,26 +462,64 @@ impl Application {
 
     pub fn command_name_entered(&mut self) {
         let text: String = self.state.input.drain();
-        match CommandName::from_str(&text) {
-            Ok(it) => {
-                info!("command name entered {it:?}");
-                self.state.setup_for_command(&it);
-                self.state.input.push_to_history(it);
+        match CommandLine::parse(&text) {
+            Ok(line) => {
+                info!("command entered {line:?}");
+                self.state.setup_for_command(line.name());
+                if line.has_values() {
+                    if let Err(e) = self.insert_command_line_parameters(&line) {
+                        self.state.reset(true);
+                        self.state.error_message = Some(e.to_string());
+                        self.state.mode = Mode::Normal;
+                        return;
+                    }
+                }
+                self.state.command_line = Some(line);
             }
-            Err(_) => {
+            Err(e) => {
                 self.state.mode = Mode::Normal;
-                self.state.error_message = Some("Unknown command name".to_string());
+                self.state.error_message = Some(match e {
+                    InteractiveError::ParsingIssue { message } => message,
+                    e => e.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Fills in the parameters given on the command line. Of the rest, only the ones the
+    /// command needs are still asked for, and the others keep their defaults.
+    fn insert_command_line_parameters(
+        &mut self,
+        line: &CommandLine,
+    ) -> Result<(), InteractiveError> {
+        let mut missing = vec![];
+        for (spec, value) in line.parameters() {
+            match value {
+                Some(value) => self.state.insert_command_parameter(&value, &spec)?,
+                None if line.name().is_required(&spec) => missing.push(spec),
+                None => {}
             }
         }
+        self.state.command_parameter_inputs = missing;
+
+        Ok(())
     }
 
-    fn message_sets(&self) -> Vec<&MessageSet> {
+    /// The title and lines of each output that's text, leaving out charts.
+    fn text_outputs(&self) -> Vec<(String, Vec<String>)> {
         self.state
             .output()
             .iter()
             .filter_map(|output| match output {
-                super::Output::MessageSet(message_set) => Some(message_set),
-                super::Output::BarChart(_) => None,
+                Output::MessageSet(message_set) => Some((
+                    message_set.title().to_string(),
+                    message_set.messages().to_vec(),
+                )),
+                Output::Table(table) => Some((
+                    format!("{} ({})", table.title(), table.columns().join(" - ")),
+                    table.messages(),
+                )),
+                Output::BarChart(_) => None,
             })
             .collect()
     }
@@ -245,25 +533,64 @@ impl Application {
         };
 
         let today = Local::now().format("%Y-%m-%d %H:%M:%S");
-        let message_sets: Vec<MessageSet> = self
+        if format.is_image() {
+            self.export_charts(&writer, &format, &today.to_string());
+            self.state.command_parameters = None;
+            return;
+        }
+
+        let outputs = self.state.output().clone();
+        let name = format!("messages_{today}");
+        let result = match render_export(&outputs, &format) {
+            Some(text) => writer
+                .write_text(&text, &name, format.extension_display())
+                .map(|_| true),
+            None => writer.write(&outputs, &name, format).await,
+        };
+        if let Err(e) = result {
+            self.state.error_message = Some(format!("{e}"));
+        }
+        self.state.command_parameters = None;
+    }
+
+    /// Writes every chart in the output to an image file of its own.
+    fn export_charts(&mut self, writer: &FileWriter, format: &Format, today: &str) {
+        let charts: Vec<&super::BarChart> = self
             .state
             .output()
             .iter()
             .filter_map(|output| match output {
-                super::Output::MessageSet(message_set) => Some(message_set.clone()),
-                super::Output::BarChart(_) => None,
+                Output::BarChart(chart) => Some(chart),
+                Output::MessageSet(_) | Output::Table(_) => None,
             })
             .collect();
-        writer
-            .write(&message_sets, &format!("messages_{}", &today), format)
-            .await
-            .unwrap();
-        self.state.command_parameters = None;
+        if charts.is_empty() {
+            self.state.error_message = Some("There are no charts to export".to_string());
+            return;
+        }
+
+        let result = charts
+            .into_iter()
+            .enumerate()
+            .try_for_each(|(index, chart)| {
+                let name = format!("chart_{}_{}_{today}", index + 1, image_name(chart));
+                let extension = format.extension_display();
+                match format {
+                    Format::Png => {
+                        render_png(chart).and_then(|png| writer.write_bytes(&png, &name, extension))
+                    }
+                    _ => writer.write_text(&render_svg(chart), &name, extension),
+                }
+                .map(|_| ())
+            });
+        if let Err(e) = result {
+            self.state.error_message = Some(format!("{e}"));
+        }
     }
 
     pub fn copy_to_clipboard(&mut self) {
-        let message_sets = self.message_sets();
-        if message_sets.is_empty() {
+        let text_outputs = self.text_outputs();
+        if text_outputs.is_empty() {
             return;
         }
 
@@ -276,11 +603,11 @@ impl Application {
                 return;
             }
         };
-        let text: Vec<String> = message_sets
-            .iter()
-            .flat_map(|message_set| {
-                let mut message_set_text: Vec<String> = vec![message_set.title().to_string()];
-                message_set_text.append(&mut message_set.messages().to_vec());
+        let text: Vec<String> = text_outputs
+            .into_iter()
+            .flat_map(|(title, mut messages)| {
+                let mut message_set_text: Vec<String> = vec![title];
+                message_set_text.append(&mut messages);
                 message_set_text
             })
             .collect();
@@ -299,6 +626,244 @@ impl Application {
         }
     }
 
+    /// The title of every output, when it was added and whether it's pinned, for the
+    /// outputs sidebar.
+    pub fn output_summaries(&self) -> Vec<(&str, Option<&OutputDetails>)> {
+        self.state
+            .output()
+            .iter()
+            .map(|output| (output.title(), self.state.output_details(output.id())))
+            .collect()
+    }
+
+    pub fn selected_output(&self) -> usize {
+        self.state.selected_output
+    }
+
+    pub fn start_output_navigation(&mut self) {
+        if self.state.output().is_empty() {
+            return;
+        }
+
+        self.state.selected_output = self.state.current_page;
+        self.state.mode = Mode::Outputs;
+    }
+
+    pub fn stop_output_navigation(&mut self) {
+        self.state.mode = Mode::Normal;
+    }
+
+    pub fn select_next_output(&mut self) {
+        let last = self.state.output().len().saturating_sub(1);
+        self.state.selected_output = (self.state.selected_output + 1).min(last);
+    }
+
+    pub fn select_previous_output(&mut self) {
+        self.state.selected_output = self.state.selected_output.saturating_sub(1);
+    }
+
+    pub fn jump_to_selected_output(&mut self) {
+        self.state.current_page = self.state.selected_output;
+        self.state.mode = Mode::Normal;
+    }
+
+    pub fn close_selected_output(&mut self) {
+        self.state.remove_output(self.state.selected_output);
+        if self.state.output().is_empty() {
+            self.state.mode = Mode::Normal;
+        }
+    }
+
+    pub fn toggle_selected_output_pinned(&mut self) {
+        self.state.toggle_pinned(self.state.selected_output);
+    }
+
+    /// Starts editing the selected output's title, beginning with the title it has now.
+    pub fn start_output_rename(&mut self) {
+        if let Some(output) = self.state.output().get(self.state.selected_output) {
+            let title = output.title().to_string();
+            self.state.input.set_text(&title);
+            self.state.mode = Mode::RenameOutput;
+        }
+    }
+
+    pub fn output_rename_entered(&mut self) {
+        let title = self.state.input.drain();
+        if !title.trim().is_empty() {
+            self.state
+                .rename_output(self.state.selected_output, title.trim());
+        }
+        self.state.mode = Mode::Outputs;
+    }
+
+    pub fn cancel_output_rename(&mut self) {
+        self.state.input.clear();
+        self.state.mode = Mode::Outputs;
+    }
+
+    /// Keeps the command about to run in the history, as a line with all of its
+    /// parameters.
+    fn record_history(&mut self) {
+        if self.state.command_parameters.is_none() {
+            return;
+        }
+        if let Some(line) = self.state.command_line.take() {
+            self.state
+                .push_to_history(HistoryEntry::new(line.to_string()));
+        }
+    }
+
+    /// The commands run, newest first, for the history view.
+    pub fn history_summaries(&self) -> Vec<&HistoryEntry> {
+        self.state
+            .input
+            .history
+            .recent()
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+
+    pub fn selected_history(&self) -> usize {
+        self.state.selected_history
+    }
+
+    pub fn start_history_navigation(&mut self) {
+        if self.state.input.history.is_empty() {
+            return;
+        }
+
+        self.state.selected_history = 0;
+        self.state.mode = Mode::History;
+    }
+
+    pub fn stop_history_navigation(&mut self) {
+        self.state.mode = Mode::Normal;
+    }
+
+    pub fn select_next_history(&mut self) {
+        let last = self.state.input.history.len().saturating_sub(1);
+        self.state.selected_history = (self.state.selected_history + 1).min(last);
+    }
+
+    pub fn select_previous_history(&mut self) {
+        self.state.selected_history = self.state.selected_history.saturating_sub(1);
+    }
+
+    /// The index in the history of the entry selected in the view, which lists the newest
+    /// first.
+    fn selected_history_index(&self) -> Option<usize> {
+        self.state
+            .input
+            .history
+            .len()
+            .checked_sub(self.state.selected_history + 1)
+    }
+
+    pub fn rerun_selected_history(&mut self) {
+        if let Some(index) = self.selected_history_index() {
+            self.rerun_history(index);
+        }
+    }
+
+    pub fn edit_selected_history(&mut self) {
+        if let Some(index) = self.selected_history_index() {
+            self.edit_history(index);
+        }
+    }
+
+    /// Runs the entry again with the parameters parsed from its line. Parameters it still
+    /// needs, as for commands kept before the history had them, are asked for.
+    fn rerun_history(&mut self, index: usize) {
+        let Some(command) = self
+            .state
+            .input
+            .history
+            .get(index)
+            .map(|entry| entry.command().to_string())
+        else {
+            return;
+        };
+        self.state.reset(true);
+        let result = CommandLine::parse(&command).and_then(|line| {
+            self.state.setup_for_command(line.name());
+            self.insert_command_line_parameters(&line)?;
+            self.state.command_line = Some(line);
+            Ok(())
+        });
+        if let Err(e) = result {
+            self.reject_command(e);
+            self.state.mode = Mode::Normal;
+        }
+    }
+
+    /// Puts the entry's line in the input, to change before running it again.
+    fn edit_history(&mut self, index: usize) {
+        let Some(command) = self
+            .state
+            .input
+            .history
+            .get(index)
+            .map(|entry| entry.command().to_string())
+        else {
+            return;
+        };
+        self.state.reset(true);
+        self.state.input.set_text(&command);
+        self.state.mode = Mode::EnterCommand;
+    }
+
+    /// The entry the reverse search has found, if any.
+    pub fn history_search_match(&self) -> Option<&HistoryEntry> {
+        self.state
+            .history_search
+            .as_ref()
+            .and_then(|search| search.current())
+            .and_then(|index| self.state.input.history.get(index))
+    }
+
+    /// Starts a reverse search, or moves on to an older match if one is going.
+    pub fn search_history(&mut self) {
+        if let Some(search) = self.state.history_search.as_mut() {
+            search.older(&self.state.input.history);
+            return;
+        }
+
+        self.state.input.clear();
+        self.state.history_search = Some(HistorySearch::default());
+        self.state.mode = Mode::HistorySearch;
+    }
+
+    pub fn history_search_entered(&mut self) {
+        match self.state.history_search.take().and_then(|it| it.current()) {
+            Some(index) => self.rerun_history(index),
+            None => self.cancel_history_search(),
+        }
+    }
+
+    pub fn edit_history_search_match(&mut self) {
+        match self.state.history_search.take().and_then(|it| it.current()) {
+            Some(index) => self.edit_history(index),
+            None => self.cancel_history_search(),
+        }
+    }
+
+    pub fn cancel_history_search(&mut self) {
+        self.state.history_search = None;
+        self.state.input.clear();
+        self.state.mode = Mode::EnterCommand;
+    }
+
+    fn update_history_search(&mut self) {
+        if !matches!(self.state.mode, Mode::HistorySearch) {
+            return;
+        }
+
+        let query = self.state.input.current().to_string();
+        if let Some(search) = self.state.history_search.as_mut() {
+            search.set_query(&query, &self.state.input.history);
+        }
+    }
+
     pub fn start_command_input(&mut self) {
         self.state.reset(true);
         self.state.mode = Mode::EnterCommand;
@@ -316,9 +881,30 @@ impl Application {
                 month,
                 count: artist_count,
                 min_listens,
-            }) => self.run_random_artists(year, month, artist_count, min_listens),
-            Some(CommandParameters::ArtistSongs { name }) => {
-                self.run_artist_songs(&name.unwrap_or_default());
+                from,
+                to,
+            }) => match Self::period_parameter(from, to, year.is_some() || month.is_some()) {
+                Ok(period) => {
+                    self.run_random_artists(year, month, artist_count, min_listens, period)
+                }
+                Err(e) => self.reject_command(e),
+            },
+            Some(CommandParameters::ArtistSongs { name, album_name }) => {
+                self.run_artist_songs(&name.unwrap_or_default(), album_name)
+                    .await;
+            }
+            Some(CommandParameters::Artist { name, count }) => {
+                self.run_artist(&name.unwrap_or_default(), count);
+            }
+            Some(CommandParameters::Song {
+                artist_name,
+                track_name,
+            }) => {
+                self.run_song(
+                    &artist_name.unwrap_or_default(),
+                    &track_name.unwrap_or_default(),
+                )
+                .await;
             }
             Some(CommandParameters::ArtistsOnDay { date }) => {
                 self.run_artists_on_day(date.unwrap_or_default());
@@ -334,11 +920,40 @@ impl Application {
                 count: artist_count,
                 year,
                 month,
-            }) => {
-                self.run_top_artists(artist_count, year, month);
-            }
-            Some(CommandParameters::TopSongs { count, year }) => self.run_top_songs(count, year),
-            Some(CommandParameters::MostSkipped { count }) => self.run_most_skipped(count),
+                metric,
+                from,
+                to,
+            }) => match Self::period_parameter(from, to, year.is_some() || month.is_some()) {
+                Ok(period) => self.run_top_artists(artist_count, year, month, metric, period),
+                Err(e) => self.reject_command(e),
+            },
+            Some(CommandParameters::TopSongs {
+                count,
+                year,
+                metric,
+                from,
+                to,
+            }) => match Self::period_parameter(from, to, year.is_some()) {
+                Ok(period) => self.run_top_songs(count, year, metric, period),
+                Err(e) => self.reject_command(e),
+            },
+            Some(CommandParameters::MostSkipped {
+                count,
+                year,
+                month,
+                artist_name,
+                from,
+                to,
+            }) => match Self::period_parameter(from, to, year.is_some() || month.is_some()) {
+                Ok(period) => self.run_most_skipped(count, year, month, artist_name, period),
+                Err(e) => self.reject_command(e),
+            },
+            Some(CommandParameters::SkipRate {
+                count,
+                min_plays,
+                year,
+                month,
+            }) => self.run_skip_rate(count, min_plays, year, month),
             Some(CommandParameters::Export {
                 output_folder,
                 format,
@@ -347,16 +962,95 @@ impl Application {
                 year,
                 breakdown,
                 artist_name,
-            }) => {
-                self.run_chart(year, breakdown, artist_name);
-            }
-            Some(CommandParameters::TopAlbums { count, year }) => {
-                self.run_top_albums(count, year);
+                track_name,
+                from,
+                to,
+            }) => match Self::period_parameter(from, to, year.is_some()) {
+                Ok(period) => self.run_chart(year, breakdown, artist_name, track_name, period),
+                Err(e) => self.reject_command(e),
+            },
+            Some(CommandParameters::TopAlbums {
+                count,
+                year,
+                metric,
+                from,
+                to,
+            }) => match Self::period_parameter(from, to, year.is_some()) {
+                Ok(period) => self.run_top_albums(count, year, metric, period),
+                Err(e) => self.reject_command(e),
+            },
+            Some(CommandParameters::DiscoveredArtists { year, month }) => {
+                self.run_discovered_artists(year, month);
             }
-            Some(CommandParameters::ClearOutput) => {
-                self.run_clear_output();
+            Some(CommandParameters::NewArtistsChart { year }) => {
+                self.run_new_artists_chart(year);
             }
-            None => {}
+            Some(CommandParameters::ForgottenArtists {
+                months,
+                min_listens,
+                count,
+            }) => self.run_forgotten_artists(months, min_listens, count),
+            Some(CommandParameters::Streaks {
+                count,
+                year,
+                artist_name,
+            }) => self.run_streaks(count, year, artist_name),
+            Some(CommandParameters::Wrapped {
+                year,
+                count,
+                output_folder,
+                report_format,
+            }) => self.run_wrapped(year, count, output_folder, report_format),
+            Some(CommandParameters::Compare {
+                first,
+                second,
+                count,
+                metric,
+            }) => self.run_compare(first, second, count, metric),
+            Some(CommandParameters::Query { query }) => self.run_query(query).await,
+            Some(CommandParameters::AliasArtist { alias, artist_name }) => {
+                self.run_alias_artist(alias, artist_name).await;
+            }
+            Some(CommandParameters::UnaliasArtist { alias }) => {
+                self.run_unalias_artist(alias).await;
+            }
+            Some(CommandParameters::NormalizeTitles { rules }) => {
+                self.run_normalize_titles(rules).await;
+            }
+            Some(CommandParameters::PreviewTitles { rules, count }) => {
+                self.run_preview_titles(rules, count).await;
+            }
+            Some(CommandParameters::ArtistCredits {
+                separators,
+                exceptions,
+            }) => {
+                self.run_artist_credits(separators, exceptions).await;
+            }
+            Some(CommandParameters::Reclassify {
+                service,
+                min_listen_ms,
+                min_percent,
+                honour_skipped_flag,
+                honour_reason_end,
+            }) => {
+                self.run_reclassify(
+                    &service,
+                    min_listen_ms,
+                    min_percent,
+                    honour_skipped_flag,
+                    honour_reason_end,
+                )
+                .await;
+            }
+            Some(CommandParameters::SaveQuery { name, command }) => {
+                self.run_save_query(name, command);
+            }
+            Some(CommandParameters::RunQuery { name }) => self.run_saved_query(name),
+            Some(CommandParameters::DeleteQuery { name }) => self.run_delete_query(name),
+            Some(CommandParameters::ClearOutput) => {
+                self.run_clear_output();
+            }
+            None => {}
         }
     }
 
@@ -392,6 +1086,295 @@ impl Application {
         }
     }
 
+    async fn run_reclassify(
+        &mut self,
+        service: &Option<String>,
+        min_listen_ms: Option<u64>,
+        min_percent: Option<f64>,
+        honour_skipped_flag: Option<bool>,
+        honour_reason_end: Option<bool>,
+    ) {
+        let policy = self.state.classification_mut().policy_mut(service);
+        if let Some(ms) = min_listen_ms {
+            policy.min_listen_ms = ms;
+        }
+        if let Some(percent) = min_percent {
+            policy.min_percent = percent;
+        }
+        if let Some(honour) = honour_skipped_flag {
+            policy.honour_skipped_flag = honour;
+        }
+        if let Some(honour) = honour_reason_end {
+            policy.honour_reason_end = honour;
+        }
+
+        let messages = match self.rebuild_projections().await {
+            Ok(_) => {
+                let mut messages = self.state.classification().descriptions();
+                messages.push(format!(
+                    "Counted {} plays and {} skips",
+                    self.processor.artists_counts.total_count(&None),
+                    self.processor.artists_counts.total_skip_count()
+                ));
+                messages
+            }
+            Err(e) => vec![format!("Error: {e}")],
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages("Reclassified listens", messages)),
+        );
+        self.state.command_parameters = None;
+    }
+
+    async fn run_alias_artist(&mut self, alias: Option<String>, artist_name: Option<String>) {
+        let messages = match (alias, artist_name) {
+            (Some(alias), Some(artist_name)) => {
+                match self.state.aliases_mut().add(&alias, &artist_name) {
+                    Some(artist_alias) => {
+                        self.rebuild_with_message(&artist_alias.to_string()).await
+                    }
+                    None => vec![format!("{alias} is already counted as itself")],
+                }
+            }
+            (Some(alias), None) => vec![format!("No artist given to count {alias} as")],
+            (None, _) => {
+                let descriptions = self.state.aliases().descriptions();
+                if descriptions.is_empty() {
+                    vec!["No aliases found".to_string()]
+                } else {
+                    descriptions
+                }
+            }
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages("Artist aliases", messages)),
+        );
+        self.state.command_parameters = None;
+    }
+
+    async fn run_unalias_artist(&mut self, alias: Option<String>) {
+        let removed = alias
+            .as_ref()
+            .and_then(|alias| self.state.aliases_mut().remove(alias));
+        let messages = match (removed, alias) {
+            (Some(artist_alias), _) => {
+                self.rebuild_with_message(&format!("Removed {artist_alias}"))
+                    .await
+            }
+            (None, Some(alias)) => vec![format!("No alias found for {alias}")],
+            (None, None) => vec!["No alias given".to_string()],
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages("Artist aliases", messages)),
+        );
+        self.state.command_parameters = None;
+    }
+
+    async fn run_normalize_titles(&mut self, rules: Option<TitleNormalizer>) {
+        let messages = match rules {
+            Some(rules) => {
+                self.state.set_titles(rules);
+                let mut messages = self.state.titles().descriptions();
+                messages.extend(self.rebuild_with_message("Title rules updated").await);
+                messages
+            }
+            None => self.state.titles().descriptions(),
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages("Title rules", messages)),
+        );
+        self.state.command_parameters = None;
+    }
+
+    async fn run_artist_credits(
+        &mut self,
+        separators: Option<Vec<String>>,
+        exceptions: Option<Vec<String>>,
+    ) {
+        let messages = if separators.is_none() && exceptions.is_none() {
+            self.state.credits().descriptions()
+        } else {
+            let credits = self.state.credits_mut();
+            if let Some(separators) = separators {
+                credits.separators = separators;
+            }
+            if let Some(exceptions) = exceptions {
+                credits.exceptions = exceptions;
+            }
+            let mut messages = self.state.credits().descriptions();
+            messages.extend(self.rebuild_with_message("Artist credits updated").await);
+            messages
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages("Artist credits", messages)),
+        );
+        self.state.command_parameters = None;
+    }
+
+    async fn run_preview_titles(&mut self, rules: Option<TitleNormalizer>, count: usize) {
+        let rules = rules.unwrap_or_else(|| self.state.titles().clone());
+        let event_stream = self.store.lock().await.get_events("listens").await;
+
+        let message_sets = match event_stream {
+            Ok(event_stream) => {
+                let mut tracks: Vec<(ArtistName, String)> = Vec::new();
+                let mut albums: Vec<(ArtistName, String)> = Vec::new();
+                for listen in event_stream
+                    .events
+                    .iter()
+                    .filter_map(|event| Listen::from_event(event, self.state.classification()))
+                {
+                    let artist_name = self.state.aliases().resolve(&listen.artist_name);
+                    if let Some(album_name) = listen.album_name {
+                        albums.push((artist_name.clone(), album_name.0));
+                    }
+                    tracks.push((artist_name, listen.track_name.0));
+                }
+
+                vec![
+                    MessageSet::with_messages(
+                        &format!("Song titles that would merge (rules: {rules}, count: {count})"),
+                        title_merge_messages(
+                            tracks,
+                            |title| rules.track_name(&TrackName(title.to_string())).0,
+                            count,
+                        ),
+                    ),
+                    MessageSet::with_messages(
+                        &format!("Album titles that would merge (rules: {rules}, count: {count})"),
+                        title_merge_messages(
+                            albums,
+                            |title| rules.album_name(&AlbumName(title.to_string())).0,
+                            count,
+                        ),
+                    ),
+                ]
+            }
+            Err(e) => vec![MessageSet::with_messages(
+                "Title merges",
+                vec![format!("Error: {e}")],
+            )],
+        };
+
+        for message_set in message_sets.into_iter().rev() {
+            self.state.insert_output(0, Output::MessageSet(message_set));
+        }
+        self.state.command_parameters = None;
+    }
+
+    async fn rebuild_with_message(&mut self, message: &str) -> Vec<String> {
+        match self.rebuild_projections().await {
+            Ok(_) => vec![
+                message.to_string(),
+                format!(
+                    "Rebuilt statistics from {} plays and {} skips",
+                    self.processor.artists_counts.total_count(&None),
+                    self.processor.artists_counts.total_skip_count()
+                ),
+            ],
+            Err(e) => vec![format!("Error: {e}")],
+        }
+    }
+
+    /// Saves the command given, or else the last one run, so it can be run by name.
+    fn run_save_query(&mut self, name: Option<String>, command: Option<String>) {
+        let command = command.or_else(|| {
+            self.state
+                .input
+                .history
+                .recent()
+                .map(|(_, entry)| entry)
+                .find(|entry| {
+                    CommandLine::parse(entry.command())
+                        .is_ok_and(|line| !line.name().is_saved_query_command())
+                })
+                .map(|entry| entry.command().to_string())
+        });
+        let messages = match (name, command) {
+            (None, _) => self.state.saved_queries().descriptions(),
+            (Some(_), None) => vec!["No command to save".to_string()],
+            (Some(name), Some(command)) => match CommandLine::parse(&command) {
+                Ok(line) if line.name().is_saved_query_command() => {
+                    vec!["A saved query can't save, run or delete another one".to_string()]
+                }
+                Ok(_) => {
+                    self.state.saved_queries_mut().save(&name, &command);
+                    vec![format!("Saved {name} - {command}")]
+                }
+                Err(e) => vec![e.to_string()],
+            },
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages("Saved queries", messages)),
+        );
+        self.state.command_parameters = None;
+    }
+
+    /// Sets up the saved command to run next, working its parameters out again so
+    /// relative ones like `this month` are up to date. Parameters it still needs are
+    /// asked for.
+    fn run_saved_query(&mut self, name: Option<String>) {
+        let command = name
+            .as_ref()
+            .and_then(|name| self.state.saved_queries().get(name))
+            .map(|command| command.to_string());
+        let message = match (name, command) {
+            (None, _) => None,
+            (Some(name), None) => Some(format!("No saved query called {name}")),
+            (Some(_), Some(command)) => match CommandLine::parse(&command) {
+                Ok(line) if line.name().is_saved_query_command() => {
+                    Some("A saved query can't save, run or delete another one".to_string())
+                }
+                Ok(line) => {
+                    self.state.setup_for_command(line.name());
+                    match self.insert_command_line_parameters(&line) {
+                        Ok(_) => return,
+                        Err(e) => Some(e.to_string()),
+                    }
+                }
+                Err(e) => Some(e.to_string()),
+            },
+        };
+
+        let messages = match message {
+            Some(message) => vec![message],
+            None => self.state.saved_queries().descriptions(),
+        };
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages("Saved queries", messages)),
+        );
+        self.state.command_parameters = None;
+    }
+
+    fn run_delete_query(&mut self, name: Option<String>) {
+        let message = match name {
+            Some(name) => match self.state.saved_queries_mut().remove(&name) {
+                Some(_) => format!("Deleted {name}"),
+                None => format!("No saved query called {name}"),
+            },
+            None => "No saved query given".to_string(),
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages("Saved queries", vec![message])),
+        );
+        self.state.command_parameters = None;
+    }
+
     fn run_clear_output(&mut self) {
         self.state.clear_output();
         self.state.command_parameters = None;
@@ -399,12 +1382,24 @@ impl Application {
 
     async fn run_process_listens(&mut self, mut files: Vec<PathBuf>) {
         if let Some(path) = files.pop() {
-            let result = processing::process_file(&path, &self.store, &self.repository).await;
+            let result = processing::process_file(
+                &path,
+                &self.store,
+                &self.repository,
+                self.state.classification(),
+            )
+            .await;
 
             let message = match result {
                 Ok(events) => {
                     let count = events.len();
 
+                    self.listens
+                        .lock()
+                        .await
+                        .project_events(&events, &self.state.listen_rules())
+                        .await;
+
                     events
                         .into_iter()
                         .for_each(|event| self.processor.process_event(&event));
@@ -431,7 +1426,7 @@ impl Application {
                             None
                         }
                     }
-                    Output::BarChart(_) => None,
+                    Output::BarChart(_) | Output::Table(_) => None,
                 }) {
                 Some(it) => it.append_messages(&mut messages),
                 None => {
@@ -454,7 +1449,7 @@ impl Application {
                             None
                         }
                     }
-                    Output::BarChart(_) => None,
+                    Output::BarChart(_) | Output::Table(_) => None,
                 }) {
                 Some(it) => it.push_message("Done processing"),
                 None => {
@@ -474,16 +1469,109 @@ impl Application {
         year: Option<i32>,
         breakdown: BarBreakdown,
         artist_name: Option<String>,
+        track_name: Option<String>,
+        period: Option<Period>,
     ) {
-        match breakdown {
-            BarBreakdown::Month => self.monthly_bar_chart(year, artist_name),
-            BarBreakdown::Weekday => self.weekday_bar_chart(year, artist_name),
+        match (breakdown, period) {
+            (breakdown, Some(period)) => {
+                self.period_bar_chart(&period, breakdown, artist_name, track_name)
+            }
+            (BarBreakdown::Month, None) => self.monthly_bar_chart(year, artist_name, track_name),
+            (BarBreakdown::Weekday, None) => self.weekday_bar_chart(year, artist_name, track_name),
         }
 
         self.state.command_parameters = None;
     }
 
-    fn weekday_bar_chart(&mut self, year: Option<i32>, artist_name: Option<String>) {
+    fn period_bar_chart(
+        &mut self,
+        period: &Period,
+        breakdown: BarBreakdown,
+        artist_name: Option<String>,
+        track_name: Option<String>,
+    ) {
+        let day_counts = self.processor.day_counts_in(period);
+
+        let (title, data_points) = match breakdown {
+            BarBreakdown::Month => {
+                let mut month_counts: HashMap<(i32, u32), u64> = HashMap::new();
+                for (date, day_count) in day_counts.iter() {
+                    *month_counts.entry((date.year(), date.month())).or_default() += day_count
+                        .artists_counts
+                        .play_count(&artist_name, &track_name);
+                }
+                // Months without plays show as zero, across the whole period when it is
+                // bounded and between the first and last listen otherwise.
+                let first = day_counts.first().map(|(date, _)| {
+                    let start = if period.start == NaiveDate::MIN {
+                        *date
+                    } else {
+                        period.start
+                    };
+                    (start.year(), start.month())
+                });
+                let last = day_counts.last().map(|(date, _)| {
+                    let end = if period.end == NaiveDate::MAX {
+                        *date
+                    } else {
+                        period.end
+                    };
+                    (end.year(), end.month())
+                });
+                let data_points = match (first, last) {
+                    (Some(first), Some(last)) => months_between(first, last)
+                        .into_iter()
+                        .map(|(year, month)| {
+                            BarDataPoint::new(
+                                format!("{year}-{month:02}"),
+                                month_counts
+                                    .get(&(year, month))
+                                    .copied()
+                                    .unwrap_or_default(),
+                            )
+                        })
+                        .collect::<Vec<BarDataPoint>>(),
+                    _ => Vec::new(),
+                };
+                (format!("Monthly Bar Chart (period: {period})"), data_points)
+            }
+            BarBreakdown::Weekday => {
+                let mut weekday_counts: HashMap<Weekday, u64> = HashMap::new();
+                for (date, day_count) in day_counts.iter() {
+                    *weekday_counts.entry(date.weekday()).or_default() += day_count
+                        .artists_counts
+                        .play_count(&artist_name, &track_name);
+                }
+                let mut entries: Vec<(Weekday, u64)> = weekday_counts.into_iter().collect();
+                entries.sort_by_key(|(weekday, _)| order_in_week(weekday));
+                (
+                    format!("Weekday Bar Chart (period: {period})"),
+                    entries
+                        .into_iter()
+                        .map(|(weekday, count)| BarDataPoint::new(weekday.to_string(), count))
+                        .collect::<Vec<BarDataPoint>>(),
+                )
+            }
+        };
+
+        if data_points.is_empty() {
+            let message_set =
+                MessageSet::with_messages(&title, vec!["No data for period".to_string()]);
+            self.state.insert_output(0, Output::MessageSet(message_set));
+        } else {
+            self.state.insert_output(
+                0,
+                Output::BarChart(super::BarChart::with_data_points(&title, data_points)),
+            );
+        }
+    }
+
+    fn weekday_bar_chart(
+        &mut self,
+        year: Option<i32>,
+        artist_name: Option<String>,
+        track_name: Option<String>,
+    ) {
         let all_year_counts = if let Some(y) = year {
             self.processor
                 .year_count(y)
@@ -508,12 +1596,9 @@ impl Application {
             for year_counts in all_year_counts {
                 for month_count in year_counts.month_counts().iter() {
                     for day_count in month_count.day_counts().iter() {
-                        weekday_counts
-                            .entry(day_count.weekday)
-                            .and_modify(|entry| {
-                                *entry += day_count.artists_counts.total_count(&artist_name)
-                            })
-                            .or_insert_with(|| day_count.artists_counts.total_count(&artist_name));
+                        *weekday_counts.entry(day_count.weekday).or_default() += day_count
+                            .artists_counts
+                            .play_count(&artist_name, &track_name);
                     }
                 }
             }
@@ -530,7 +1615,12 @@ impl Application {
         }
     }
 
-    fn monthly_bar_chart(&mut self, year: Option<i32>, artist_name: Option<String>) {
+    fn monthly_bar_chart(
+        &mut self,
+        year: Option<i32>,
+        artist_name: Option<String>,
+        track_name: Option<String>,
+    ) {
         let title = if let Some(y) = year {
             format!("Monthly Bar Chart (year: {y})")
         } else {
@@ -565,7 +1655,9 @@ impl Application {
                 .map(|month_count| {
                     BarDataPoint::new(
                         format!("{:02}", month_count.month),
-                        month_count.artists_counts.total_count(&artist_name),
+                        month_count
+                            .artists_counts
+                            .play_count(&artist_name, &track_name),
                     )
                 })
                 .collect();
@@ -576,44 +1668,66 @@ impl Application {
         }
     }
 
-    fn run_top_artists(&mut self, artist_count: usize, year: Option<i32>, month: Option<u32>) {
+    fn run_top_artists(
+        &mut self,
+        artist_count: usize,
+        year: Option<i32>,
+        month: Option<u32>,
+        metric: Metric,
+        period: Option<Period>,
+    ) {
+        if let Some(period) = period {
+            let title =
+                format!("Top artists (period: {period}, count: {artist_count}, by: {metric})");
+            let counters = self
+                .processor
+                .artists_counts_in(&period)
+                .top(artist_count, &metric);
+            self.insert_period_table(
+                Table::ranked_artists(&title, &counters, &metric),
+                "No artists found",
+            );
+            self.state.command_parameters = None;
+            return;
+        }
+
         match (year, month) {
-            (None, None) => self.top_artists(artist_count),
-            (None, Some(m)) => self.top_artists_for_month(artist_count, m),
-            (Some(y), None) => self.top_artists_for_year(artist_count, y),
-            (Some(y), Some(m)) => self.top_artists_for_year_month(artist_count, y, m),
+            (None, None) => self.top_artists(artist_count, &metric),
+            (None, Some(m)) => self.top_artists_for_month(artist_count, m, &metric),
+            (Some(y), None) => self.top_artists_for_year(artist_count, y, &metric),
+            (Some(y), Some(m)) => self.top_artists_for_year_month(artist_count, y, m, &metric),
         }
 
         self.state.command_parameters = None;
     }
 
-    fn top_artists_for_month(&mut self, artist_count: usize, month: u32) {
-        let title = format!("Top artists (month: {month}, count: {artist_count})");
+    fn top_artists_for_month(&mut self, artist_count: usize, month: u32, metric: &Metric) {
+        let title = format!("Top artists (month: {month}, count: {artist_count}, by: {metric})");
         let month_counts = self.processor.month_counts(month);
         let artist_counts = MonthCounts::merge_to_counts(month_counts);
-        let artist_song_counters = artist_counts.top(artist_count);
-        let messages: Vec<String> = artist_song_counters
-            .into_iter()
-            .map(|counter| counter.total_plays_display())
-            .collect();
+        let artist_song_counters = artist_counts.top(artist_count, metric);
         self.state.insert_output(
             0,
-            Output::MessageSet(MessageSet::with_messages(&title, messages)),
+            Output::Table(Table::ranked_artists(&title, &artist_song_counters, metric)),
         );
     }
 
-    fn top_artists_for_year_month(&mut self, artist_count: usize, year: i32, month: u32) {
-        let title = format!("Top artists (year: {year}, month: {month}, count: {artist_count})");
+    fn top_artists_for_year_month(
+        &mut self,
+        artist_count: usize,
+        year: i32,
+        month: u32,
+        metric: &Metric,
+    ) {
+        let title = format!(
+            "Top artists (year: {year}, month: {month}, count: {artist_count}, by: {metric})"
+        );
         if let Some(year_counts) = self.processor.year_count(year) {
             if let Some(month_counts) = year_counts.month_count(month) {
-                let artist_song_counters = month_counts.artists_counts.top(artist_count);
-                let messages: Vec<String> = artist_song_counters
-                    .into_iter()
-                    .map(|counter| counter.total_plays_display())
-                    .collect();
+                let artist_song_counters = month_counts.artists_counts.top(artist_count, metric);
                 self.state.insert_output(
                     0,
-                    Output::MessageSet(MessageSet::with_messages(&title, messages)),
+                    Output::Table(Table::ranked_artists(&title, &artist_song_counters, metric)),
                 )
             } else {
                 self.state.insert_output(
@@ -635,17 +1749,13 @@ impl Application {
         }
     }
 
-    fn top_artists_for_year(&mut self, artist_count: usize, year: i32) {
-        let title = format!("Top artists (year: {year}, count: {artist_count})");
+    fn top_artists_for_year(&mut self, artist_count: usize, year: i32, metric: &Metric) {
+        let title = format!("Top artists (year: {year}, count: {artist_count}, by: {metric})");
         if let Some(year_counts) = self.processor.year_count(year) {
-            let artist_song_counters = year_counts.artists_counts.top(artist_count);
-            let messages: Vec<String> = artist_song_counters
-                .into_iter()
-                .map(|counter| counter.total_plays_display())
-                .collect();
+            let artist_song_counters = year_counts.artists_counts.top(artist_count, metric);
             self.state.insert_output(
                 0,
-                Output::MessageSet(MessageSet::with_messages(&title, messages)),
+                Output::Table(Table::ranked_artists(&title, &artist_song_counters, metric)),
             )
         } else {
             self.state.insert_output(
@@ -658,102 +1768,177 @@ impl Application {
         }
     }
 
-    fn top_artists(&mut self, artist_count: usize) {
-        let title = format!("Top artists (count: {artist_count})");
-        let artist_counters = self.processor.artists_counts.top(artist_count);
-        let messages: Vec<String> = artist_counters
-            .into_iter()
-            .map(|counter| counter.total_plays_display())
-            .collect();
+    fn top_artists(&mut self, artist_count: usize, metric: &Metric) {
+        let title = format!("Top artists (count: {artist_count}, by: {metric})");
+        let artist_counters = self.processor.artists_counts.top(artist_count, metric);
         self.state.insert_output(
             0,
-            Output::MessageSet(MessageSet::with_messages(&title, messages)),
+            Output::Table(Table::ranked_artists(&title, &artist_counters, metric)),
         )
     }
 
-    fn run_top_albums(&mut self, count: usize, year: Option<i32>) {
-        if let Some(y) = year {
-            let title = format!("Top albums (year: {y}, count: {count})");
+    fn run_top_albums(
+        &mut self,
+        count: usize,
+        year: Option<i32>,
+        metric: Metric,
+        period: Option<Period>,
+    ) {
+        if let Some(period) = period {
+            let title = format!("Top albums (period: {period}, count: {count}, by: {metric})");
+            let album_counts = self
+                .processor
+                .artists_counts_in(&period)
+                .top_albums(count, &metric);
+            self.insert_period_table(
+                Table::ranked_albums(&title, &album_counts, &metric),
+                "No albums found",
+            );
+        } else if let Some(y) = year {
+            let title = format!("Top albums (year: {y}, count: {count}, by: {metric})");
             if let Some(year_counts) = self.processor.year_count(y) {
-                let artist_album_counts = year_counts.artists_counts.top_albums(count);
-                let messages: Vec<String> = artist_album_counts
-                    .into_iter()
-                    .map(|count| format!("{count}"))
-                    .collect();
+                let artist_album_counts = year_counts.artists_counts.top_albums(count, &metric);
                 self.state.insert_output(
                     0,
-                    Output::MessageSet(MessageSet::with_messages(&title, messages)),
+                    Output::Table(Table::ranked_albums(&title, &artist_album_counts, &metric)),
                 )
             } else {
                 self.state.insert_output(
                     0,
                     Output::MessageSet(MessageSet::with_messages(
                         &title,
-                        vec!["No artists found".to_string()],
+                        vec!["No albums found".to_string()],
                     )),
                 );
             }
         } else {
-            let title = format!("Top albums (count: {count})");
-            let artist_counts = self.processor.artists_counts.top_albums(count);
-            let messages: Vec<String> = artist_counts
-                .into_iter()
-                .map(|count| format!("{count}"))
-                .collect();
+            let title = format!("Top albums (count: {count}, by: {metric})");
+            let artist_counts = self.processor.artists_counts.top_albums(count, &metric);
             self.state.insert_output(
                 0,
-                Output::MessageSet(MessageSet::with_messages(&title, messages)),
+                Output::Table(Table::ranked_albums(&title, &artist_counts, &metric)),
             )
         }
 
         self.state.command_parameters = None;
     }
 
-    fn run_top_songs(&mut self, count: usize, year: Option<i32>) {
-        if let Some(y) = year {
-            let title = format!("Top songs (year: {y}, count: {count})");
+    fn run_top_songs(
+        &mut self,
+        count: usize,
+        year: Option<i32>,
+        metric: Metric,
+        period: Option<Period>,
+    ) {
+        if let Some(period) = period {
+            let title = format!("Top songs (period: {period}, count: {count}, by: {metric})");
+            let song_counts = self
+                .processor
+                .artists_counts_in(&period)
+                .top_songs(count, &metric);
+            self.insert_period_table(
+                Table::ranked_songs(&title, &song_counts, &metric),
+                "No songs found",
+            );
+        } else if let Some(y) = year {
+            let title = format!("Top songs (year: {y}, count: {count}, by: {metric})");
             if let Some(year_counts) = self.processor.year_count(y) {
-                let artist_song_counters = year_counts.artists_counts.top_songs(count);
-                let messages: Vec<String> = artist_song_counters
-                    .into_iter()
-                    .map(|count| format!("{count}"))
-                    .collect();
+                let artist_song_counters = year_counts.artists_counts.top_songs(count, &metric);
                 self.state.insert_output(
                     0,
-                    Output::MessageSet(MessageSet::with_messages(&title, messages)),
+                    Output::Table(Table::ranked_songs(&title, &artist_song_counters, &metric)),
                 )
             } else {
                 self.state.insert_output(
                     0,
                     Output::MessageSet(MessageSet::with_messages(
                         &title,
-                        vec!["No artists found".to_string()],
+                        vec!["No songs found".to_string()],
                     )),
                 );
             }
         } else {
-            let title = format!("Top songs (count: {count})");
-            let artist_counters = self.processor.artists_counts.top_songs(count);
-            let messages: Vec<String> = artist_counters
-                .into_iter()
-                .map(|count| format!("{count}"))
-                .collect();
+            let title = format!("Top songs (count: {count}, by: {metric})");
+            let artist_counters = self.processor.artists_counts.top_songs(count, &metric);
             self.state.insert_output(
                 0,
-                Output::MessageSet(MessageSet::with_messages(&title, messages)),
+                Output::Table(Table::ranked_songs(&title, &artist_counters, &metric)),
             )
         }
 
         self.state.command_parameters = None;
     }
 
-    fn run_most_skipped(&mut self, count: usize) {
-        let most_skipped = self.processor.top_skipped(count);
-        let title = format!("Most skipped songs: (count: {count})");
-        let messages: Vec<String> = most_skipped
-            .iter()
-            .map(|song_count| format!("{song_count}"))
-            .collect();
+    fn run_most_skipped(
+        &mut self,
+        count: usize,
+        year: Option<i32>,
+        month: Option<u32>,
+        artist_name: Option<String>,
+        period: Option<Period>,
+    ) {
+        let most_skipped = self
+            .processor
+            .top_skipped(count, year, month, &period, &artist_name);
+        let year_text = year
+            .map(|y| format!("{y}"))
+            .unwrap_or_else(|| "None".to_string());
+        let month_text = month
+            .map(|m| format!("{m}"))
+            .unwrap_or_else(|| "None".to_string());
+        let range_text = match &period {
+            Some(period) => format!("period: {period}"),
+            None => format!("year: {year_text}, month: {month_text}"),
+        };
+        let title = match &artist_name {
+            Some(name) => {
+                format!("Most skipped songs (artist: {name}, {range_text}, count: {count})")
+            }
+            None => format!("Most skipped songs ({range_text}, count: {count})"),
+        };
+        let output = if most_skipped.is_empty() {
+            Output::MessageSet(MessageSet::with_messages(
+                &title,
+                vec!["No skipped songs found".to_string()],
+            ))
+        } else {
+            Output::Table(Table::ranked_songs_with_count(
+                &title,
+                &most_skipped,
+                "Skips",
+                &Metric::Plays,
+            ))
+        };
+        self.state.insert_output(0, output);
+
+        self.state.command_parameters = None;
+    }
+
+    fn run_skip_rate(
+        &mut self,
+        count: usize,
+        min_plays: u64,
+        year: Option<i32>,
+        month: Option<u32>,
+    ) {
+        let skip_rates = self.processor.top_skip_rates(count, min_plays, year, month);
+        let year_text = year
+            .map(|y| format!("{y}"))
+            .unwrap_or_else(|| "None".to_string());
+        let month_text = month
+            .map(|m| format!("{m}"))
+            .unwrap_or_else(|| "None".to_string());
+        let title = format!(
+            "Highest skip rate songs (year: {year_text}, month: {month_text}, min plays: {min_plays}, count: {count})"
+        );
+        let messages: Vec<String> = if skip_rates.is_empty() {
+            vec!["No skipped songs found".to_string()]
+        } else {
+            skip_rates
+                .iter()
+                .map(|skip_rate| format!("{skip_rate}"))
+                .collect()
+        };
         self.state.insert_output(
             0,
             Output::MessageSet(MessageSet::with_messages(&title, messages)),
@@ -768,10 +1953,13 @@ impl Application {
         month: Option<u32>,
         artist_count: usize,
         min_listens: u64,
+        period: Option<Period>,
     ) {
-        let mut artist_names: HashSet<String> = HashSet::new();
+        let mut artist_plays: HashMap<ArtistName, u64> = HashMap::new();
 
-        let year_counts = if let Some(y) = year {
+        let year_counts = if period.is_some() {
+            Vec::new()
+        } else if let Some(y) = year {
             self.processor
                 .year_count(y)
                 .map(|year_count| vec![year_count])
@@ -791,26 +1979,94 @@ impl Application {
             .unwrap_or_default();
 
             for artist_count in artist_counts.iter() {
-                if !artist_names.contains(&artist_count.artist_name.0) {
-                    artist_names.insert(artist_count.artist_name.0.clone());
-                }
+                *artist_plays
+                    .entry(artist_count.artist_name.clone())
+                    .or_default() += artist_count.total_song_plays();
             }
         }
 
-        let year_text = year
-            .map(|y| format!("{y}"))
-            .unwrap_or_else(|| "None".to_string());
-        let month_text = month
-            .map(|m| format!("{m}"))
-            .unwrap_or_else(|| "None".to_string());
+        if let Some(period) = &period {
+            for artist_count in self
+                .processor
+                .artists_counts_in(period)
+                .over_min_plays(min_listens)
+            {
+                artist_plays.insert(
+                    artist_count.artist_name.clone(),
+                    artist_count.total_song_plays(),
+                );
+            }
+        }
+
+        // Favour artists that were played a lot, but haven't been listened to in a while.
+        let today = Local::now().naive_local().date();
+        let candidates: Vec<(ArtistName, f64)> = artist_plays
+            .into_iter()
+            .map(|(artist_name, plays)| {
+                let months_since_listen = self
+                    .processor
+                    .listen_timeline
+                    .artist_span(&artist_name)
+                    .map(|span| (today - span.last.date()).num_days().max(0) / 30)
+                    .unwrap_or_default();
+                let weight = plays as f64 * (1 + months_since_listen) as f64;
+                (artist_name, weight)
+            })
+            .collect();
+
+        let year_text = year
+            .map(|y| format!("{y}"))
+            .unwrap_or_else(|| "None".to_string());
+        let month_text = month
+            .map(|m| format!("{m}"))
+            .unwrap_or_else(|| "None".to_string());
+        let range_text = match &period {
+            Some(period) => format!("period: {period}"),
+            None => format!("year: {year_text}, month: {month_text}"),
+        };
         let title = format!(
-            "Random artists (year: {year_text}, month: {month_text}, min listens: {min_listens}, count: {artist_count})"
+            "Random artists ({range_text}, min listens: {min_listens}, count: {artist_count})"
+        );
+
+        let mut rng = rand::thread_rng();
+        let messages = match candidates.choose_multiple_weighted(
+            &mut rng,
+            artist_count,
+            |(_, weight)| *weight,
+        ) {
+            Ok(chosen) if !candidates.is_empty() => chosen
+                .map(|(artist_name, _)| artist_name.to_string())
+                .collect(),
+            _ => vec!["No artists found".to_string()],
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages(&title, messages)),
         );
 
-        let messages = if artist_names.is_empty() {
+        self.state.command_parameters = None;
+    }
+
+    fn run_discovered_artists(&mut self, year: Option<i32>, month: Option<u32>) {
+        let year_text = year
+            .map(|y| format!("{y}"))
+            .unwrap_or_else(|| "None".to_string());
+        let month_text = month
+            .map(|m| format!("{m}"))
+            .unwrap_or_else(|| "None".to_string());
+        let title = format!("Discovered artists (year: {year_text}, month: {month_text})");
+
+        let discovered = self.processor.listen_timeline.discovered(year, month);
+        let messages = if discovered.is_empty() {
             vec!["No artists found".to_string()]
         } else {
-            artist_names.iter().take(artist_count).cloned().collect()
+            discovered
+                .iter()
+                .map(|(artist_name, span)| {
+                    format!("{} - {}", span.first.format("%Y-%m-%d"), artist_name)
+                })
+                .collect()
         };
 
         self.state.insert_output(
@@ -821,7 +2077,152 @@ impl Application {
         self.state.command_parameters = None;
     }
 
-    fn run_artist_songs(&mut self, name: &str) {
+    fn run_new_artists_chart(&mut self, year: Option<i32>) {
+        let title = if let Some(y) = year {
+            format!("New artists per month (year: {y})")
+        } else {
+            "New artists per month".to_string()
+        };
+
+        let months = self.processor.listen_timeline.discoveries_per_month(year);
+        if months.is_empty() {
+            let message = match year {
+                Some(y) => format!("No new artists found in {y}"),
+                None => "No new artists found".to_string(),
+            };
+            let message_set = MessageSet::with_messages(&title, vec![message]);
+            self.state.insert_output(0, Output::MessageSet(message_set));
+        } else {
+            let data_points = months
+                .into_iter()
+                .map(|(y, m, count)| {
+                    let label = if year.is_some() {
+                        format!("{m:02}")
+                    } else {
+                        format!("{y}-{m:02}")
+                    };
+                    BarDataPoint::new(label, count)
+                })
+                .collect();
+            self.state.insert_output(
+                0,
+                Output::BarChart(super::BarChart::with_data_points(&title, data_points)),
+            );
+        }
+
+        self.state.command_parameters = None;
+    }
+
+    fn run_forgotten_artists(&mut self, months: u32, min_listens: u64, count: usize) {
+        let title = format!(
+            "Forgotten artists (months: {months}, min listens: {min_listens}, count: {count})"
+        );
+        let today = Local::now().naive_local().date();
+        let cutoff = today
+            .checked_sub_months(Months::new(months))
+            .unwrap_or(NaiveDate::MIN);
+
+        let mut forgotten: Vec<(ArtistName, ListenSpan, u64)> = self
+            .processor
+            .listen_timeline
+            .not_listened_since(&cutoff)
+            .into_iter()
+            .filter_map(|(artist_name, span)| {
+                self.processor
+                    .artist_song_counter(&artist_name)
+                    .map(|counter| counter.total_song_plays())
+                    .filter(|plays| *plays >= min_listens)
+                    .map(|plays| (artist_name, span, plays))
+            })
+            .collect();
+        forgotten.sort_by_key(|(_, _, plays)| Reverse(*plays));
+
+        let messages = if forgotten.is_empty() {
+            vec!["No artists found".to_string()]
+        } else {
+            forgotten
+                .iter()
+                .take(count)
+                .map(|(artist_name, span, plays)| {
+                    format!(
+                        "{} - {} plays - last listened {}",
+                        artist_name,
+                        plays,
+                        span.last.format("%Y-%m-%d")
+                    )
+                })
+                .collect()
+        };
+
+        self.state.insert_output(
+            0,
+            Output::MessageSet(MessageSet::with_messages(&title, messages)),
+        );
+
+        self.state.command_parameters = None;
+    }
+
+    fn run_streaks(&mut self, count: usize, year: Option<i32>, artist_name: Option<String>) {
+        let today = Local::now().naive_local().date();
+        let streaks = self.processor.listen_streaks(year, &today);
+        let year_text = year
+            .map(|y| format!("{y}"))
+            .unwrap_or_else(|| "None".to_string());
+        let matches_artist = |name: &ArtistName| {
+            artist_name
+                .as_ref()
+                .map(|filter| filter.to_lowercase() == name.0.to_lowercase())
+                .unwrap_or(true)
+        };
+
+        let artist_streaks: Vec<String> = streaks
+            .artist_streaks
+            .iter()
+            .filter(|(name, _)| matches_artist(name))
+            .take(count)
+            .map(|(name, streak)| format!("{name} - {streak}"))
+            .collect();
+        let artist_gaps: Vec<String> = streaks
+            .artist_gaps
+            .iter()
+            .filter(|(name, _)| matches_artist(name))
+            .take(count)
+            .map(|(name, gap)| format!("{name} - {gap}"))
+            .collect();
+
+        let message_sets = vec![
+            streaks_message_set(&year, &streaks),
+            MessageSet::with_messages(
+                &format!("Longest artist streaks (year: {year_text}, count: {count})"),
+                if artist_streaks.is_empty() {
+                    vec!["No artists found".to_string()]
+                } else {
+                    artist_streaks
+                },
+            ),
+            MessageSet::with_messages(
+                &format!("Longest gaps between artist listens (year: {year_text}, count: {count})"),
+                if artist_gaps.is_empty() {
+                    vec!["No artists found".to_string()]
+                } else {
+                    artist_gaps
+                },
+            ),
+        ];
+
+        for message_set in message_sets.into_iter().rev() {
+            self.state.insert_output(0, Output::MessageSet(message_set));
+        }
+
+        self.state.command_parameters = None;
+    }
+
+    async fn run_artist_songs(&mut self, name: &str, album_name: Option<String>) {
+        if let Some(album_name) = album_name {
+            self.run_artist_album_songs(name, &album_name).await;
+            return;
+        }
+
         let mut songs: Vec<String> = self
             .processor
             .artist_song_counter(&ArtistName(name.to_string()))
@@ -834,6 +2235,25 @@ impl Application {
                     .collect()
             })
             .unwrap_or_default();
+        songs.extend(
+            self.processor
+                .artists_counts
+                .find_featured_artist(
+                    &self
+                        .processor
+                        .aliases
+                        .resolve(&ArtistName(name.to_string())),
+                )
+                .map(|artist_counter| {
+                    artist_counter
+                        .play_details
+                        .all_song_plays()
+                        .iter()
+                        .map(|song_play| format!("{} (featured)", song_play.0))
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default(),
+        );
 
         songs.sort();
         songs.dedup();
@@ -849,30 +2269,352 @@ impl Application {
         self.state.command_parameters = None;
     }
 
-    fn run_artists_on_day(&mut self, date: NaiveDate) {
-        let names = self
+    fn run_artist(&mut self, name: &str, count: usize) {
+        match artist_detail(&self.processor, &ArtistName(name.to_string()), count) {
+            Some(pages) => {
+                for page in pages.into_iter().rev() {
+                    self.state.insert_output(0, page);
+                }
+            }
+            None => self.state.insert_output(
+                0,
+                Output::MessageSet(MessageSet::with_messages(
+                    &format!("Artist {name}"),
+                    vec!["No listens found for this artist".to_string()],
+                )),
+            ),
+        }
+
+        self.state.command_parameters = None;
+    }
+
+    async fn run_song(&mut self, artist_name: &str, track_name: &str) {
+        let track_name = self
             .processor
-            .artists_on_day(date)
-            .into_iter()
-            .map(|artist_counter| artist_counter.total_plays_display())
-            .collect::<Vec<String>>();
+            .titles
+            .track_name(&TrackName(track_name.to_string()));
+        let listens = self.listens.lock().await.track_listens(&track_name).await;
+        let pages = match listens {
+            Ok(listens) => song_detail(
+                &self.processor,
+                &listens,
+                &ArtistName(artist_name.to_string()),
+                &track_name,
+            )
+            .ok_or_else(|| "No listens found for this song".to_string()),
+            Err(e) => Err(format!("Error: {e}")),
+        };
 
-        let title = format!("Artists listened to on {}", date.format("%Y-%m-%d"));
+        match pages {
+            Ok(pages) => {
+                for page in pages.into_iter().rev() {
+                    self.state.insert_output(0, page);
+                }
+            }
+            Err(message) => self.state.insert_output(
+                0,
+                Output::MessageSet(MessageSet::with_messages(
+                    &format!("{artist_name} - {track_name}"),
+                    vec![message],
+                )),
+            ),
+        }
 
-        let messages = if names.is_empty() {
-            vec!["No artists found".to_string()]
-        } else {
-            names
+        self.state.command_parameters = None;
+    }
+
+    async fn run_artist_album_songs(&mut self, name: &str, album_name: &str) {
+        let artist_name = self
+            .processor
+            .aliases
+            .resolve(&ArtistName(name.to_string()));
+        let album_name = self
+            .processor
+            .titles
+            .album_name(&AlbumName(album_name.to_string()));
+        let listens = self.listens.lock().await.album_listens(&album_name).await;
+
+        let songs: Vec<String> = match listens {
+            Ok(listens) => {
+                let mut songs: Vec<String> = listens
+                    .into_iter()
+                    .filter(|listen| listen.artist_name.0.eq_ignore_ascii_case(&artist_name.0))
+                    .map(|listen| listen.track_name.0)
+                    .collect();
+                songs.sort();
+                songs.dedup();
+                songs
+            }
+            Err(e) => vec![format!("Error: {e}")],
         };
 
         self.state.insert_output(
             0,
-            Output::MessageSet(MessageSet::with_messages(&title, messages)),
+            Output::MessageSet(MessageSet::with_messages(
+                &format!("Songs for {name} on {album_name}"),
+                songs,
+            )),
+        );
+
+        self.state.command_parameters = None;
+    }
+
+    fn run_artists_on_day(&mut self, date: NaiveDate) {
+        let mut counters = self.processor.artists_on_day(date);
+        counters.sort_by_key(|counter| Reverse(counter.total_song_plays()));
+        let title = format!("Artists listened to on {}", date.format("%Y-%m-%d"));
+
+        let output = if counters.is_empty() {
+            Output::MessageSet(MessageSet::with_messages(
+                &title,
+                vec!["No artists found".to_string()],
+            ))
+        } else {
+            Output::Table(Table::ranked_artists(&title, &counters, &Metric::Plays))
+        };
+
+        self.state.insert_output(0, output);
+
+        self.state.command_parameters = None;
+    }
+
+    fn run_wrapped(
+        &mut self,
+        year: Option<i32>,
+        count: usize,
+        output_folder: Option<String>,
+        report_format: ReportFormat,
+    ) {
+        let year = year.or_else(|| self.processor.years.keys().max().copied());
+        let report = year.and_then(|y| wrapped_report(&self.processor, y, count));
+
+        let Some(report) = report else {
+            let year_text = year
+                .map(|y| format!("{y}"))
+                .unwrap_or_else(|| "None".to_string());
+            self.state.insert_output(
+                0,
+                Output::MessageSet(MessageSet::with_messages(
+                    &format!("Wrapped (year: {year_text})"),
+                    vec!["No statistics gathered".to_string()],
+                )),
+            );
+            self.state.command_parameters = None;
+            return;
+        };
+
+        let written = output_folder.and_then(|output_folder| {
+            let writer = FileWriter {
+                folder: Box::new(OutputFolder {
+                    root: output_folder,
+                }),
+            };
+            let name = report.title.to_lowercase().replace(' ', "_");
+            match writer.write_text(
+                &report.render(&report_format),
+                &name,
+                report_format.extension_display(),
+            ) {
+                Ok(path) => {
+                    info!("wrote report to {path}");
+                    Some(MessageSet::with_messages(
+                        &format!("{}: report", report.title),
+                        vec![format!("Wrote the report to {path}")],
+                    ))
+                }
+                Err(e) => {
+                    self.state.error_message = Some(format!("{e}"));
+                    None
+                }
+            }
+        });
+
+        for page in report.pages.into_iter().rev() {
+            self.state.insert_output(0, Output::MessageSet(page));
+        }
+        if let Some(written) = written {
+            self.state.insert_output(0, Output::MessageSet(written));
+        }
+
+        self.state.command_parameters = None;
+    }
+
+    async fn run_query(&mut self, query: Option<String>) {
+        let text = query.unwrap_or_default();
+
+        match query::parse(&text) {
+            Ok(listen_query) => {
+                let listens = self.listens.lock().await.listens().await;
+                let mut messages = match listens {
+                    Ok(listens) => listen_query.run(&listens),
+                    Err(e) => vec![format!("Error: {e}")],
+                };
+                if messages.is_empty() {
+                    messages.push("No listens found".to_string());
+                }
+
+                self.state.insert_output(
+                    0,
+                    Output::MessageSet(MessageSet::with_messages(
+                        &format!("Query ({text})"),
+                        messages,
+                    )),
+                );
+            }
+            Err(e) => self.state.error_message = Some(e.to_string()),
+        }
+
+        self.state.command_parameters = None;
+    }
+
+    fn run_compare(
+        &mut self,
+        first: Option<Period>,
+        second: Option<Period>,
+        count: usize,
+        metric: Metric,
+    ) {
+        let second = second.or_else(|| {
+            self.processor
+                .years
+                .keys()
+                .max()
+                .and_then(|year| Period::year(*year))
+        });
+        let first = first.or_else(|| {
+            second
+                .as_ref()
+                .and_then(|period| Period::year(period.start.year() - 1))
+        });
+
+        let (Some(first), Some(second)) = (first, second) else {
+            self.state.insert_output(
+                0,
+                Output::MessageSet(MessageSet::with_messages(
+                    "Compare",
+                    vec!["No statistics gathered".to_string()],
+                )),
+            );
+            self.state.command_parameters = None;
+            return;
+        };
+
+        let first_counts = self.processor.artists_counts_in(&first);
+        let second_counts = self.processor.artists_counts_in(&second);
+
+        let first_minutes = first_counts.time_played().time_min;
+        let second_minutes = second_counts.time_played().time_min;
+        let change = if first_minutes > 0.0 {
+            format!(
+                "Change: {:+.1} minutes ({:+.1}%)",
+                second_minutes - first_minutes,
+                (second_minutes - first_minutes) / first_minutes * 100.0
+            )
+        } else {
+            format!("Change: {:+.1} minutes", second_minutes - first_minutes)
+        };
+        let time_messages = vec![
+            format!(
+                "{first}: {first_minutes:.1} minutes ({} plays)",
+                first_counts.total_count(&None)
+            ),
+            format!(
+                "{second}: {second_minutes:.1} minutes ({} plays)",
+                second_counts.total_count(&None)
+            ),
+            change,
+        ];
+
+        let artist_ranking = |counts: &ArtistsCounts| -> Vec<String> {
+            counts
+                .top(counts.artist_count(), &metric)
+                .into_iter()
+                .map(|counter| counter.artist_name.0)
+                .collect()
+        };
+        let song_ranking = |counts: &ArtistsCounts| -> Vec<String> {
+            counts
+                .top_songs(usize::MAX, &metric)
+                .into_iter()
+                .map(|song_count| {
+                    format!("{} - {}", song_count.artist_name, song_count.song_count.0)
+                })
+                .collect()
+        };
+
+        let artists = RankComparison::compare(
+            &artist_ranking(&first_counts),
+            &artist_ranking(&second_counts),
+            count,
         );
+        let songs = RankComparison::compare(
+            &song_ranking(&first_counts),
+            &song_ranking(&second_counts),
+            count,
+        );
+
+        let message_sets = vec![
+            MessageSet::with_messages(
+                &format!("Listening time ({first} vs {second})"),
+                time_messages,
+            ),
+            MessageSet::with_messages(
+                &format!(
+                    "Artist rank movement ({first} vs {second}, count: {count}, by: {metric})"
+                ),
+                rank_movement_messages(&artists, &first),
+            ),
+            MessageSet::with_messages(
+                &format!("Song rank movement ({first} vs {second}, count: {count}, by: {metric})"),
+                rank_movement_messages(&songs, &first),
+            ),
+        ];
+
+        for message_set in message_sets.into_iter().rev() {
+            self.state.insert_output(0, Output::MessageSet(message_set));
+        }
 
         self.state.command_parameters = None;
     }
 
+    /// The period given by `from` and `to`. It can't be combined with a year or month,
+    /// which it would otherwise quietly replace.
+    fn period_parameter(
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        has_year_or_month: bool,
+    ) -> Result<Option<Period>, InteractiveError> {
+        let period = Period::between(from, to)?;
+        if period.is_some() && has_year_or_month {
+            return Err(InteractiveError::ParsingIssue {
+                message: "Give either from/to or a year/month, not both".to_string(),
+            });
+        }
+
+        Ok(period)
+    }
+
+    fn reject_command(&mut self, error: InteractiveError) {
+        self.state.error_message = Some(match error {
+            InteractiveError::ParsingIssue { message } => message,
+            e => e.to_string(),
+        });
+        self.state.command_parameters = None;
+    }
+
+    fn insert_period_table(&mut self, table: Table, empty_message: &str) {
+        let output = if table.is_empty() {
+            Output::MessageSet(MessageSet::with_messages(
+                table.title(),
+                vec![empty_message.to_string()],
+            ))
+        } else {
+            Output::Table(table)
+        };
+
+        self.state.insert_output(0, output);
+    }
+
     fn run_print_statistics(&mut self, year: Option<i32>) {
         let mut message_sets = if let Some(y) = year {
             if let Some(year_counts) = self.processor.year_count(y) {
@@ -929,6 +2671,9 @@ impl Application {
             .map(|y| format!("Most listened songs (unique artist, year: {y})"))
             .unwrap_or_else(|| "Most listened songs (unique artist, year: None)".to_string());
 
+        let today = Local::now().naive_local().date();
+        let streaks = self.processor.listen_streaks(*year, &today);
+
         vec![
             MessageSet::with_messages(
                 &general_stats_title,
@@ -949,6 +2694,132 @@ impl Application {
                 &most_listened_songs_unique_artist_title,
                 general.artist_most_played_songs.to_vec(),
             ),
+            streaks_message_set(year, &streaks),
         ]
     }
 }
+
+fn rank_movement_messages(comparison: &RankComparison, first: &Period) -> Vec<String> {
+    if comparison.ranked.is_empty() && comparison.dropped_out.is_empty() {
+        return vec!["No listens found".to_string()];
+    }
+
+    let mut messages: Vec<String> = comparison
+        .ranked
+        .iter()
+        .map(|movement| format!("{movement}"))
+        .collect();
+    messages.push(format!(
+        "New entries: {}, climbers: {}, drop-outs: {}",
+        comparison.new_entries().len(),
+        comparison.climbers().len(),
+        comparison.dropped_out.len()
+    ));
+    if let Some(climber) = comparison.climbers().first() {
+        messages.push(format!("Biggest climber: {climber}"));
+    }
+    for (name, rank) in comparison.dropped_out.iter() {
+        messages.push(format!("Dropped out: {name} (#{rank} in {first})"));
+    }
+
+    messages
+}
+
+fn streaks_message_set(year: &Option<i32>, streaks: &ListenStreaks) -> MessageSet {
+    let title = year
+        .map(|y| format!("Listening streaks (year: {y})"))
+        .unwrap_or_else(|| "Listening streaks (year: None)".to_string());
+
+    let longest = streaks
+        .longest
+        .as_ref()
+        .map(|streak| format!("Longest streak: {streak}"))
+        .unwrap_or_else(|| "Longest streak: none".to_string());
+    let current = streaks
+        .current
+        .as_ref()
+        .map(|streak| format!("Current streak: {streak}"))
+        .unwrap_or_else(|| "Current streak: none".to_string());
+    let busiest_day = streaks
+        .busiest_day
+        .map(|(date, plays)| {
+            format!(
+                "Busiest day: {} with {plays} plays",
+                date.format("%Y-%m-%d")
+            )
+        })
+        .unwrap_or_else(|| "Busiest day: none".to_string());
+
+    MessageSet::with_messages(&title, vec![longest, current, busiest_day])
+}
+
+struct TitleMerge {
+    artist_name: ArtistName,
+    title: String,
+    spellings: Vec<(String, u64)>,
+}
+
+impl TitleMerge {
+    fn plays(&self) -> u64 {
+        self.spellings.iter().map(|(_, plays)| plays).sum()
+    }
+}
+
+/// Groups the titles an artist was listened to under by what they normalise to, keeping
+/// only the groups where more than one spelling would be merged.
+fn title_merge_messages(
+    titles: Vec<(ArtistName, String)>,
+    normalize: impl Fn(&str) -> String,
+    count: usize,
+) -> Vec<String> {
+    let mut groups: HashMap<(String, String), TitleMerge> = HashMap::new();
+    for (artist_name, title) in titles.into_iter() {
+        let normalized = normalize(&title);
+        let merge = groups
+            .entry((artist_name.0.to_lowercase(), normalized.to_lowercase()))
+            .or_insert_with(|| TitleMerge {
+                artist_name,
+                title: normalized,
+                spellings: Vec::new(),
+            });
+        match merge
+            .spellings
+            .iter_mut()
+            .find(|(spelling, _)| spelling.eq_ignore_ascii_case(&title))
+        {
+            Some((_, plays)) => *plays += 1,
+            None => merge.spellings.push((title, 1)),
+        }
+    }
+
+    let mut merges: Vec<TitleMerge> = groups
+        .into_values()
+        .filter(|merge| merge.spellings.len() > 1)
+        .collect();
+    merges.sort_by_key(|merge| Reverse(merge.plays()));
+
+    let messages: Vec<String> = merges
+        .into_iter()
+        .take(count)
+        .map(|mut merge| {
+            merge.spellings.sort_by_key(|(_, plays)| Reverse(*plays));
+            let spellings: Vec<String> = merge
+                .spellings
+                .iter()
+                .map(|(spelling, plays)| format!("{spelling} ({plays})"))
+                .collect();
+            format!(
+                "{} - {} <- {}",
+                merge.artist_name,
+                merge.title,
+                spellings.join(", ")
+            )
+        })
+        .collect();
+
+    if messages.is_empty() {
+        vec!["No titles would merge".to_string()]
+    } else {
+        messages
+    }
+}
@@ -0,0 +1,283 @@
+use std::cmp::Reverse;
+
+use chrono::NaiveDate;
+
+use crate::{projections::statistics::EventProcessor, track_plays::ArtistName};
+
+use super::{chart::BarDataPoint, table::Cell, BarChart, MessageSet, Output, Table};
+
+/// Everything known about one artist, as pages to flip through: an overview, their top
+/// songs and albums, a chart of every month they were listened to and their busiest days.
+pub fn artist_detail(
+    processor: &EventProcessor,
+    artist_name: &ArtistName,
+    count: usize,
+) -> Option<Vec<Output>> {
+    let counter = processor.artist_song_counter(artist_name)?;
+    let artist_name = counter.artist_name.clone();
+    let counts = &processor.artists_counts;
+
+    let plays = counter.total_song_plays();
+    let time_played = counter.play_details.total_time_played();
+    let skips = counts.artist_skip_count(&artist_name);
+    let mut overview = vec![
+        format!("Plays: {plays}"),
+        format!(
+            "Listened for {:.0} minutes ({:.1} hours)",
+            time_played.time_min, time_played.time_hr
+        ),
+    ];
+    if let Some(span) = processor.listen_timeline.artist_span(&artist_name) {
+        overview.push(format!(
+            "First listen: {}",
+            span.first.format("%Y-%m-%d %H:%M")
+        ));
+        overview.push(format!(
+            "Last listen: {}",
+            span.last.format("%Y-%m-%d %H:%M")
+        ));
+    }
+    overview.push(format!(
+        "Skipped {skips} times ({})",
+        skip_rate_display(plays, skips)
+    ));
+    if let Some(featured) = counts.find_featured_artist(&artist_name) {
+        overview.push(format!(
+            "Featured on {} plays of other artists' songs",
+            featured.total_song_plays()
+        ));
+    }
+
+    let mut songs = counter.play_details.all_song_plays();
+    songs.sort_by_key(|song_count| Reverse(song_count.1));
+    let songs: Vec<Vec<Cell>> = songs
+        .iter()
+        .take(count)
+        .enumerate()
+        .map(|(index, song_count)| {
+            vec![
+                Cell::Count(index as u64 + 1),
+                Cell::Text(song_count.0 .0.clone()),
+                Cell::Count(song_count.1),
+                Cell::minutes(song_count.2),
+            ]
+        })
+        .collect();
+
+    let mut albums = counts.artist_albums(&artist_name);
+    albums.sort_by_key(|album_count| Reverse(album_count.1));
+    let albums: Vec<Vec<Cell>> = albums
+        .iter()
+        .take(count)
+        .enumerate()
+        .map(|(index, album_count)| {
+            vec![
+                Cell::Count(index as u64 + 1),
+                Cell::Text(album_count.0 .0.clone()),
+                Cell::Count(album_count.1),
+                Cell::minutes(album_count.2),
+            ]
+        })
+        .collect();
+
+    let name = Some(artist_name.0.clone());
+    let mut month_plays: Vec<(i32, u32, u64)> = processor
+        .year_counts()
+        .iter()
+        .flat_map(|year_counts| {
+            year_counts.month_counts().into_iter().map(|month_counts| {
+                (
+                    year_counts.year,
+                    month_counts.month,
+                    month_counts.artists_counts.total_count(&name),
+                )
+            })
+        })
+        .collect();
+    month_plays.sort_by_key(|(year, month, _)| (*year, *month));
+    // Months before the first listen and after the last would only pad the chart.
+    let first = month_plays.iter().position(|(_, _, plays)| *plays > 0);
+    let last = month_plays.iter().rposition(|(_, _, plays)| *plays > 0);
+    let data_points: Vec<BarDataPoint> = match (first, last) {
+        (Some(first), Some(last)) => month_plays[first..=last]
+            .iter()
+            .map(|(year, month, plays)| BarDataPoint::new(format!("{year}-{month:02}"), *plays))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut day_plays: Vec<(NaiveDate, u64)> = Vec::new();
+    for year_counts in processor.year_counts() {
+        for month_counts in year_counts.month_counts() {
+            for day_counts in month_counts.day_counts() {
+                let plays = day_counts.artists_counts.total_count(&name);
+                if let Some(date) = NaiveDate::from_ymd_opt(
+                    year_counts.year,
+                    month_counts.month,
+                    day_counts.day_of_month,
+                )
+                .filter(|_| plays > 0)
+                {
+                    day_plays.push((date, plays));
+                }
+            }
+        }
+    }
+    day_plays.sort_by_key(|(date, plays)| (Reverse(*plays), *date));
+    let days: Vec<Vec<Cell>> = day_plays
+        .iter()
+        .take(count)
+        .enumerate()
+        .map(|(index, (date, plays))| {
+            vec![
+                Cell::Count(index as u64 + 1),
+                Cell::Text(date.format("%Y-%m-%d").to_string()),
+                Cell::Count(*plays),
+            ]
+        })
+        .collect();
+
+    Some(vec![
+        Output::MessageSet(MessageSet::with_messages(
+            &format!("{artist_name}: overview"),
+            overview,
+        )),
+        Output::Table(Table::with_rows(
+            &format!("{artist_name}: top songs"),
+            &["Rank", "Song", "Plays", "Minutes"],
+            songs,
+        )),
+        if albums.is_empty() {
+            Output::MessageSet(MessageSet::with_messages(
+                &format!("{artist_name}: top albums"),
+                vec!["No albums found".to_string()],
+            ))
+        } else {
+            Output::Table(Table::with_rows(
+                &format!("{artist_name}: top albums"),
+                &["Rank", "Album", "Plays", "Minutes"],
+                albums,
+            ))
+        },
+        Output::BarChart(BarChart::with_data_points(
+            &format!("{artist_name}: plays per month"),
+            data_points,
+        )),
+        Output::Table(Table::with_rows(
+            &format!("{artist_name}: top days"),
+            &["Rank", "Date", "Plays"],
+            days,
+        )),
+    ])
+}
+
+fn skip_rate_display(plays: u64, skips: u64) -> String {
+    let listens = plays + skips;
+    if listens == 0 {
+        return "no listens".to_string();
+    }
+
+    format!("{:.1}% skip rate", skips as f64 / listens as f64 * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::{table::Cell, Output},
+        processing::{
+            events::{Event, EventData, TrackPlayAdded},
+            ArtistAliases, ArtistCredits, ClassificationPolicies, TitleNormalizer,
+        },
+        projections::statistics::EventProcessor,
+        track_plays::{ArtistName, TrackName},
+    };
+
+    use super::{artist_detail, skip_rate_display};
+
+    fn play(version: u32, artist_name: &str, track_name: &str, ms_played: u64) -> Event {
+        Event {
+            version,
+            data: EventData::TrackPlayAdded(TrackPlayAdded {
+                artist_name: ArtistName(artist_name.to_string()),
+                track_name: TrackName(track_name.to_string()),
+                album_name: None,
+                ms_played,
+                end_time: format!("2022-10-0{version} 21:00:00"),
+                service_hint: "spotify".to_string(),
+                track_ms: None,
+                skipped: None,
+                reason_end: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn gathers_aliases_credits_and_skips() {
+        let mut aliases = ArtistAliases::default();
+        aliases.add("Ulcerate NZ", "Ulcerate");
+        let credits = ArtistCredits {
+            separators: ArtistCredits::parse_separators("&"),
+            exceptions: Vec::new(),
+        };
+        let mut processor = EventProcessor::new(
+            ClassificationPolicies::default(),
+            aliases,
+            TitleNormalizer::default(),
+            credits,
+        );
+        for event in [
+            play(1, "Ulcerate", "Visceral Ends", 240_000),
+            play(2, "Ulcerate NZ", "Visceral Ends", 240_000),
+            play(3, "Ulcerate", "Visceral Ends", 5_000),
+            play(4, "Blut Aus Nord & Ulcerate", "Split", 240_000),
+            play(5, "Mgła feat. Ulcerate", "Exercises in Futility", 240_000),
+        ] {
+            processor.process_event(&event);
+        }
+
+        let outputs = artist_detail(&processor, &ArtistName("ulcerate nz".to_string()), 5).unwrap();
+
+        let Output::MessageSet(overview) = &outputs[0] else {
+            panic!("expected an overview");
+        };
+        assert_eq!("Ulcerate: overview", overview.title());
+        assert_eq!("Plays: 3", overview.messages()[0]);
+        assert!(overview
+            .messages()
+            .contains(&"Skipped 1 times (25.0% skip rate)".to_string()));
+        assert!(overview
+            .messages()
+            .contains(&"Featured on 1 plays of other artists' songs".to_string()));
+
+        let Output::Table(songs) = &outputs[1] else {
+            panic!("expected a table of songs");
+        };
+        let songs: Vec<(Cell, Cell)> = songs
+            .rows()
+            .into_iter()
+            .map(|row| (row[1].clone(), row[2].clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                (Cell::Text("Visceral Ends".to_string()), Cell::Count(2)),
+                (Cell::Text("Split".to_string()), Cell::Count(1))
+            ],
+            songs
+        );
+
+        assert!(artist_detail(&processor, &ArtistName("Portal".to_string()), 5).is_none());
+
+        let span = processor
+            .listen_timeline
+            .artist_span(&ArtistName("Ulcerate".to_string()))
+            .unwrap();
+        assert_eq!("2022-10-04", span.last.date().to_string());
+    }
+
+    #[test]
+    fn formats_skip_rate() {
+        assert_eq!("25.0% skip rate", skip_rate_display(3, 1));
+        assert_eq!("0.0% skip rate", skip_rate_display(3, 0));
+        assert_eq!("no listens", skip_rate_display(0, 0));
+    }
+}
@@ -24,6 +24,10 @@ impl BarChart {
         &self.title
     }
 
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
     pub fn data_points(&self) -> &Vec<BarDataPoint> {
         &self.data_points
     }
@@ -37,18 +41,13 @@ impl BarChart {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub enum BarBreakdown {
+    #[default]
     Month,
     Weekday,
 }
 
-impl Default for BarBreakdown {
-    fn default() -> Self {
-        Self::Month
-    }
-}
-
 impl Display for BarBreakdown {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let value = match *self {
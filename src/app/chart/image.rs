@@ -0,0 +1,176 @@
+use crate::{app::report::escape_html, errors::WriteError};
+
+use super::BarChart;
+
+const BAR_WIDTH: u32 = 28;
+const BAR_GAP: u32 = 8;
+const CHART_HEIGHT: u32 = 320;
+const MARGIN_LEFT: u32 = 64;
+const MARGIN_RIGHT: u32 = 24;
+const MARGIN_TOP: u32 = 56;
+const MARGIN_BOTTOM: u32 = 88;
+const TICKS: u64 = 5;
+
+/// Draws the chart as an SVG document in the same colours as the app, with a y axis,
+/// the value above every bar and the labels slanted underneath so long ones still fit.
+pub fn render_svg(chart: &BarChart) -> String {
+    let data_points = chart.data_points();
+    let slot = BAR_WIDTH + BAR_GAP;
+    let width = (MARGIN_LEFT + slot * data_points.len() as u32 + MARGIN_RIGHT).max(480);
+    let height = MARGIN_TOP + CHART_HEIGHT + MARGIN_BOTTOM;
+    let axis_max = axis_max(
+        data_points
+            .iter()
+            .map(|data_point| data_point.y())
+            .max()
+            .unwrap_or_default(),
+    );
+    let baseline = MARGIN_TOP + CHART_HEIGHT;
+    let y_of = |value: u64| baseline - (value * CHART_HEIGHT as u64 / axis_max) as u32;
+
+    let mut elements = vec![
+        format!(r##"  <rect width="{width}" height="{height}" fill="#111"/>"##),
+        format!(
+            r##"  <text x="{MARGIN_LEFT}" y="32" font-size="18" fill="#eee">{}</text>"##,
+            escape_html(chart.title())
+        ),
+    ];
+    for tick in 0..=TICKS {
+        let value = axis_max * tick / TICKS;
+        let y = y_of(value);
+        elements.push(format!(
+            r##"  <line x1="{MARGIN_LEFT}" y1="{y}" x2="{}" y2="{y}" stroke="#333"/>"##,
+            width - MARGIN_RIGHT
+        ));
+        elements.push(format!(
+            r##"  <text x="{}" y="{}" font-size="11" fill="#aaa" text-anchor="end">{value}</text>"##,
+            MARGIN_LEFT - 8,
+            y + 4
+        ));
+    }
+    for (index, data_point) in data_points.iter().enumerate() {
+        let x = MARGIN_LEFT + BAR_GAP / 2 + slot * index as u32;
+        let y = y_of(data_point.y());
+        let center = x + BAR_WIDTH / 2;
+        elements.push(format!(
+            r##"  <rect x="{x}" y="{y}" width="{BAR_WIDTH}" height="{}" fill="#e5c07b"/>"##,
+            baseline - y
+        ));
+        elements.push(format!(
+            r##"  <text x="{center}" y="{}" font-size="10" fill="#eee" text-anchor="middle">{}</text>"##,
+            y.saturating_sub(4),
+            data_point.y()
+        ));
+        elements.push(format!(
+            r##"  <text x="{center}" y="{}" font-size="11" fill="#eee" text-anchor="end" transform="rotate(-45 {center} {})">{}</text>"##,
+            baseline + 16,
+            baseline + 16,
+            escape_html(data_point.x())
+        ));
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\">\n{}\n</svg>\n",
+        elements.join("\n")
+    )
+}
+
+/// Rasterises the SVG locally, using the system fonts for the text.
+#[cfg(feature = "png")]
+pub fn render_png(chart: &BarChart) -> Result<Vec<u8>, WriteError> {
+    use resvg::{tiny_skia, usvg};
+
+    let to_error = |message: String| WriteError::CannotRenderImage { message };
+
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    let tree =
+        usvg::Tree::from_str(&render_svg(chart), &options).map_err(|e| to_error(e.to_string()))?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| to_error("chart is too large".to_string()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    pixmap.encode_png().map_err(|e| to_error(e.to_string()))
+}
+
+#[cfg(not(feature = "png"))]
+pub fn render_png(_chart: &BarChart) -> Result<Vec<u8>, WriteError> {
+    Err(WriteError::CannotRenderImage {
+        message: "krustens was built without the png feature".to_string(),
+    })
+}
+
+/// The chart's title made safe to use in a file name.
+pub fn image_name(chart: &BarChart) -> String {
+    chart
+        .title()
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+/// A round number at or above the largest value, so the axis ticks are easy to read.
+fn axis_max(max: u64) -> u64 {
+    if max == 0 {
+        return TICKS;
+    }
+
+    let step = max.div_ceil(TICKS);
+    let magnitude = 10_u64.pow(step.ilog10());
+    let step = [1, 2, 5, 10]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|nice| *nice >= step)
+        .unwrap_or(step);
+
+    step * TICKS
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::{chart::BarDataPoint, BarChart};
+
+    use super::{axis_max, image_name, render_svg};
+
+    #[test]
+    fn rounds_axis_up() {
+        assert_eq!(5, axis_max(0));
+        assert_eq!(5, axis_max(4));
+        assert_eq!(50, axis_max(42));
+        assert_eq!(250, axis_max(212));
+        assert_eq!(5000, axis_max(4100));
+    }
+
+    #[test]
+    fn renders_bars_and_escaped_labels() {
+        let chart = BarChart::with_data_points(
+            "Plays <by> month",
+            vec![
+                BarDataPoint::new("2022-01".to_string(), 10),
+                BarDataPoint::new("2022-02".to_string(), 0),
+            ],
+        );
+        let svg = render_svg(&chart);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("Plays &lt;by&gt; month"));
+        assert_eq!(2, svg.matches(r##"fill="#e5c07b""##).count());
+        assert!(svg.contains(">2022-02</text>"));
+        assert_eq!("plays-by-month", image_name(&chart));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn renders_png() {
+        let chart = BarChart::with_data_points(
+            "Plays by month",
+            vec![BarDataPoint::new("2022-01".to_string(), 10)],
+        );
+        let png = super::render_png(&chart).unwrap();
+
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
@@ -1,5 +1,7 @@
 mod bar_chart;
 mod data_point;
+mod image;
 
 pub use bar_chart::{BarBreakdown, BarChart};
 pub use data_point::BarDataPoint;
+pub use image::{image_name, render_png, render_svg};
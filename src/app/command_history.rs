@@ -0,0 +1,192 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::fold;
+
+use super::CommandName;
+
+const MAX_ENTRIES: usize = 500;
+
+/// A command that was run: the line it could be typed as, with every parameter it ran
+/// with, and when. The parameters are parsed from the line again to run it again.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HistoryEntry {
+    command: String,
+    /// Missing for commands kept in the history before it recorded when they ran.
+    #[serde(default)]
+    ran_at: Option<DateTime<Local>>,
+}
+
+impl HistoryEntry {
+    pub fn new(command: String) -> Self {
+        Self {
+            command,
+            ran_at: Some(Local::now()),
+        }
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    pub fn ran_at(&self) -> Option<&DateTime<Local>> {
+        self.ran_at.as_ref()
+    }
+}
+
+/// The commands run so far, oldest first. Running the same line twice in a row keeps
+/// only the latest run.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(from = "StoredHistory")]
+pub struct CommandHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+/// The history as it's been saved: the entries, or only the names of the commands run
+/// in states saved before the history kept their parameters.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredHistory {
+    Entries { entries: Vec<HistoryEntry> },
+    Names(Vec<CommandName>),
+}
+
+impl From<StoredHistory> for CommandHistory {
+    fn from(history: StoredHistory) -> Self {
+        let entries = match history {
+            StoredHistory::Entries { entries } => entries,
+            StoredHistory::Names(names) => names
+                .into_iter()
+                .map(|name| HistoryEntry {
+                    command: name.to_string(),
+                    ran_at: None,
+                })
+                .collect(),
+        };
+
+        Self { entries }
+    }
+}
+
+impl CommandHistory {
+    pub fn push(&mut self, entry: HistoryEntry) {
+        if self
+            .entries
+            .last()
+            .is_some_and(|last| last.command == entry.command)
+        {
+            self.entries.pop();
+        }
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&HistoryEntry> {
+        self.entries.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entries newest first, with their index.
+    pub fn recent(&self) -> impl Iterator<Item = (usize, &HistoryEntry)> {
+        self.entries.iter().enumerate().rev()
+    }
+}
+
+/// A reverse search through the history, like Ctrl-R in a shell. The match is the newest
+/// entry containing the query, ignoring case and accents, and searching again moves on
+/// to older ones.
+#[derive(Clone, Debug, Default)]
+pub struct HistorySearch {
+    folded_query: String,
+    current: Option<usize>,
+}
+
+impl HistorySearch {
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub fn set_query(&mut self, query: &str, history: &CommandHistory) {
+        self.folded_query = fold(query.trim());
+        self.current = self.find(history, history.len());
+    }
+
+    /// Moves to the next older match, staying on the current one if there are none.
+    pub fn older(&mut self, history: &CommandHistory) {
+        let before = self.current.unwrap_or(history.len());
+        if let Some(index) = self.find(history, before) {
+            self.current = Some(index);
+        }
+    }
+
+    fn find(&self, history: &CommandHistory, before: usize) -> Option<usize> {
+        history
+            .recent()
+            .filter(|(index, _)| *index < before)
+            .find(|(_, entry)| fold(&entry.command).contains(&self.folded_query))
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::Input;
+
+    use super::{CommandHistory, HistoryEntry, HistorySearch};
+
+    fn entry(command: &str) -> HistoryEntry {
+        HistoryEntry::new(command.to_string())
+    }
+
+    #[test]
+    fn searches_back_through_history() {
+        let mut history = CommandHistory::default();
+        history.push(entry("top artists count=20"));
+        history.push(entry("chart 2021 artist=Mgła"));
+        history.push(entry("top songs"));
+        history.push(entry("top songs"));
+        assert_eq!(3, history.len());
+
+        let mut search = HistorySearch::default();
+        search.set_query("TOP", &history);
+        assert_eq!(Some(2), search.current());
+        search.older(&history);
+        assert_eq!(Some(0), search.current());
+        search.older(&history);
+        assert_eq!(Some(0), search.current());
+
+        search.set_query("mgla", &history);
+        assert_eq!(Some(1), search.current());
+        search.set_query("wrapped", &history);
+        assert_eq!(None, search.current());
+    }
+
+    #[test]
+    fn reads_the_command_names_of_older_states() {
+        let input: Input = serde_json::from_str(
+            r#"{"text": "", "command_history": ["TopArtists", "Chart"], "history_index": null}"#,
+        )
+        .unwrap();
+
+        let commands: Vec<&str> = input
+            .history
+            .recent()
+            .map(|(_, entry)| entry.command())
+            .collect();
+        assert_eq!(vec!["chart", "top artists"], commands);
+        assert!(input.history.get(0).unwrap().ran_at().is_none());
+
+        let saved = serde_json::to_string(&input).unwrap();
+        let input: Input = serde_json::from_str(&saved).unwrap();
+        assert_eq!(2, input.history.len());
+    }
+}
@@ -0,0 +1,306 @@
+use std::fmt::Display;
+
+use strum::IntoEnumIterator;
+
+use crate::errors::InteractiveError;
+
+use super::{CommandName, CommandParameterSpec};
+
+/// A command typed on one line with some or all of its parameters, like
+/// `top artists count=20 year=2022` or `chart 2021 --breakdown weekday`. Values are
+/// given by key, as `key=value`, `--key=value` or `--key value`, or by position, in
+/// the order the command would otherwise ask for them. Quotes keep a value with spaces
+/// together.
+#[derive(Debug)]
+pub struct CommandLine {
+    name: CommandName,
+    values: Vec<Option<String>>,
+}
+
+impl CommandLine {
+    pub fn parse(text: &str) -> Result<Self, InteractiveError> {
+        let text = text.trim();
+        let name = CommandName::iter()
+            .filter(|name| {
+                let name = name.to_string();
+                text == name || text.starts_with(&format!("{name} "))
+            })
+            .max_by_key(|name| name.to_string().len())
+            .ok_or_else(|| parsing_issue("Unknown command name".to_string()))?;
+        let rest = text[name.to_string().len()..].trim();
+        let specs = name.parameters();
+        let mut values: Vec<Option<String>> = specs.iter().map(|_| None).collect();
+
+        if rest.is_empty() {
+            return Ok(Self { name, values });
+        }
+
+        // A lone parameter takes the rest of the line as it is, so a query doesn't
+        // need quoting.
+        if let [spec] = specs.as_slice() {
+            let prefix = format!("{}=", spec.key());
+            let value = rest.strip_prefix(&prefix).unwrap_or(rest);
+            values[0] = Some(unquote(value.trim()).to_string());
+            return Ok(Self { name, values });
+        }
+
+        let mut tokens = tokenize(rest)?.into_iter();
+        while let Some((start, token)) = tokens.next() {
+            let (index, value) = match keyed(&token) {
+                Some((key, value)) => {
+                    let index =
+                        specs
+                            .iter()
+                            .position(|spec| spec.key() == key)
+                            .ok_or_else(|| {
+                                parsing_issue(format!("Unknown parameter {key} for {name}"))
+                            })?;
+                    let value = match value {
+                        Some(value) => value,
+                        None => tokens
+                            .next()
+                            .map(|(_, value)| value)
+                            .ok_or_else(|| parsing_issue(format!("Missing value for {key}")))?,
+                    };
+                    (index, value)
+                }
+                None => {
+                    let index = values
+                        .iter()
+                        .position(|value| value.is_none())
+                        .ok_or_else(|| parsing_issue(format!("Too many values for {name}")))?;
+                    // A command to save is the rest of the line, with its own parameters.
+                    if let CommandParameterSpec::Command { description: _ } = specs[index] {
+                        values[index] = Some(unquote(rest[start..].trim()).to_string());
+                        break;
+                    }
+                    (index, token)
+                }
+            };
+            values[index] = Some(value);
+        }
+
+        Ok(Self { name, values })
+    }
+
+    pub fn name(&self) -> &CommandName {
+        &self.name
+    }
+
+    pub fn has_values(&self) -> bool {
+        self.values.iter().any(|value| value.is_some())
+    }
+
+    /// Records a value entered when the command asked for it, so the whole line can be
+    /// kept in the history.
+    pub fn set_value(&mut self, spec: &CommandParameterSpec, value: &str) {
+        let index = self
+            .name
+            .parameters()
+            .iter()
+            .position(|it| it.key() == spec.key());
+        if let Some(index) = index {
+            self.values[index] = Some(value.to_string());
+        }
+    }
+
+    /// Each of the command's parameters with the value given for it, if any.
+    pub fn parameters(&self) -> Vec<(CommandParameterSpec, Option<String>)> {
+        self.name
+            .parameters()
+            .into_iter()
+            .zip(self.values.iter().cloned())
+            .collect()
+    }
+}
+
+impl Display for CommandLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        let parameters = self.parameters();
+        if let [(_, Some(value))] = parameters.as_slice() {
+            return write!(f, " {value}");
+        }
+
+        for (spec, value) in parameters {
+            if let Some(value) = value {
+                write!(f, " {}={}", spec.key(), quote(&value))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parsing_issue(message: String) -> InteractiveError {
+    InteractiveError::ParsingIssue { message }
+}
+
+/// The key and, when it's joined on with `=`, the value of a keyed token. A key given
+/// as `--key` on its own takes the next token as its value.
+fn keyed(token: &str) -> Option<(String, Option<String>)> {
+    let normalize = |key: &str| key.to_lowercase().replace('_', "-");
+    if let Some(flag) = token.strip_prefix("--") {
+        return Some(match flag.split_once('=') {
+            Some((key, value)) => (normalize(key), Some(value.to_string())),
+            None => (normalize(flag), None),
+        });
+    }
+
+    token
+        .split_once('=')
+        .filter(|(key, _)| !key.is_empty() && !key.contains(' '))
+        .map(|(key, value)| (normalize(key), Some(value.to_string())))
+}
+
+/// Splits on whitespace, keeping anything in single or double quotes together. Each
+/// token comes with where it starts in the text.
+fn tokenize(text: &str) -> Result<Vec<(usize, String)>, InteractiveError> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut start: Option<usize> = None;
+    let mut quote: Option<char> = None;
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => token.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                start.get_or_insert(index);
+            }
+            None if c.is_whitespace() => {
+                if let Some(start) = start.take() {
+                    tokens.push((start, std::mem::take(&mut token)));
+                }
+            }
+            None => {
+                token.push(c);
+                start.get_or_insert(index);
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err(parsing_issue("Unclosed quote".to_string()));
+    }
+    if let Some(start) = start {
+        tokens.push((start, token));
+    }
+
+    Ok(tokens)
+}
+
+/// Quotes a value with spaces in it, with whichever quote it doesn't contain.
+fn quote(value: &str) -> String {
+    if !value.is_empty() && !value.contains(char::is_whitespace) {
+        return value.to_string();
+    }
+
+    let q = if value.contains('"') { '\'' } else { '"' };
+    format!("{q}{value}{q}")
+}
+
+fn unquote(text: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|q| text.strip_prefix(q).and_then(|inner| inner.strip_suffix(q)))
+        .unwrap_or(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::CommandName;
+
+    use super::CommandLine;
+
+    fn given(line: &CommandLine) -> Vec<(&'static str, String)> {
+        line.parameters()
+            .into_iter()
+            .filter_map(|(spec, value)| value.map(|value| (spec.key(), value)))
+            .collect()
+    }
+
+    #[test]
+    fn parses_keyed_and_positional_values() {
+        let line = CommandLine::parse("top artists count=20 year=2022 month=6").unwrap();
+        assert_eq!(&CommandName::TopArtists, line.name());
+        assert_eq!(
+            vec![
+                ("count", "20".to_string()),
+                ("year", "2022".to_string()),
+                ("month", "6".to_string())
+            ],
+            given(&line)
+        );
+
+        let line =
+            CommandLine::parse("chart 2021 --breakdown weekday --artist \"Ulcerate\"").unwrap();
+        assert_eq!(
+            vec![
+                ("year", "2021".to_string()),
+                ("artist", "Ulcerate".to_string()),
+                ("breakdown", "weekday".to_string())
+            ],
+            given(&line)
+        );
+
+        let line = CommandLine::parse("artist songs 'Portal Of I'").unwrap();
+        assert_eq!(&CommandName::ArtistSongs, line.name());
+        assert_eq!(vec![("artist", "Portal Of I".to_string())], given(&line));
+
+        let line = CommandLine::parse("query artist = Mgła and year = 2022").unwrap();
+        assert_eq!(
+            vec![("query", "artist = Mgła and year = 2022".to_string())],
+            given(&line)
+        );
+
+        let line = CommandLine::parse("top songs").unwrap();
+        assert!(!line.has_values());
+    }
+
+    #[test]
+    fn writes_the_line_back() {
+        let mut line = CommandLine::parse("chart 2021 --breakdown weekday").unwrap();
+        let (spec, _) = line.parameters().remove(1);
+        line.set_value(&spec, "Portal Of I");
+        let text = line.to_string();
+        assert_eq!(
+            "chart year=2021 artist=\"Portal Of I\" breakdown=weekday",
+            text
+        );
+        assert_eq!(given(&line), given(&CommandLine::parse(&text).unwrap()));
+
+        let line = CommandLine::parse("query artist = \"Mgła\"").unwrap();
+        assert_eq!("query artist = \"Mgła\"", line.to_string());
+    }
+
+    #[test]
+    fn keeps_the_rest_of_the_line_as_a_command_to_save() {
+        let line = CommandLine::parse(
+            "save query monthly-report top artists count=25 month=\"this month\"",
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                ("name", "monthly-report".to_string()),
+                (
+                    "command",
+                    "top artists count=25 month=\"this month\"".to_string()
+                )
+            ],
+            given(&line)
+        );
+        assert_eq!(
+            given(&line),
+            given(&CommandLine::parse(&line.to_string()).unwrap())
+        );
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        assert!(CommandLine::parse("top artists colour=red").is_err());
+        assert!(CommandLine::parse("chart --artist").is_err());
+        assert!(CommandLine::parse("chart \"2021").is_err());
+        assert!(CommandLine::parse("song Mgła Exercises Extra").is_err());
+        assert!(CommandLine::parse("top bananas").is_err());
+    }
+}
@@ -2,23 +2,42 @@ use serde::{Deserialize, Serialize};
 use std::{fmt::Display, str::FromStr};
 use strum_macros::EnumIter;
 
-use crate::persistence::Format;
+use crate::{persistence::Format, projections::statistics::Metric};
 
-use super::{chart::BarBreakdown, CommandParameterSpec, CommandParameters};
+use super::{chart::BarBreakdown, CommandParameterSpec, CommandParameters, ReportFormat};
 
 #[derive(Clone, Deserialize, Serialize, EnumIter, PartialEq, Debug)]
 pub enum CommandName {
     RandomArtists,
     TopArtists,
     ArtistSongs,
+    Artist,
+    Song,
     ArtistsOnDay,
     Summarize,
     ProcessListens,
     TopAlbums,
     TopSongs,
     MostSkipped,
+    SkipRate,
     Export,
     Chart,
+    DiscoveredArtists,
+    NewArtistsChart,
+    ForgottenArtists,
+    Streaks,
+    Wrapped,
+    Compare,
+    Query,
+    AliasArtist,
+    UnaliasArtist,
+    NormalizeTitles,
+    PreviewTitles,
+    ArtistCredits,
+    Reclassify,
+    SaveQuery,
+    RunQuery,
+    DeleteQuery,
     ClearOutput,
 }
 
@@ -27,15 +46,34 @@ impl Display for CommandName {
         let value = match *self {
             Self::RandomArtists => "random artists",
             Self::ArtistSongs => "artist songs",
+            Self::Artist => "artist",
+            Self::Song => "song",
             Self::ArtistsOnDay => "artists on day",
             Self::Summarize => "summarize",
             Self::ProcessListens => "process",
             Self::TopArtists => "top artists",
             Self::TopSongs => "top songs",
             Self::MostSkipped => "most skipped",
+            Self::SkipRate => "skip rate",
             Self::Export => "export",
             Self::Chart => "chart",
             Self::TopAlbums => "top albums",
+            Self::DiscoveredArtists => "discovered artists",
+            Self::NewArtistsChart => "new artists chart",
+            Self::ForgottenArtists => "forgotten artists",
+            Self::Streaks => "streaks",
+            Self::Wrapped => "wrapped",
+            Self::Compare => "compare",
+            Self::Query => "query",
+            Self::AliasArtist => "alias artist",
+            Self::UnaliasArtist => "unalias artist",
+            Self::NormalizeTitles => "normalize titles",
+            Self::PreviewTitles => "preview titles",
+            Self::ArtistCredits => "artist credits",
+            Self::Reclassify => "reclassify",
+            Self::SaveQuery => "save query",
+            Self::RunQuery => "run",
+            Self::DeleteQuery => "delete query",
             Self::ClearOutput => "clear output",
         };
 
@@ -50,15 +88,34 @@ impl FromStr for CommandName {
         match s {
             "random artists" => Ok(Self::RandomArtists),
             "artist songs" => Ok(Self::ArtistSongs),
+            "artist" => Ok(Self::Artist),
+            "song" => Ok(Self::Song),
             "artists on day" => Ok(Self::ArtistsOnDay),
             "summarize" => Ok(Self::Summarize),
             "process" => Ok(Self::ProcessListens),
             "top artists" => Ok(Self::TopArtists),
             "top songs" => Ok(Self::TopSongs),
             "most skipped" => Ok(Self::MostSkipped),
+            "skip rate" => Ok(Self::SkipRate),
             "export" => Ok(Self::Export),
             "chart" => Ok(Self::Chart),
             "top albums" => Ok(Self::TopAlbums),
+            "discovered artists" => Ok(Self::DiscoveredArtists),
+            "new artists chart" => Ok(Self::NewArtistsChart),
+            "forgotten artists" => Ok(Self::ForgottenArtists),
+            "streaks" => Ok(Self::Streaks),
+            "wrapped" => Ok(Self::Wrapped),
+            "compare" => Ok(Self::Compare),
+            "query" => Ok(Self::Query),
+            "alias artist" => Ok(Self::AliasArtist),
+            "unalias artist" => Ok(Self::UnaliasArtist),
+            "normalize titles" => Ok(Self::NormalizeTitles),
+            "preview titles" => Ok(Self::PreviewTitles),
+            "artist credits" => Ok(Self::ArtistCredits),
+            "reclassify" => Ok(Self::Reclassify),
+            "save query" => Ok(Self::SaveQuery),
+            "run" => Ok(Self::RunQuery),
+            "delete query" => Ok(Self::DeleteQuery),
             "clear output" => Ok(Self::ClearOutput),
             _ => Err("Unknown text".to_string()),
         }
@@ -69,15 +126,32 @@ const DEFAULT_ARTIST_COUNT: usize = 5;
 const DEFAULT_SONG_COUNT: usize = 20;
 const DEFAULT_ALBUM_COUNT: usize = 10;
 const DEFAULT_MIN_LISTENS: u64 = 5;
+const DEFAULT_MIN_PLAYS: u64 = 5;
+const DEFAULT_FORGOTTEN_MONTHS: u32 = 6;
 const DEFAULT_INPUT_FOLDER: &str = "./data/play_history";
 const DEFAULT_OUTPUT_FOLDER: &str = "./output";
 const DEFAULT_FILE_FORMAT: Format = Format::Yaml;
+const PERIOD_FORMATS: &str =
+    "a year (2022), month (2022-06) or date range (2022-01-01..2022-03-31)";
+const FROM_DESCRIPTION: &str =
+    "Start of a date range, YYYY-MM-DD or N days ago (optional, replaces year and month)";
+const TO_DESCRIPTION: &str =
+    "End of a date range, YYYY-MM-DD or N days ago (optional, replaces year and month)";
+const TITLE_RULES_DESCRIPTION: &str =
+    "Title rules to turn on, any of remaster, live, featured, edition, unicode and credits, or all or none";
+const METRIC_DESCRIPTION: &str = "Rank by plays or minutes listened (default: plays)";
 
 impl CommandName {
     pub fn description(&self) -> &str {
         match *self {
-            Self::RandomArtists => "Select a number of random artists from some parameters",
+            Self::RandomArtists => {
+                "Select a number of random artists, favouring ones you haven't listened to in a while"
+            }
             Self::ArtistSongs => "List out the songs you've listened to from an artist",
+            Self::Artist => {
+                "Show an artist's plays, top songs and albums, monthly chart, skip rate and top days"
+            }
+            Self::Song => "List every time you played a song, with its skips and a chart over time",
             Self::ArtistsOnDay => "List all the songs you listened to on a specific day",
             Self::Summarize => "Print out a summary of your listens, either for a year or all time",
             Self::ProcessListens => {
@@ -85,10 +159,43 @@ impl CommandName {
             }
             Self::TopArtists => "Return the most listened to artists",
             Self::TopSongs => "Return the most listened to songs",
-            Self::MostSkipped => "Return the most skipped songs of all time",
+            Self::MostSkipped => "Return the most skipped songs",
+            Self::SkipRate => "Return the songs with the highest skip rate",
             Self::Export => "Export the current output to a file",
             Self::Chart => "Create a chart of listens in a year by month",
             Self::TopAlbums => "Return the most listened to albums",
+            Self::DiscoveredArtists => "List the artists you first listened to in a year or month",
+            Self::NewArtistsChart => "Create a chart of how many new artists you found over time",
+            Self::ForgottenArtists => {
+                "List the artists you used to listen to but haven't in a number of months"
+            }
+            Self::Streaks => "Show your listening streaks and the longest gaps between artists",
+            Self::Wrapped => "Build a year in review report, optionally exported to HTML or Markdown",
+            Self::Compare => {
+                "Compare two years, months or date ranges by rank movement and listening time"
+            }
+            Self::Query => {
+                "Filter, group and order your listens, e.g. artist ~ death and weekday in (sat, sun) group by track"
+            }
+            Self::AliasArtist => {
+                "Count another spelling of an artist's name as that artist, or list the aliases"
+            }
+            Self::UnaliasArtist => "Stop counting a spelling of an artist's name as another artist",
+            Self::NormalizeTitles => {
+                "Fold remaster, live, featured and edition versions of song and album titles together"
+            }
+            Self::PreviewTitles => "Show which song and album titles would merge under title rules",
+            Self::ArtistCredits => {
+                "Split artists like \"A & B feat. C\" so each credited artist gets the play"
+            }
+            Self::Reclassify => {
+                "Change how listens are counted as skips and rebuild the statistics"
+            }
+            Self::SaveQuery => {
+                "Save a command with its parameters under a name, or list the saved queries"
+            }
+            Self::RunQuery => "Run a saved query, or list the saved queries",
+            Self::DeleteQuery => "Delete a saved query",
             Self::ClearOutput => "Clear all of the output",
         }
     }
@@ -100,8 +207,21 @@ impl CommandName {
                 month: None,
                 count: DEFAULT_ARTIST_COUNT,
                 min_listens: DEFAULT_MIN_LISTENS,
+                from: None,
+                to: None,
+            },
+            Self::ArtistSongs => CommandParameters::ArtistSongs {
+                name: None,
+                album_name: None,
+            },
+            Self::Artist => CommandParameters::Artist {
+                name: None,
+                count: DEFAULT_ALBUM_COUNT,
+            },
+            Self::Song => CommandParameters::Song {
+                artist_name: None,
+                track_name: None,
             },
-            Self::ArtistSongs => CommandParameters::ArtistSongs { name: None },
             Self::ArtistsOnDay => CommandParameters::ArtistsOnDay { date: None },
             Self::Summarize => CommandParameters::PrintStatistics { year: None },
             Self::ProcessListens => CommandParameters::GetFileNames {
@@ -111,17 +231,37 @@ impl CommandName {
                 count: DEFAULT_ARTIST_COUNT,
                 year: None,
                 month: None,
+                metric: Metric::default(),
+                from: None,
+                to: None,
             },
             Self::TopAlbums => CommandParameters::TopAlbums {
                 count: DEFAULT_ALBUM_COUNT,
                 year: None,
+                metric: Metric::default(),
+                from: None,
+                to: None,
             },
             Self::TopSongs => CommandParameters::TopSongs {
                 count: DEFAULT_SONG_COUNT,
                 year: None,
+                metric: Metric::default(),
+                from: None,
+                to: None,
             },
             Self::MostSkipped => CommandParameters::MostSkipped {
                 count: DEFAULT_SONG_COUNT,
+                year: None,
+                month: None,
+                artist_name: None,
+                from: None,
+                to: None,
+            },
+            Self::SkipRate => CommandParameters::SkipRate {
+                count: DEFAULT_SONG_COUNT,
+                min_plays: DEFAULT_MIN_PLAYS,
+                year: None,
+                month: None,
             },
             Self::Export => CommandParameters::Export {
                 output_folder: DEFAULT_OUTPUT_FOLDER.to_string(),
@@ -131,11 +271,104 @@ impl CommandName {
                 year: None,
                 breakdown: BarBreakdown::default(),
                 artist_name: None,
+                track_name: None,
+                from: None,
+                to: None,
+            },
+            Self::DiscoveredArtists => CommandParameters::DiscoveredArtists {
+                year: None,
+                month: None,
+            },
+            Self::NewArtistsChart => CommandParameters::NewArtistsChart { year: None },
+            Self::ForgottenArtists => CommandParameters::ForgottenArtists {
+                months: DEFAULT_FORGOTTEN_MONTHS,
+                min_listens: DEFAULT_MIN_LISTENS,
+                count: DEFAULT_ARTIST_COUNT,
+            },
+            Self::Streaks => CommandParameters::Streaks {
+                count: DEFAULT_ARTIST_COUNT,
+                year: None,
+                artist_name: None,
             },
+            Self::Wrapped => CommandParameters::Wrapped {
+                year: None,
+                count: DEFAULT_ARTIST_COUNT,
+                output_folder: None,
+                report_format: ReportFormat::default(),
+            },
+            Self::Compare => CommandParameters::Compare {
+                first: None,
+                second: None,
+                count: DEFAULT_SONG_COUNT,
+                metric: Metric::default(),
+            },
+            Self::Query => CommandParameters::Query { query: None },
+            Self::AliasArtist => CommandParameters::AliasArtist {
+                alias: None,
+                artist_name: None,
+            },
+            Self::UnaliasArtist => CommandParameters::UnaliasArtist { alias: None },
+            Self::NormalizeTitles => CommandParameters::NormalizeTitles { rules: None },
+            Self::PreviewTitles => CommandParameters::PreviewTitles {
+                rules: None,
+                count: DEFAULT_SONG_COUNT,
+            },
+            Self::ArtistCredits => CommandParameters::ArtistCredits {
+                separators: None,
+                exceptions: None,
+            },
+            Self::Reclassify => CommandParameters::Reclassify {
+                service: None,
+                min_listen_ms: None,
+                min_percent: None,
+                honour_skipped_flag: None,
+                honour_reason_end: None,
+            },
+            Self::SaveQuery => CommandParameters::SaveQuery {
+                name: None,
+                command: None,
+            },
+            Self::RunQuery => CommandParameters::RunQuery { name: None },
+            Self::DeleteQuery => CommandParameters::DeleteQuery { name: None },
             Self::ClearOutput => CommandParameters::ClearOutput,
         }
     }
 
+    /// Whether the command works on the saved queries, so isn't one that can be saved.
+    pub fn is_saved_query_command(&self) -> bool {
+        matches!(self, Self::SaveQuery | Self::RunQuery | Self::DeleteQuery)
+    }
+
+    /// Whether the command can't run without the parameter. When parameters are given on
+    /// the command line, these are the only ones still asked for.
+    pub fn is_required(&self, spec: &CommandParameterSpec) -> bool {
+        matches!(
+            (self, spec),
+            (
+                Self::ArtistSongs | Self::Artist | Self::Song | Self::AliasArtist,
+                CommandParameterSpec::ArtistName { description: _ }
+            ) | (
+                Self::Song,
+                CommandParameterSpec::TrackName { description: _ }
+            ) | (
+                Self::ArtistsOnDay,
+                CommandParameterSpec::Date { description: _ }
+            ) | (Self::Query, CommandParameterSpec::Query { description: _ })
+                | (
+                    Self::AliasArtist | Self::UnaliasArtist,
+                    CommandParameterSpec::Alias { description: _ }
+                )
+                | (
+                    Self::NormalizeTitles | Self::PreviewTitles,
+                    CommandParameterSpec::TitleRules { description: _ }
+                )
+                | (
+                    Self::DeleteQuery,
+                    CommandParameterSpec::SavedQueryName { description: _ }
+                )
+        )
+    }
+
     pub fn parameters(&self) -> Vec<CommandParameterSpec> {
         match *self {
             CommandName::RandomArtists => vec![
@@ -155,10 +388,41 @@ impl CommandName {
                 CommandParameterSpec::Month {
                     description: "Month to search in (optional, 1-12)".to_string(),
                 },
+                CommandParameterSpec::From {
+                    description: FROM_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::To {
+                    description: TO_DESCRIPTION.to_string(),
+                },
+            ],
+            CommandName::ArtistSongs => vec![
+                CommandParameterSpec::ArtistName {
+                    description: "The name of the artist to get songs of (Tab to complete)"
+                        .to_string(),
+                },
+                CommandParameterSpec::AlbumName {
+                    description: "Only list the songs on this album (optional, Tab to complete)"
+                        .to_string(),
+                },
+            ],
+            CommandName::Artist => vec![
+                CommandParameterSpec::ArtistName {
+                    description: "The name of the artist (Tab to complete)".to_string(),
+                },
+                CommandParameterSpec::Count {
+                    description: format!(
+                        "Number of songs, albums and days to list (default: {DEFAULT_ALBUM_COUNT})"
+                    ),
+                },
+            ],
+            CommandName::Song => vec![
+                CommandParameterSpec::ArtistName {
+                    description: "The name of the artist (Tab to complete)".to_string(),
+                },
+                CommandParameterSpec::TrackName {
+                    description: "The name of the song (Tab to complete)".to_string(),
+                },
             ],
-            CommandName::ArtistSongs => vec![CommandParameterSpec::ArtistName {
-                description: "The name of the artist to get songs of".to_string(),
-            }],
             CommandName::ArtistsOnDay => vec![CommandParameterSpec::Date {
                 description: "Date to search on (required, format YYYY-MM-DD)".to_string(),
             }],
@@ -181,7 +445,16 @@ impl CommandName {
                 },
                 CommandParameterSpec::Month {
                     description: "Month to search in(optional, 1-12)".to_string(),
-                }
+                },
+                CommandParameterSpec::Metric {
+                    description: METRIC_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::From {
+                    description: FROM_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::To {
+                    description: TO_DESCRIPTION.to_string(),
+                },
             ],
             CommandName::TopSongs => vec![
                 CommandParameterSpec::Count {
@@ -192,6 +465,15 @@ impl CommandName {
                 CommandParameterSpec::Year {
                     description: "Year to search in (optional, e.g 2022)".to_string(),
                 },
+                CommandParameterSpec::Metric {
+                    description: METRIC_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::From {
+                    description: FROM_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::To {
+                    description: TO_DESCRIPTION.to_string(),
+                },
             ],
             CommandName::TopAlbums => vec![
                 CommandParameterSpec::Count {
@@ -202,22 +484,67 @@ impl CommandName {
                 CommandParameterSpec::Year {
                     description: "Year to search in (optional, e.g 2022)".to_string(),
                 },
+                CommandParameterSpec::Metric {
+                    description: METRIC_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::From {
+                    description: FROM_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::To {
+                    description: TO_DESCRIPTION.to_string(),
+                },
+            ],
+            CommandName::MostSkipped => vec![
+                CommandParameterSpec::Count {
+                    description: format!(
+                        "Number of songs to return (default: {DEFAULT_SONG_COUNT})"
+                    ),
+                },
+                CommandParameterSpec::Year {
+                    description: "Year to search in (optional, e.g 2022)".to_string(),
+                },
+                CommandParameterSpec::Month {
+                    description: "Month to search in (optional, 1-12)".to_string(),
+                },
+                CommandParameterSpec::ArtistName {
+                    description: "If you'd like to filter by artist, enter the name (Tab to complete)".to_string()
+                },
+                CommandParameterSpec::TrackName {
+                    description: "If you'd like to chart a single song, enter the name (optional, Tab to complete)".to_string()
+                },
+                CommandParameterSpec::From {
+                    description: FROM_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::To {
+                    description: TO_DESCRIPTION.to_string(),
+                },
+            ],
+            CommandName::SkipRate => vec![
+                CommandParameterSpec::Count {
+                    description: format!(
+                        "Number of songs to return (default: {DEFAULT_SONG_COUNT})"
+                    ),
+                },
+                CommandParameterSpec::MinPlays {
+                    description: format!(
+                        "Minimum number of plays of a song, not counting skips (default: {DEFAULT_MIN_PLAYS})"
+                    ),
+                },
+                CommandParameterSpec::Year {
+                    description: "Year to search in (optional, e.g 2022)".to_string(),
+                },
+                CommandParameterSpec::Month {
+                    description: "Month to search in (optional, 1-12)".to_string(),
+                },
             ],
-            CommandName::MostSkipped => vec![CommandParameterSpec::Count {
-                description: format!(
-                    "Number of songs to return (default: {DEFAULT_SONG_COUNT})"
-                ),
-            }],
             CommandName::Export => vec![
                 CommandParameterSpec::OutputFolder {
                     description: format!("Where to put the file (default: {DEFAULT_OUTPUT_FOLDER})")
                 },
                 CommandParameterSpec::FileFormat {
                     description: format!(
-                        "What file format to use ({} or {}, default: {})",
-                        Format::Json.extension_display(),
-                        Format::Yaml.extension_display(),
-                        Format::Yaml.extension_display()
+                        "What file format to use (json, yaml, csv, markdown, html, or svg or png for charts, default: {})",
+                        DEFAULT_FILE_FORMAT.extension_display()
                     )
                 }
             ],
@@ -230,8 +557,159 @@ impl CommandName {
                 },
                 CommandParameterSpec::BarBreakdown {
                     description: "How do you want to break down the data (weekday or month, defaults to month)".to_string()
-                }
+                },
+                CommandParameterSpec::From {
+                    description: FROM_DESCRIPTION.to_string(),
+                },
+                CommandParameterSpec::To {
+                    description: TO_DESCRIPTION.to_string(),
+                },
+            ],
+            CommandName::DiscoveredArtists => vec![
+                CommandParameterSpec::Year {
+                    description: "Year the artists were discovered in (optional, e.g 2023)".to_string(),
+                },
+                CommandParameterSpec::Month {
+                    description: "Month the artists were discovered in (optional, 1-12)".to_string(),
+                },
+            ],
+            CommandName::NewArtistsChart => vec![CommandParameterSpec::Year {
+                description: "What year for the chart (optional, leave empty for every month since your first listen)".to_string(),
+            }],
+            CommandName::ForgottenArtists => vec![
+                CommandParameterSpec::Months {
+                    description: format!(
+                        "Number of months since you last listened to the artist (default: {DEFAULT_FORGOTTEN_MONTHS})"
+                    ),
+                },
+                CommandParameterSpec::MinListens {
+                    description: format!(
+                        "Minimum number of listens to filter artists by (default: {DEFAULT_MIN_LISTENS})"
+                    ),
+                },
+                CommandParameterSpec::Count {
+                    description: format!(
+                        "Number of artists to return (default: {DEFAULT_ARTIST_COUNT})"
+                    ),
+                },
+            ],
+            CommandName::Streaks => vec![
+                CommandParameterSpec::Count {
+                    description: format!(
+                        "Number of artists to return (default: {DEFAULT_ARTIST_COUNT})"
+                    ),
+                },
+                CommandParameterSpec::Year {
+                    description: "Year to search in (optional, e.g 2022)".to_string(),
+                },
+                CommandParameterSpec::ArtistName {
+                    description: "If you'd like to filter by artist, enter the name.".to_string()
+                },
             ],
+            CommandName::Wrapped => vec![
+                CommandParameterSpec::Year {
+                    description: "Year to review (default: the latest year with listens)"
+                        .to_string(),
+                },
+                CommandParameterSpec::Count {
+                    description: format!(
+                        "Number of artists, songs and albums per page (default: {DEFAULT_ARTIST_COUNT})"
+                    ),
+                },
+                CommandParameterSpec::OutputFolder {
+                    description: "Folder to export the report to (optional)".to_string(),
+                },
+                CommandParameterSpec::ReportFormat {
+                    description: "Format of the exported report, html or markdown (default: html)"
+                        .to_string(),
+                },
+            ],
+            CommandName::Compare => vec![
+                CommandParameterSpec::FirstPeriod {
+                    description: format!(
+                        "First period, {PERIOD_FORMATS} (default: the year before the second period)"
+                    ),
+                },
+                CommandParameterSpec::SecondPeriod {
+                    description: format!(
+                        "Second period, {PERIOD_FORMATS} (default: the latest year with listens)"
+                    ),
+                },
+                CommandParameterSpec::Count {
+                    description: format!(
+                        "Number of artists and songs to rank (default: {DEFAULT_SONG_COUNT})"
+                    ),
+                },
+                CommandParameterSpec::Metric {
+                    description: METRIC_DESCRIPTION.to_string(),
+                },
+            ],
+            CommandName::Query => vec![CommandParameterSpec::Query {
+                description: "Query on artist, track, album, service, date, year, month, hour, time, weekday or skipped, joined by and, then optionally group by FIELD, order by count|minutes|name|time [asc|desc] and limit N".to_string(),
+            }],
+            CommandName::AliasArtist => vec![
+                CommandParameterSpec::Alias {
+                    description: "The spelling to merge, e.g. Mgla (leave empty to list the aliases)".to_string(),
+                },
+                CommandParameterSpec::ArtistName {
+                    description: "The artist to count it as, e.g. Mgła".to_string(),
+                },
+            ],
+            CommandName::UnaliasArtist => vec![CommandParameterSpec::Alias {
+                description: "The spelling to stop merging".to_string(),
+            }],
+            CommandName::NormalizeTitles => vec![CommandParameterSpec::TitleRules {
+                description: format!("{TITLE_RULES_DESCRIPTION} (leave empty to show the current rules)"),
+            }],
+            CommandName::PreviewTitles => vec![
+                CommandParameterSpec::TitleRules {
+                    description: format!("{TITLE_RULES_DESCRIPTION} (default: the current rules)"),
+                },
+                CommandParameterSpec::Count {
+                    description: format!(
+                        "Number of merged titles to show (default: {DEFAULT_SONG_COUNT})"
+                    ),
+                },
+            ],
+            CommandName::ArtistCredits => vec![
+                CommandParameterSpec::Separators {
+                    description: "Separators between artists, e.g. & , x or off (leave empty to keep current)".to_string(),
+                },
+                CommandParameterSpec::CreditExceptions {
+                    description: "Artists never to split, separated by ; e.g. Simon & Garfunkel; Earth, Wind & Fire, or none (leave empty to keep current)".to_string(),
+                },
+            ],
+            CommandName::Reclassify => vec![
+                CommandParameterSpec::Service {
+                    description: "What service to change the rules of (spotify or apple_music, leave empty for the default rules)".to_string()
+                },
+                CommandParameterSpec::MinListenTime {
+                    description: "Minimum seconds played to count as a listen (optional, leave empty to keep current)".to_string()
+                },
+                CommandParameterSpec::MinPercent {
+                    description: "Minimum percent of the track played to count as a listen (optional, 0-100)".to_string()
+                },
+                CommandParameterSpec::HonourSkippedFlag {
+                    description: "Count listens the service flagged as skipped as skips (optional, yes or no)".to_string()
+                },
+                CommandParameterSpec::HonourReasonEnd {
+                    description: "Count listens that ended with a skip button as skips (optional, yes or no)".to_string()
+                },
+            ],
+            CommandName::SaveQuery => vec![
+                CommandParameterSpec::SavedQueryName {
+                    description: "Name to save the command as, e.g. monthly-report (leave empty to list the saved queries)".to_string(),
+                },
+                CommandParameterSpec::Command {
+                    description: "The command with its parameters, e.g. top artists count=25 month=\"this month\" (leave empty for the last command run)".to_string(),
+                },
+            ],
+            CommandName::RunQuery => vec![CommandParameterSpec::SavedQueryName {
+                description: "The saved query to run (leave empty to list the saved queries)".to_string(),
+            }],
+            CommandName::DeleteQuery => vec![CommandParameterSpec::SavedQueryName {
+                description: "The saved query to delete".to_string(),
+            }],
             CommandName::ClearOutput => Vec::new(),
         }
     }
@@ -3,15 +3,20 @@ use std::path::PathBuf;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
-use crate::persistence::Format;
+use crate::{
+    persistence::Format,
+    processing::TitleNormalizer,
+    projections::statistics::{Metric, Period},
+};
 
-use super::chart::BarBreakdown;
+use super::{chart::BarBreakdown, ReportFormat};
 
 #[derive(Deserialize, Serialize)]
 pub enum CommandParameterSpec {
     Year { description: String },
     Month { description: String },
     MinListens { description: String },
+    MinPlays { description: String },
     Count { description: String },
     Date { description: String },
     ArtistName { description: String },
@@ -19,6 +24,27 @@ pub enum CommandParameterSpec {
     OutputFolder { description: String },
     FileFormat { description: String },
     BarBreakdown { description: String },
+    Service { description: String },
+    MinListenTime { description: String },
+    MinPercent { description: String },
+    HonourSkippedFlag { description: String },
+    HonourReasonEnd { description: String },
+    Metric { description: String },
+    Months { description: String },
+    ReportFormat { description: String },
+    FirstPeriod { description: String },
+    SecondPeriod { description: String },
+    From { description: String },
+    To { description: String },
+    Query { description: String },
+    Alias { description: String },
+    TitleRules { description: String },
+    TrackName { description: String },
+    AlbumName { description: String },
+    Separators { description: String },
+    CreditExceptions { description: String },
+    SavedQueryName { description: String },
+    Command { description: String },
 }
 
 impl CommandParameterSpec {
@@ -27,40 +53,121 @@ impl CommandParameterSpec {
             CommandParameterSpec::Year { description }
             | CommandParameterSpec::Month { description }
             | CommandParameterSpec::MinListens { description }
+            | CommandParameterSpec::MinPlays { description }
             | CommandParameterSpec::Date { description }
             | CommandParameterSpec::ArtistName { description }
             | CommandParameterSpec::Count { description }
             | CommandParameterSpec::InputFolder { description }
             | CommandParameterSpec::OutputFolder { description }
             | CommandParameterSpec::FileFormat { description }
-            | CommandParameterSpec::BarBreakdown { description } => description.clone(),
+            | CommandParameterSpec::BarBreakdown { description }
+            | CommandParameterSpec::Service { description }
+            | CommandParameterSpec::MinListenTime { description }
+            | CommandParameterSpec::MinPercent { description }
+            | CommandParameterSpec::HonourSkippedFlag { description }
+            | CommandParameterSpec::HonourReasonEnd { description }
+            | CommandParameterSpec::Metric { description }
+            | CommandParameterSpec::Months { description }
+            | CommandParameterSpec::ReportFormat { description }
+            | CommandParameterSpec::FirstPeriod { description }
+            | CommandParameterSpec::SecondPeriod { description }
+            | CommandParameterSpec::From { description }
+            | CommandParameterSpec::To { description }
+            | CommandParameterSpec::Query { description }
+            | CommandParameterSpec::Alias { description }
+            | CommandParameterSpec::TitleRules { description }
+            | CommandParameterSpec::TrackName { description }
+            | CommandParameterSpec::AlbumName { description }
+            | CommandParameterSpec::Separators { description }
+            | CommandParameterSpec::CreditExceptions { description }
+            | CommandParameterSpec::SavedQueryName { description }
+            | CommandParameterSpec::Command { description } => description.clone(),
+        }
+    }
+
+    /// The name the parameter is given by on the command line, as `key=value` or
+    /// `--key value`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            CommandParameterSpec::Year { description: _ } => "year",
+            CommandParameterSpec::Month { description: _ } => "month",
+            CommandParameterSpec::MinListens { description: _ } => "min-listens",
+            CommandParameterSpec::MinPlays { description: _ } => "min-plays",
+            CommandParameterSpec::Count { description: _ } => "count",
+            CommandParameterSpec::Date { description: _ } => "date",
+            CommandParameterSpec::ArtistName { description: _ } => "artist",
+            CommandParameterSpec::InputFolder { description: _ } => "input-folder",
+            CommandParameterSpec::OutputFolder { description: _ } => "output-folder",
+            CommandParameterSpec::FileFormat { description: _ } => "format",
+            CommandParameterSpec::BarBreakdown { description: _ } => "breakdown",
+            CommandParameterSpec::Service { description: _ } => "service",
+            CommandParameterSpec::MinListenTime { description: _ } => "min-listen-time",
+            CommandParameterSpec::MinPercent { description: _ } => "min-percent",
+            CommandParameterSpec::HonourSkippedFlag { description: _ } => "honour-skipped-flag",
+            CommandParameterSpec::HonourReasonEnd { description: _ } => "honour-reason-end",
+            CommandParameterSpec::Metric { description: _ } => "metric",
+            CommandParameterSpec::Months { description: _ } => "months",
+            CommandParameterSpec::ReportFormat { description: _ } => "report-format",
+            CommandParameterSpec::FirstPeriod { description: _ } => "first",
+            CommandParameterSpec::SecondPeriod { description: _ } => "second",
+            CommandParameterSpec::From { description: _ } => "from",
+            CommandParameterSpec::To { description: _ } => "to",
+            CommandParameterSpec::Query { description: _ } => "query",
+            CommandParameterSpec::Alias { description: _ } => "alias",
+            CommandParameterSpec::TitleRules { description: _ } => "rules",
+            CommandParameterSpec::TrackName { description: _ } => "track",
+            CommandParameterSpec::AlbumName { description: _ } => "album",
+            CommandParameterSpec::Separators { description: _ } => "separators",
+            CommandParameterSpec::CreditExceptions { description: _ } => "exceptions",
+            CommandParameterSpec::SavedQueryName { description: _ } => "name",
+            CommandParameterSpec::Command { description: _ } => "command",
         }
     }
 }
 
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum CommandParameters {
     RandomArtists {
         year: Option<i32>,
         month: Option<u32>,
         count: usize,
         min_listens: u64,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
     },
     TopArtists {
         count: usize,
         year: Option<i32>,
         month: Option<u32>,
+        metric: Metric,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
     },
     TopAlbums {
         count: usize,
         year: Option<i32>,
+        metric: Metric,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
     },
     TopSongs {
         count: usize,
         year: Option<i32>,
+        metric: Metric,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
     },
     ArtistSongs {
         name: Option<String>,
+        album_name: Option<String>,
+    },
+    Artist {
+        name: Option<String>,
+        count: usize,
+    },
+    Song {
+        artist_name: Option<String>,
+        track_name: Option<String>,
     },
     ArtistsOnDay {
         date: Option<NaiveDate>,
@@ -76,6 +183,17 @@ pub enum CommandParameters {
     },
     MostSkipped {
         count: usize,
+        year: Option<i32>,
+        month: Option<u32>,
+        artist_name: Option<String>,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    SkipRate {
+        count: usize,
+        min_plays: u64,
+        year: Option<i32>,
+        month: Option<u32>,
     },
     Export {
         output_folder: String,
@@ -84,7 +202,77 @@ pub enum CommandParameters {
     Chart {
         year: Option<i32>,
         artist_name: Option<String>,
+        track_name: Option<String>,
         breakdown: BarBreakdown,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    DiscoveredArtists {
+        year: Option<i32>,
+        month: Option<u32>,
+    },
+    NewArtistsChart {
+        year: Option<i32>,
+    },
+    ForgottenArtists {
+        months: u32,
+        min_listens: u64,
+        count: usize,
+    },
+    Streaks {
+        count: usize,
+        year: Option<i32>,
+        artist_name: Option<String>,
+    },
+    Wrapped {
+        year: Option<i32>,
+        count: usize,
+        output_folder: Option<String>,
+        report_format: ReportFormat,
+    },
+    Compare {
+        first: Option<Period>,
+        second: Option<Period>,
+        count: usize,
+        metric: Metric,
+    },
+    Query {
+        query: Option<String>,
+    },
+    AliasArtist {
+        alias: Option<String>,
+        artist_name: Option<String>,
+    },
+    UnaliasArtist {
+        alias: Option<String>,
+    },
+    NormalizeTitles {
+        rules: Option<TitleNormalizer>,
+    },
+    PreviewTitles {
+        rules: Option<TitleNormalizer>,
+        count: usize,
+    },
+    ArtistCredits {
+        separators: Option<Vec<String>>,
+        exceptions: Option<Vec<String>>,
+    },
+    Reclassify {
+        service: Option<String>,
+        min_listen_ms: Option<u64>,
+        min_percent: Option<f64>,
+        honour_skipped_flag: Option<bool>,
+        honour_reason_end: Option<bool>,
+    },
+    SaveQuery {
+        name: Option<String>,
+        command: Option<String>,
+    },
+    RunQuery {
+        name: Option<String>,
+    },
+    DeleteQuery {
+        name: Option<String>,
     },
     ClearOutput,
 }
@@ -108,6 +296,34 @@ impl CommandParameters {
                 output_folder: output_folder.to_string(),
                 format: format.to_owned(),
             },
+            Self::Wrapped {
+                year,
+                count,
+                output_folder: _,
+                report_format,
+            } => Self::Wrapped {
+                year: year.to_owned(),
+                count: count.to_owned(),
+                output_folder: Some(output_folder.to_string()),
+                report_format: report_format.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_report_format_parameter(&self, report_format: ReportFormat) -> Self {
+        match self {
+            Self::Wrapped {
+                year,
+                count,
+                output_folder,
+                report_format: _,
+            } => Self::Wrapped {
+                year: year.to_owned(),
+                count: count.to_owned(),
+                output_folder: output_folder.to_owned(),
+                report_format,
+            },
             _ => self.to_owned(),
         }
     }
@@ -132,38 +348,123 @@ impl CommandParameters {
                 month,
                 count,
                 min_listens,
+                from,
+                to,
             } => Self::RandomArtists {
                 year: Some(year),
                 month: month.to_owned(),
                 count: count.to_owned(),
                 min_listens: min_listens.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
             Self::TopArtists {
                 count,
                 year: _,
                 month,
+                metric,
+                from,
+                to,
             } => Self::TopArtists {
                 count: count.to_owned(),
                 year: Some(year),
                 month: month.to_owned(),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
-            Self::TopAlbums { count, year: _ } => Self::TopAlbums {
+            Self::TopAlbums {
+                count,
+                year: _,
+                metric,
+                from,
+                to,
+            } => Self::TopAlbums {
                 count: count.to_owned(),
                 year: Some(year),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
-            Self::TopSongs { count, year: _ } => Self::TopSongs {
+            Self::TopSongs {
+                count,
+                year: _,
+                metric,
+                from,
+                to,
+            } => Self::TopSongs {
                 count: count.to_owned(),
                 year: Some(year),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
             Self::PrintStatistics { year: _ } => Self::PrintStatistics { year: Some(year) },
+            Self::DiscoveredArtists { year: _, month } => Self::DiscoveredArtists {
+                year: Some(year),
+                month: month.to_owned(),
+            },
+            Self::NewArtistsChart { year: _ } => Self::NewArtistsChart { year: Some(year) },
+            Self::Wrapped {
+                year: _,
+                count,
+                output_folder,
+                report_format,
+            } => Self::Wrapped {
+                year: Some(year),
+                count: count.to_owned(),
+                output_folder: output_folder.to_owned(),
+                report_format: report_format.to_owned(),
+            },
+            Self::Streaks {
+                count,
+                year: _,
+                artist_name,
+            } => Self::Streaks {
+                count: count.to_owned(),
+                year: Some(year),
+                artist_name: artist_name.to_owned(),
+            },
+            Self::MostSkipped {
+                count,
+                year: _,
+                month,
+                artist_name,
+                from,
+                to,
+            } => Self::MostSkipped {
+                count: count.to_owned(),
+                year: Some(year),
+                month: month.to_owned(),
+                artist_name: artist_name.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::SkipRate {
+                count,
+                min_plays,
+                year: _,
+                month,
+            } => Self::SkipRate {
+                count: count.to_owned(),
+                min_plays: min_plays.to_owned(),
+                year: Some(year),
+                month: month.to_owned(),
+            },
             Self::Chart {
                 year: _,
                 breakdown,
                 artist_name,
+                track_name,
+                from,
+                to,
             } => Self::Chart {
                 year: Some(year),
                 breakdown: breakdown.to_owned(),
                 artist_name: artist_name.to_owned(),
+                track_name: track_name.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
             _ => self.to_owned(),
         }
@@ -175,10 +476,16 @@ impl CommandParameters {
                 year,
                 breakdown: _,
                 artist_name,
+                track_name,
+                from,
+                to,
             } => Self::Chart {
                 year: year.to_owned(),
                 artist_name: artist_name.to_owned(),
+                track_name: track_name.to_owned(),
                 breakdown,
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
             _ => self.to_owned(),
         }
@@ -191,20 +498,60 @@ impl CommandParameters {
                 month: _,
                 count: artist_count,
                 min_listens,
+                from,
+                to,
             } => Self::RandomArtists {
                 year: year.to_owned(),
                 month: Some(month),
                 count: artist_count.to_owned(),
                 min_listens: min_listens.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
             Self::TopArtists {
                 count,
                 year,
                 month: _,
+                metric,
+                from,
+                to,
             } => Self::TopArtists {
                 year: year.to_owned(),
                 count: count.to_owned(),
                 month: Some(month),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::MostSkipped {
+                count,
+                year,
+                month: _,
+                artist_name,
+                from,
+                to,
+            } => Self::MostSkipped {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                month: Some(month),
+                artist_name: artist_name.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::SkipRate {
+                count,
+                min_plays,
+                year,
+                month: _,
+            } => Self::SkipRate {
+                count: count.to_owned(),
+                min_plays: min_plays.to_owned(),
+                year: year.to_owned(),
+                month: Some(month),
+            },
+            Self::DiscoveredArtists { year, month: _ } => Self::DiscoveredArtists {
+                year: year.to_owned(),
+                month: Some(month),
             },
             _ => self.to_owned(),
         }
@@ -217,31 +564,130 @@ impl CommandParameters {
                 month,
                 count: _,
                 min_listens,
+                from,
+                to,
             } => Self::RandomArtists {
                 year: year.to_owned(),
                 month: month.to_owned(),
                 count,
                 min_listens: min_listens.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
             Self::TopArtists {
                 count: _,
                 year,
                 month,
+                metric,
+                from,
+                to,
             } => Self::TopArtists {
                 count,
                 year: year.to_owned(),
                 month: month.to_owned(),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
-            Self::TopAlbums { count: _, year } => Self::TopAlbums {
+            Self::TopAlbums {
+                count: _,
+                year,
+                metric,
+                from,
+                to,
+            } => Self::TopAlbums {
                 count,
                 year: year.to_owned(),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
-            Self::TopSongs { count: _, year } => Self::TopSongs {
+            Self::TopSongs {
+                count: _,
+                year,
+                metric,
+                from,
+                to,
+            } => Self::TopSongs {
                 count,
                 year: year.to_owned(),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
             },
-            Self::MostSkipped { count: _ } => Self::MostSkipped {
-                count: count.to_owned(),
+            Self::MostSkipped {
+                count: _,
+                year,
+                month,
+                artist_name,
+                from,
+                to,
+            } => Self::MostSkipped {
+                count,
+                year: year.to_owned(),
+                month: month.to_owned(),
+                artist_name: artist_name.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::SkipRate {
+                count: _,
+                min_plays,
+                year,
+                month,
+            } => Self::SkipRate {
+                count,
+                min_plays: min_plays.to_owned(),
+                year: year.to_owned(),
+                month: month.to_owned(),
+            },
+            Self::ForgottenArtists {
+                months,
+                min_listens,
+                count: _,
+            } => Self::ForgottenArtists {
+                months: months.to_owned(),
+                min_listens: min_listens.to_owned(),
+                count,
+            },
+            Self::Streaks {
+                count: _,
+                year,
+                artist_name,
+            } => Self::Streaks {
+                count,
+                year: year.to_owned(),
+                artist_name: artist_name.to_owned(),
+            },
+            Self::Wrapped {
+                year,
+                count: _,
+                output_folder,
+                report_format,
+            } => Self::Wrapped {
+                year: year.to_owned(),
+                count,
+                output_folder: output_folder.to_owned(),
+                report_format: report_format.to_owned(),
+            },
+            Self::Compare {
+                first,
+                second,
+                count: _,
+                metric,
+            } => Self::Compare {
+                first: first.to_owned(),
+                second: second.to_owned(),
+                count,
+                metric: metric.to_owned(),
+            },
+            Self::Artist { name, count: _ } => Self::Artist {
+                name: name.to_owned(),
+                count,
+            },
+            Self::PreviewTitles { rules, count: _ } => Self::PreviewTitles {
+                rules: rules.to_owned(),
+                count,
             },
             _ => self.to_owned(),
         }
@@ -254,11 +700,225 @@ impl CommandParameters {
                 month,
                 count: artist_count,
                 min_listens: _,
+                from,
+                to,
             } => Self::RandomArtists {
                 year: year.to_owned(),
                 month: month.to_owned(),
                 count: artist_count.to_owned(),
                 min_listens,
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::ForgottenArtists {
+                months,
+                min_listens: _,
+                count,
+            } => Self::ForgottenArtists {
+                months: months.to_owned(),
+                min_listens,
+                count: count.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_min_plays_parameter(&self, min_plays: u64) -> Self {
+        match self {
+            Self::SkipRate {
+                count,
+                min_plays: _,
+                year,
+                month,
+            } => Self::SkipRate {
+                count: count.to_owned(),
+                min_plays,
+                year: year.to_owned(),
+                month: month.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_from_parameter(&self, from: NaiveDate) -> Self {
+        match self {
+            Self::RandomArtists {
+                year,
+                month,
+                count,
+                min_listens,
+                from: _,
+                to,
+            } => Self::RandomArtists {
+                year: year.to_owned(),
+                month: month.to_owned(),
+                count: count.to_owned(),
+                min_listens: min_listens.to_owned(),
+                from: Some(from),
+                to: to.to_owned(),
+            },
+            Self::TopArtists {
+                count,
+                year,
+                month,
+                metric,
+                from: _,
+                to,
+            } => Self::TopArtists {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                month: month.to_owned(),
+                metric: metric.to_owned(),
+                from: Some(from),
+                to: to.to_owned(),
+            },
+            Self::TopAlbums {
+                count,
+                year,
+                metric,
+                from: _,
+                to,
+            } => Self::TopAlbums {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                metric: metric.to_owned(),
+                from: Some(from),
+                to: to.to_owned(),
+            },
+            Self::TopSongs {
+                count,
+                year,
+                metric,
+                from: _,
+                to,
+            } => Self::TopSongs {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                metric: metric.to_owned(),
+                from: Some(from),
+                to: to.to_owned(),
+            },
+            Self::MostSkipped {
+                count,
+                year,
+                month,
+                artist_name,
+                from: _,
+                to,
+            } => Self::MostSkipped {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                month: month.to_owned(),
+                artist_name: artist_name.to_owned(),
+                from: Some(from),
+                to: to.to_owned(),
+            },
+            Self::Chart {
+                year,
+                artist_name,
+                track_name,
+                breakdown,
+                from: _,
+                to,
+            } => Self::Chart {
+                year: year.to_owned(),
+                artist_name: artist_name.to_owned(),
+                track_name: track_name.to_owned(),
+                breakdown: breakdown.to_owned(),
+                from: Some(from),
+                to: to.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_to_parameter(&self, to: NaiveDate) -> Self {
+        match self {
+            Self::RandomArtists {
+                year,
+                month,
+                count,
+                min_listens,
+                from,
+                to: _,
+            } => Self::RandomArtists {
+                year: year.to_owned(),
+                month: month.to_owned(),
+                count: count.to_owned(),
+                min_listens: min_listens.to_owned(),
+                from: from.to_owned(),
+                to: Some(to),
+            },
+            Self::TopArtists {
+                count,
+                year,
+                month,
+                metric,
+                from,
+                to: _,
+            } => Self::TopArtists {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                month: month.to_owned(),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: Some(to),
+            },
+            Self::TopAlbums {
+                count,
+                year,
+                metric,
+                from,
+                to: _,
+            } => Self::TopAlbums {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: Some(to),
+            },
+            Self::TopSongs {
+                count,
+                year,
+                metric,
+                from,
+                to: _,
+            } => Self::TopSongs {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                metric: metric.to_owned(),
+                from: from.to_owned(),
+                to: Some(to),
+            },
+            Self::MostSkipped {
+                count,
+                year,
+                month,
+                artist_name,
+                from,
+                to: _,
+            } => Self::MostSkipped {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                month: month.to_owned(),
+                artist_name: artist_name.to_owned(),
+                from: from.to_owned(),
+                to: Some(to),
+            },
+            Self::Chart {
+                year,
+                artist_name,
+                track_name,
+                breakdown,
+                from,
+                to: _,
+            } => Self::Chart {
+                year: year.to_owned(),
+                artist_name: artist_name.to_owned(),
+                track_name: track_name.to_owned(),
+                breakdown: breakdown.to_owned(),
+                from: from.to_owned(),
+                to: Some(to),
             },
             _ => self.to_owned(),
         }
@@ -271,6 +931,8 @@ impl CommandParameters {
                 month: _,
                 count: _,
                 min_listens: _,
+                from: _,
+                to: _,
             } => self.to_owned(),
             Self::ArtistsOnDay { date: _ } => Self::ArtistsOnDay { date: Some(date) },
             _ => self.to_owned(),
@@ -284,18 +946,479 @@ impl CommandParameters {
                 month: _,
                 count: _,
                 min_listens: _,
+                from: _,
+                to: _,
             } => self.to_owned(),
-            Self::ArtistSongs { name: _ } => Self::ArtistSongs {
+            Self::ArtistSongs {
+                name: _,
+                album_name,
+            } => Self::ArtistSongs {
+                name: Some(name.to_string()),
+                album_name: album_name.to_owned(),
+            },
+            Self::Artist { name: _, count } => Self::Artist {
                 name: Some(name.to_string()),
+                count: count.to_owned(),
+            },
+            Self::Song {
+                artist_name: _,
+                track_name,
+            } => Self::Song {
+                artist_name: Some(name.to_string()),
+                track_name: track_name.to_owned(),
             },
             Self::Chart {
                 year,
                 artist_name: _,
+                track_name,
                 breakdown,
+                from,
+                to,
             } => Self::Chart {
                 artist_name: Some(name.to_string()),
+                track_name: track_name.to_owned(),
+                year: year.to_owned(),
+                breakdown: breakdown.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::MostSkipped {
+                count,
+                year,
+                month,
+                artist_name: _,
+                from,
+                to,
+            } => Self::MostSkipped {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                month: month.to_owned(),
+                artist_name: Some(name.to_string()),
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::Streaks {
+                count,
+                year,
+                artist_name: _,
+            } => Self::Streaks {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                artist_name: Some(name.to_string()),
+            },
+            Self::AliasArtist {
+                alias,
+                artist_name: _,
+            } => Self::AliasArtist {
+                alias: alias.to_owned(),
+                artist_name: Some(name.to_string()),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_track_name_parameter(&self, track_name: &str) -> Self {
+        match self {
+            Self::Chart {
+                year,
+                artist_name,
+                track_name: _,
+                breakdown,
+                from,
+                to,
+            } => Self::Chart {
                 year: year.to_owned(),
+                artist_name: artist_name.to_owned(),
+                track_name: Some(track_name.to_string()),
                 breakdown: breakdown.to_owned(),
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::Song {
+                artist_name,
+                track_name: _,
+            } => Self::Song {
+                artist_name: artist_name.to_owned(),
+                track_name: Some(track_name.to_string()),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_album_name_parameter(&self, album_name: &str) -> Self {
+        match self {
+            Self::ArtistSongs {
+                name,
+                album_name: _,
+            } => Self::ArtistSongs {
+                name: name.to_owned(),
+                album_name: Some(album_name.to_string()),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    /// The artist already entered, which narrows down the songs and albums to complete.
+    pub fn artist_name(&self) -> Option<&String> {
+        match self {
+            Self::ArtistSongs {
+                name,
+                album_name: _,
+            } => name.as_ref(),
+            Self::Chart {
+                year: _,
+                artist_name,
+                track_name: _,
+                breakdown: _,
+                from: _,
+                to: _,
+            } => artist_name.as_ref(),
+            Self::Song {
+                artist_name,
+                track_name: _,
+            } => artist_name.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The end of the period already entered, if any.
+    pub fn to(&self) -> Option<&NaiveDate> {
+        match self {
+            Self::RandomArtists {
+                year: _,
+                month: _,
+                count: _,
+                min_listens: _,
+                from: _,
+                to,
+            }
+            | Self::TopArtists {
+                count: _,
+                year: _,
+                month: _,
+                metric: _,
+                from: _,
+                to,
+            }
+            | Self::TopAlbums {
+                count: _,
+                year: _,
+                metric: _,
+                from: _,
+                to,
+            }
+            | Self::TopSongs {
+                count: _,
+                year: _,
+                metric: _,
+                from: _,
+                to,
+            }
+            | Self::MostSkipped {
+                count: _,
+                year: _,
+                month: _,
+                artist_name: _,
+                from: _,
+                to,
+            }
+            | Self::Chart {
+                year: _,
+                artist_name: _,
+                track_name: _,
+                breakdown: _,
+                from: _,
+                to,
+            } => to.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn with_alias_parameter(&self, alias: &str) -> Self {
+        match self {
+            Self::AliasArtist {
+                alias: _,
+                artist_name,
+            } => Self::AliasArtist {
+                alias: Some(alias.to_string()),
+                artist_name: artist_name.to_owned(),
+            },
+            Self::UnaliasArtist { alias: _ } => Self::UnaliasArtist {
+                alias: Some(alias.to_string()),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_title_rules_parameter(&self, rules: TitleNormalizer) -> Self {
+        match self {
+            Self::NormalizeTitles { rules: _ } => Self::NormalizeTitles { rules: Some(rules) },
+            Self::PreviewTitles { rules: _, count } => Self::PreviewTitles {
+                rules: Some(rules),
+                count: count.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_separators_parameter(&self, separators: Vec<String>) -> Self {
+        match self {
+            Self::ArtistCredits {
+                separators: _,
+                exceptions,
+            } => Self::ArtistCredits {
+                separators: Some(separators),
+                exceptions: exceptions.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_credit_exceptions_parameter(&self, exceptions: Vec<String>) -> Self {
+        match self {
+            Self::ArtistCredits {
+                separators,
+                exceptions: _,
+            } => Self::ArtistCredits {
+                separators: separators.to_owned(),
+                exceptions: Some(exceptions),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_service_parameter(&self, service: &str) -> Self {
+        match self {
+            Self::Reclassify {
+                service: _,
+                min_listen_ms,
+                min_percent,
+                honour_skipped_flag,
+                honour_reason_end,
+            } => Self::Reclassify {
+                service: Some(service.to_string()),
+                min_listen_ms: min_listen_ms.to_owned(),
+                min_percent: min_percent.to_owned(),
+                honour_skipped_flag: honour_skipped_flag.to_owned(),
+                honour_reason_end: honour_reason_end.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_min_listen_ms_parameter(&self, min_listen_ms: u64) -> Self {
+        match self {
+            Self::Reclassify {
+                service,
+                min_listen_ms: _,
+                min_percent,
+                honour_skipped_flag,
+                honour_reason_end,
+            } => Self::Reclassify {
+                service: service.to_owned(),
+                min_listen_ms: Some(min_listen_ms),
+                min_percent: min_percent.to_owned(),
+                honour_skipped_flag: honour_skipped_flag.to_owned(),
+                honour_reason_end: honour_reason_end.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_min_percent_parameter(&self, min_percent: f64) -> Self {
+        match self {
+            Self::Reclassify {
+                service,
+                min_listen_ms,
+                min_percent: _,
+                honour_skipped_flag,
+                honour_reason_end,
+            } => Self::Reclassify {
+                service: service.to_owned(),
+                min_listen_ms: min_listen_ms.to_owned(),
+                min_percent: Some(min_percent),
+                honour_skipped_flag: honour_skipped_flag.to_owned(),
+                honour_reason_end: honour_reason_end.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_honour_skipped_flag_parameter(&self, honour_skipped_flag: bool) -> Self {
+        match self {
+            Self::Reclassify {
+                service,
+                min_listen_ms,
+                min_percent,
+                honour_skipped_flag: _,
+                honour_reason_end,
+            } => Self::Reclassify {
+                service: service.to_owned(),
+                min_listen_ms: min_listen_ms.to_owned(),
+                min_percent: min_percent.to_owned(),
+                honour_skipped_flag: Some(honour_skipped_flag),
+                honour_reason_end: honour_reason_end.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_honour_reason_end_parameter(&self, honour_reason_end: bool) -> Self {
+        match self {
+            Self::Reclassify {
+                service,
+                min_listen_ms,
+                min_percent,
+                honour_skipped_flag,
+                honour_reason_end: _,
+            } => Self::Reclassify {
+                service: service.to_owned(),
+                min_listen_ms: min_listen_ms.to_owned(),
+                min_percent: min_percent.to_owned(),
+                honour_skipped_flag: honour_skipped_flag.to_owned(),
+                honour_reason_end: Some(honour_reason_end),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_metric_parameter(&self, metric: Metric) -> Self {
+        match self {
+            Self::TopArtists {
+                count,
+                year,
+                month,
+                metric: _,
+                from,
+                to,
+            } => Self::TopArtists {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                month: month.to_owned(),
+                metric,
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::TopAlbums {
+                count,
+                year,
+                metric: _,
+                from,
+                to,
+            } => Self::TopAlbums {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                metric,
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::TopSongs {
+                count,
+                year,
+                metric: _,
+                from,
+                to,
+            } => Self::TopSongs {
+                count: count.to_owned(),
+                year: year.to_owned(),
+                metric,
+                from: from.to_owned(),
+                to: to.to_owned(),
+            },
+            Self::Compare {
+                first,
+                second,
+                count,
+                metric: _,
+            } => Self::Compare {
+                first: first.to_owned(),
+                second: second.to_owned(),
+                count: count.to_owned(),
+                metric,
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_first_period_parameter(&self, period: Period) -> Self {
+        match self {
+            Self::Compare {
+                first: _,
+                second,
+                count,
+                metric,
+            } => Self::Compare {
+                first: Some(period),
+                second: second.to_owned(),
+                count: count.to_owned(),
+                metric: metric.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_second_period_parameter(&self, period: Period) -> Self {
+        match self {
+            Self::Compare {
+                first,
+                second: _,
+                count,
+                metric,
+            } => Self::Compare {
+                first: first.to_owned(),
+                second: Some(period),
+                count: count.to_owned(),
+                metric: metric.to_owned(),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_query_parameter(&self, query: &str) -> Self {
+        match self {
+            Self::Query { query: _ } => Self::Query {
+                query: Some(query.to_string()),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_saved_query_name_parameter(&self, name: &str) -> Self {
+        match self {
+            Self::SaveQuery { name: _, command } => Self::SaveQuery {
+                name: Some(name.to_string()),
+                command: command.to_owned(),
+            },
+            Self::RunQuery { name: _ } => Self::RunQuery {
+                name: Some(name.to_string()),
+            },
+            Self::DeleteQuery { name: _ } => Self::DeleteQuery {
+                name: Some(name.to_string()),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_command_parameter(&self, command: &str) -> Self {
+        match self {
+            Self::SaveQuery { name, command: _ } => Self::SaveQuery {
+                name: name.to_owned(),
+                command: Some(command.to_string()),
+            },
+            _ => self.to_owned(),
+        }
+    }
+
+    pub fn with_months_parameter(&self, months: u32) -> Self {
+        match self {
+            Self::ForgottenArtists {
+                months: _,
+                min_listens,
+                count,
+            } => Self::ForgottenArtists {
+                months,
+                min_listens: min_listens.to_owned(),
+                count: count.to_owned(),
             },
             _ => self.to_owned(),
         }
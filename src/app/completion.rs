@@ -0,0 +1,50 @@
+/// The candidates offered for the current input, cycled through with Tab. The input is
+/// set to the selected candidate, so entering it as is picks that candidate.
+#[derive(Clone, Debug, Default)]
+pub struct Completion {
+    candidates: Vec<String>,
+    selected: usize,
+}
+
+impl Completion {
+    pub fn with_candidates(candidates: Vec<String>) -> Self {
+        Self {
+            candidates,
+            selected: 0,
+        }
+    }
+
+    pub fn candidates(&self) -> &Vec<String> {
+        &self.candidates
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected(&self) -> Option<&String> {
+        self.candidates.get(self.selected)
+    }
+
+    pub fn cycle(&mut self) -> Option<&String> {
+        if !self.candidates.is_empty() {
+            self.selected = (self.selected + 1) % self.candidates.len();
+        }
+        self.selected()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Completion;
+
+    #[test]
+    fn cycles_through_candidates() {
+        let mut completion =
+            Completion::with_candidates(vec!["Ulver".to_string(), "Ulcerate".to_string()]);
+
+        assert_eq!(Some(&"Ulver".to_string()), completion.selected());
+        assert_eq!(Some(&"Ulcerate".to_string()), completion.cycle());
+        assert_eq!(Some(&"Ulver".to_string()), completion.cycle());
+    }
+}
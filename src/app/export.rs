@@ -0,0 +1,227 @@
+use crate::persistence::Format;
+
+use super::{chart::render_svg, report::escape_html, MessageSet, Output, Table};
+
+/// Renders the output as a table file. Tables keep a column per value, and other
+/// messages are kept as they are, so nothing shown in the app is lost. Charts are drawn
+/// into the HTML, and left out of the CSV and Markdown as there's nothing to put in a cell.
+pub fn render_export(outputs: &[Output], format: &Format) -> Option<String> {
+    match format {
+        Format::Csv => Some(to_csv(outputs)),
+        Format::Markdown => Some(to_markdown(outputs)),
+        Format::Html => Some(to_html(outputs)),
+        Format::Json | Format::Yaml | Format::Svg | Format::Png => None,
+    }
+}
+
+/// Each output gets its own header, with the title in the first column of every row so
+/// the rows can still be told apart once they're filtered.
+fn to_csv(outputs: &[Output]) -> String {
+    let mut sections: Vec<String> = Vec::new();
+    for output in outputs {
+        let (title, header, rows) = match output {
+            Output::MessageSet(message_set) => (
+                message_set.title(),
+                vec!["message".to_string()],
+                message_set
+                    .messages()
+                    .iter()
+                    .map(|message| vec![message.clone()])
+                    .collect(),
+            ),
+            Output::Table(table) => (table.title(), table.columns().clone(), cell_values(table)),
+            Output::BarChart(_) => continue,
+        };
+        let mut lines = vec![csv_line(["title".to_string()].into_iter().chain(header))];
+        for row in rows {
+            lines.push(csv_line([title.to_string()].into_iter().chain(row)));
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    let mut csv = sections.join("\n\n");
+    csv.push('\n');
+    csv
+}
+
+fn to_markdown(outputs: &[Output]) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for output in outputs {
+        match output {
+            Output::MessageSet(message_set) => {
+                lines.push(format!("## {}", message_set.title()));
+                lines.push(String::new());
+                for message in message_set.messages() {
+                    lines.push(format!("- {message}"));
+                }
+            }
+            Output::Table(table) => {
+                lines.push(format!("## {}", table.title()));
+                lines.push(String::new());
+                lines.push(format!("| {} |", table.columns().join(" | ")));
+                lines.push(format!("|{}", " --- |".repeat(table.columns().len())));
+                for row in cell_values(table) {
+                    let values: Vec<String> =
+                        row.iter().map(|value| value.replace('|', "\\|")).collect();
+                    lines.push(format!("| {} |", values.join(" | ")));
+                }
+            }
+            Output::BarChart(_) => continue,
+        }
+        lines.push(String::new());
+    }
+
+    lines.join("\n")
+}
+
+fn to_html(outputs: &[Output]) -> String {
+    let sections: Vec<String> = outputs
+        .iter()
+        .map(|output| match output {
+            Output::MessageSet(message_set) => (message_set.title(), list_html(message_set)),
+            Output::Table(table) => (table.title(), table_html(table)),
+            Output::BarChart(chart) => (chart.title(), render_svg(chart)),
+        })
+        .map(|(title, body)| {
+            format!(
+                "  <section>\n    <h2>{}</h2>\n{body}\n  </section>",
+                escape_html(title)
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>krustens</title>
+  <style>
+    body {{ font-family: sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; background: #111; color: #eee; }}
+    section {{ border-top: 1px solid #333; padding: 0.5rem 0; }}
+    table {{ border-collapse: collapse; }}
+    th, td {{ text-align: left; padding: 0.2rem 0.8rem 0.2rem 0; }}
+  </style>
+</head>
+<body>
+{}
+</body>
+</html>
+"#,
+        sections.join("\n")
+    )
+}
+
+fn list_html(message_set: &MessageSet) -> String {
+    let items: Vec<String> = message_set
+        .messages()
+        .iter()
+        .map(|message| format!("      <li>{}</li>", escape_html(message)))
+        .collect();
+
+    format!("    <ul>\n{}\n    </ul>", items.join("\n"))
+}
+
+fn table_html(table: &Table) -> String {
+    let header: Vec<String> = table
+        .columns()
+        .iter()
+        .map(|column| format!("<th>{}</th>", escape_html(column)))
+        .collect();
+    let rows: Vec<String> = cell_values(table)
+        .iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|value| format!("<td>{}</td>", escape_html(value)))
+                .collect();
+            format!("      <tr>{}</tr>", cells.join(""))
+        })
+        .collect();
+
+    format!(
+        "    <table>\n      <tr>{}</tr>\n{}\n    </table>",
+        header.join(""),
+        rows.join("\n")
+    )
+}
+
+/// The rows in the order they're shown, sorted by whichever column was picked.
+fn cell_values(table: &Table) -> Vec<Vec<String>> {
+    table
+        .rows()
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.value()).collect())
+        .collect()
+}
+
+fn csv_line(fields: impl Iterator<Item = String>) -> String {
+    fields
+        .map(|field| escape_csv(&field))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::{table::Cell, MessageSet, Output, Table},
+        persistence::Format,
+    };
+
+    use super::render_export;
+
+    fn outputs() -> Vec<Output> {
+        vec![
+            Output::Table(Table::with_rows(
+                "Top songs",
+                &["Rank", "Artist", "Song", "Plays", "Minutes"],
+                vec![vec![
+                    Cell::Count(1),
+                    Cell::Text("Gorguts, Ulcerate".to_string()),
+                    Cell::Text("Obscura".to_string()),
+                    Cell::Count(12),
+                    Cell::minutes(2_914_500),
+                ]],
+            )),
+            Output::MessageSet(MessageSet::with_messages(
+                "Notes",
+                vec!["Listened for 2.0 hours".to_string()],
+            )),
+        ]
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render_export(&outputs(), &Format::Csv).unwrap();
+
+        assert_eq!(
+            "title,Rank,Artist,Song,Plays,Minutes\n\
+             Top songs,1,\"Gorguts, Ulcerate\",Obscura,12,48.575\n\
+             \n\
+             title,message\n\
+             Notes,Listened for 2.0 hours\n",
+            csv
+        );
+    }
+
+    #[test]
+    fn renders_markdown_tables() {
+        let markdown = render_export(&outputs(), &Format::Markdown).unwrap();
+
+        assert!(markdown.contains(
+            "| Rank | Artist | Song | Plays | Minutes |\n| --- | --- | --- | --- | --- |\n\
+             | 1 | Gorguts, Ulcerate | Obscura | 12 | 48.575 |\n"
+        ));
+        assert!(markdown.contains("## Notes\n\n- Listened for 2.0 hours\n"));
+        assert!(render_export(&outputs(), &Format::Json).is_none());
+    }
+}
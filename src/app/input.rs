@@ -1,22 +1,28 @@
 use serde::{Deserialize, Serialize};
 
-use super::CommandName;
+use super::{command_history::CommandHistory, completion::Completion, CommandName};
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Input {
     pub text: String,
-    pub command_history: Vec<CommandName>,
+    /// Read from `command_history` in states saved before the history kept parameters.
+    #[serde(default, alias = "command_history")]
+    pub history: CommandHistory,
     pub history_index: Option<usize>,
+    #[serde(skip)]
+    pub completion: Option<Completion>,
 }
 
 impl Input {
     pub fn push(&mut self, c: char) {
         self.history_index = None;
+        self.completion = None;
         self.text.push(c);
     }
 
     pub fn pop(&mut self) {
         self.history_index = None;
+        self.completion = None;
         self.text.pop();
     }
 
@@ -33,37 +39,48 @@ impl Input {
         self.text = text.to_string();
     }
 
+    /// Moves on to the next candidate, if the input is still the one last completed.
+    pub fn cycle_completion(&mut self) -> bool {
+        let Some(completion) = self.completion.as_mut() else {
+            return false;
+        };
+        if completion.selected() != Some(&self.text) {
+            return false;
+        }
+
+        if let Some(candidate) = completion.cycle() {
+            self.text = candidate.clone();
+        }
+        true
+    }
+
     pub fn drain(&mut self) -> String {
         self.history_index = None;
+        self.completion = None;
         self.text.drain(..).collect()
     }
 
     pub fn clear(&mut self) {
         self.history_index = None;
+        self.completion = None;
         self.text.clear();
     }
 
-    pub fn push_to_history(&mut self, command_name: CommandName) {
-        if !self.command_history.last().eq(&Some(&command_name)) {
-            self.command_history.push(command_name);
-        }
-    }
-
     pub fn set_from_previous_history(&mut self) {
-        if self.command_history.is_empty() {
+        if self.history.is_empty() {
             return;
         }
 
         let new_index = match self.history_index {
-            Some(index) => Some((index - 1).max(0)),
-            None => Some(self.command_history.len() - 1),
+            Some(index) => Some(index.saturating_sub(1)),
+            None => Some(self.history.len() - 1),
         };
 
         match new_index {
             Some(index) => {
                 self.history_index = Some(index);
-                if let Some(command_name) = self.command_history.get(index) {
-                    self.text = command_name.to_string();
+                if let Some(entry) = self.history.get(index) {
+                    self.text = entry.command().to_string();
                 }
             }
             None => {
@@ -74,13 +91,13 @@ impl Input {
     }
 
     pub fn set_from_next_history(&mut self) {
-        if self.command_history.is_empty() {
+        if self.history.is_empty() {
             return;
         }
 
         let new_index = match self.history_index {
             Some(index) => {
-                if index == self.command_history.len() - 1 {
+                if index == self.history.len() - 1 {
                     None
                 } else {
                     Some(index + 1)
@@ -92,8 +109,8 @@ impl Input {
         match new_index {
             Some(index) => {
                 self.history_index = Some(index);
-                if let Some(command_name) = self.command_history.get(index) {
-                    self.text = command_name.to_string();
+                if let Some(entry) = self.history.get(index) {
+                    self.text = entry.command().to_string();
                 }
             }
             None => {
@@ -20,6 +20,10 @@ impl MessageSet {
         &self.title
     }
 
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
     pub fn messages(&self) -> &Vec<String> {
         &self.messages
     }
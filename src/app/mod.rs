@@ -1,21 +1,43 @@
 mod application;
+mod artist_detail;
 mod chart;
+mod command_history;
+mod command_line;
 mod command_name;
 mod command_parameters;
+mod completion;
+mod export;
 mod has_id;
 mod input;
 mod message_set;
 mod mode;
 mod output;
+mod output_search;
+mod report;
+mod saved_queries;
+mod scroll;
+mod settings;
+mod song_detail;
 mod state;
+mod table;
+mod wrapped;
 
 pub use application::Application;
 pub use chart::BarChart;
+pub use command_history::{HistoryEntry, HistorySearch};
+pub use command_line::CommandLine;
 pub use command_name::CommandName;
 pub use command_parameters::{CommandParameterSpec, CommandParameters};
+pub use completion::Completion;
 pub use has_id::HasId;
 pub use input::Input;
 pub use message_set::MessageSet;
 pub use mode::Mode;
-pub use output::Output;
+pub use output::{Output, OutputDetails};
+pub use output_search::OutputSearch;
+pub use report::{Report, ReportFormat};
+pub use saved_queries::SavedQueries;
+pub use scroll::{Scroll, ScrollOffsets};
+pub use settings::Settings;
 pub use state::State;
+pub use table::Table;
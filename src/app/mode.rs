@@ -1,15 +1,15 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 pub enum Mode {
     CommandParameters,
     EnterCommand,
+    History,
+    HistorySearch,
+    #[default]
     Normal,
+    Outputs,
     Processing,
-}
-
-impl Default for Mode {
-    fn default() -> Self {
-        Self::Normal
-    }
+    RenameOutput,
+    Search,
 }
@@ -1,11 +1,49 @@
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 
-use super::{BarChart, HasId, MessageSet};
+use super::{BarChart, HasId, MessageSet, Table};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum Output {
     MessageSet(MessageSet),
     BarChart(BarChart),
+    Table(Table),
+}
+
+impl Output {
+    pub fn title(&self) -> &str {
+        match self {
+            Output::MessageSet(it) => it.title(),
+            Output::BarChart(it) => it.title(),
+            Output::Table(it) => it.title(),
+        }
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        match self {
+            Output::MessageSet(it) => it.set_title(title),
+            Output::BarChart(it) => it.set_title(title),
+            Output::Table(it) => it.set_title(title),
+        }
+    }
+
+    /// The lines as they're shown, to search through. Charts have none.
+    pub fn text_lines(&self) -> Vec<String> {
+        match self {
+            Output::MessageSet(it) => it.messages().clone(),
+            Output::BarChart(_) => Vec::new(),
+            Output::Table(it) => it.messages(),
+        }
+    }
+
+    /// The number of lines it takes up when scrolling, charts are never scrolled.
+    pub fn lines(&self) -> usize {
+        match self {
+            Output::MessageSet(it) => it.messages().len(),
+            Output::BarChart(_) => 0,
+            Output::Table(it) => it.len(),
+        }
+    }
 }
 
 impl HasId for Output {
@@ -13,6 +51,16 @@ impl HasId for Output {
         match self {
             Output::MessageSet(it) => it.id(),
             Output::BarChart(it) => it.id(),
+            Output::Table(it) => it.id(),
         }
     }
 }
+
+/// When an output was added and whether it's pinned, kept by the output's id. Pinned
+/// outputs are kept when the output is cleared.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OutputDetails {
+    pub created_at: DateTime<Local>,
+    #[serde(default)]
+    pub pinned: bool,
+}
@@ -0,0 +1,99 @@
+use crate::utils::fold;
+
+/// A search through the lines of the current output. Matching ignores case and accents,
+/// like completion does, and the current match is the line last jumped to.
+#[derive(Clone, Debug, Default)]
+pub struct OutputSearch {
+    query: String,
+    folded_query: String,
+    current_line: Option<usize>,
+}
+
+impl OutputSearch {
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        self.query = query.to_string();
+        self.folded_query = fold(query.trim());
+        self.current_line = None;
+    }
+
+    pub fn current_line(&self) -> Option<usize> {
+        self.current_line
+    }
+
+    pub fn is_match(&self, line: &str) -> bool {
+        !self.folded_query.is_empty() && fold(line).contains(&self.folded_query)
+    }
+
+    /// The index of every matching line.
+    pub fn matches(&self, lines: &[String]) -> Vec<usize> {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| self.is_match(line))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Moves to the first match after the current one, wrapping around to the top.
+    pub fn next(&mut self, lines: &[String]) -> Option<usize> {
+        let matches = self.matches(lines);
+        self.current_line = match self.current_line {
+            Some(current) => matches
+                .iter()
+                .find(|line| **line > current)
+                .or(matches.first())
+                .copied(),
+            None => matches.first().copied(),
+        };
+        self.current_line
+    }
+
+    /// Moves to the last match before the current one, wrapping around to the bottom.
+    pub fn previous(&mut self, lines: &[String]) -> Option<usize> {
+        let matches = self.matches(lines);
+        self.current_line = match self.current_line {
+            Some(current) => matches
+                .iter()
+                .rev()
+                .find(|line| **line < current)
+                .or(matches.last())
+                .copied(),
+            None => matches.last().copied(),
+        };
+        self.current_line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OutputSearch;
+
+    #[test]
+    fn jumps_between_matches() {
+        let lines: Vec<String> = [
+            "Mgła - 12",
+            "Ulcerate - 9",
+            "MGLA - Exercises - 3",
+            "Gorguts",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        let mut search = OutputSearch::default();
+        search.set_query("mgla");
+
+        assert_eq!(vec![0, 2], search.matches(&lines));
+        assert_eq!(Some(0), search.next(&lines));
+        assert_eq!(Some(2), search.next(&lines));
+        assert_eq!(Some(0), search.next(&lines));
+        assert_eq!(Some(2), search.previous(&lines));
+
+        search.set_query("");
+        assert!(search.matches(&lines).is_empty());
+        assert_eq!(None, search.next(&lines));
+    }
+}
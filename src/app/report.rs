@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use super::MessageSet;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn extension_display(&self) -> &str {
+        match *self {
+            ReportFormat::Html => "html",
+            ReportFormat::Markdown => "md",
+        }
+    }
+}
+
+impl TryFrom<&str> for ReportFormat {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "html" => Ok(ReportFormat::Html),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct Report {
+    pub title: String,
+    pub pages: Vec<MessageSet>,
+}
+
+impl Report {
+    pub fn render(&self, format: &ReportFormat) -> String {
+        match format {
+            ReportFormat::Html => self.to_html(),
+            ReportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut lines: Vec<String> = vec![format!("# {}", self.title)];
+
+        for page in self.pages.iter() {
+            lines.push(String::new());
+            lines.push(format!("## {}", page.title()));
+            lines.push(String::new());
+            for message in page.messages() {
+                match message.strip_suffix(':') {
+                    Some(label) => {
+                        if lines.last().is_some_and(|line| !line.is_empty()) {
+                            lines.push(String::new());
+                        }
+                        lines.push(format!("### {}", escape_markdown(label)));
+                        lines.push(String::new());
+                    }
+                    None => lines.push(format!("- {}", escape_markdown(message))),
+                }
+            }
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    pub fn to_html(&self) -> String {
+        let sections: Vec<String> = self
+            .pages
+            .iter()
+            .map(|page| {
+                let items: Vec<String> = page
+                    .messages()
+                    .iter()
+                    .map(|message| format!("      <li>{}</li>", escape_html(message)))
+                    .collect();
+                format!(
+                    "  <section>\n    <h2>{}</h2>\n    <ul>\n{}\n    </ul>\n  </section>",
+                    escape_html(page.title()),
+                    items.join("\n")
+                )
+            })
+            .collect();
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>{title}</title>
+  <style>
+    body {{ font-family: sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; background: #111; color: #eee; }}
+    h1 {{ color: #1db954; }}
+    section {{ border-top: 1px solid #333; padding: 0.5rem 0; }}
+    li {{ margin: 0.2rem 0; }}
+  </style>
+</head>
+<body>
+  <h1>{title}</h1>
+{sections}
+</body>
+</html>
+"#,
+            title = escape_html(&self.title),
+            sections = sections.join("\n")
+        )
+    }
+}
+
+/// Escapes the characters that would otherwise format artist and song names, like the
+/// `*` in "A*Teens".
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '|' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::MessageSet;
+
+    use super::{Report, ReportFormat};
+
+    fn report() -> Report {
+        Report {
+            title: "Wrapped 2022".to_string(),
+            pages: vec![MessageSet::with_messages(
+                "Top artists",
+                vec![
+                    "By plays:".to_string(),
+                    "Gorguts & Ulcerate - 12 plays".to_string(),
+                    "A*Teens - [Live] - 3 plays".to_string(),
+                ],
+            )],
+        }
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = report().render(&ReportFormat::Markdown);
+
+        assert!(markdown.starts_with("# Wrapped 2022\n"));
+        assert!(markdown
+            .contains("## Top artists\n\n### By plays\n\n- Gorguts & Ulcerate - 12 plays\n"));
+        assert!(markdown.contains("- A\\*Teens - \\[Live\\] - 3 plays\n"));
+    }
+
+    #[test]
+    fn renders_escaped_html() {
+        let html = report().render(&ReportFormat::Html);
+
+        assert!(html.contains("<h2>Top artists</h2>"));
+        assert!(html.contains("<li>Gorguts &amp; Ulcerate - 12 plays</li>"));
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Commands saved under a name to run again with `run`. The command is kept as it was
+/// typed, so relative values like `last month` are worked out each time it runs.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SavedQueries {
+    queries: BTreeMap<String, String>,
+}
+
+impl SavedQueries {
+    /// Saves the command, replacing one already saved under the name.
+    pub fn save(&mut self, name: &str, command: &str) -> Option<String> {
+        self.queries
+            .insert(name.trim().to_string(), command.trim().to_string())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.queries
+            .get(name.trim())
+            .map(|command| command.as_str())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.queries.remove(name.trim())
+    }
+
+    /// Each saved query as its name and command, by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.queries
+            .iter()
+            .map(|(name, command)| (name.as_str(), command.as_str()))
+    }
+
+    /// A line for each saved query, by name.
+    pub fn descriptions(&self) -> Vec<String> {
+        if self.queries.is_empty() {
+            return vec!["No saved queries".to_string()];
+        }
+
+        self.queries
+            .iter()
+            .map(|(name, command)| format!("{name} - {command}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SavedQueries;
+
+    #[test]
+    fn saves_and_replaces_queries() {
+        let mut queries = SavedQueries::default();
+        assert_eq!(vec!["No saved queries"], queries.descriptions());
+
+        assert_eq!(None, queries.save("monthly-report", "top artists count=20"));
+        assert_eq!(
+            Some("top artists count=20".to_string()),
+            queries.save(
+                " monthly-report ",
+                "top artists count=25 month=\"this month\""
+            )
+        );
+        queries.save("chart", "chart year=\"this year\"");
+
+        assert_eq!(
+            Some("top artists count=25 month=\"this month\""),
+            queries.get("monthly-report")
+        );
+        assert_eq!(
+            vec![
+                "chart - chart year=\"this year\"",
+                "monthly-report - top artists count=25 month=\"this month\""
+            ],
+            queries.descriptions()
+        );
+
+        assert!(queries.remove("chart").is_some());
+        assert_eq!(None, queries.get("chart"));
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+/// A move through the current output, by a number of lines, a page, to either end or to
+/// a line.
+pub enum Scroll {
+    Up(usize),
+    Down(usize),
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    To(usize),
+}
+
+/// How far each output has been scrolled, by its id, so flipping between pages keeps the
+/// place in each. The page height is however many lines fit the last time it was drawn.
+#[derive(Debug, Default)]
+pub struct ScrollOffsets {
+    offsets: HashMap<String, usize>,
+    page_height: usize,
+}
+
+impl ScrollOffsets {
+    pub fn offset(&self, id: &str) -> usize {
+        self.offsets.get(id).copied().unwrap_or_default()
+    }
+
+    pub fn set_page_height(&mut self, page_height: usize) {
+        self.page_height = page_height;
+    }
+
+    /// Moves the output, keeping the last line at the bottom of the page at the furthest.
+    pub fn scroll(&mut self, id: &str, scroll: Scroll, lines: usize) {
+        let offset = self.offset(id);
+        let max_offset = lines.saturating_sub(self.page_height);
+        let page = self.page_height.max(1);
+        let offset = match scroll {
+            Scroll::Up(count) => offset.saturating_sub(count),
+            Scroll::Down(count) => offset + count,
+            Scroll::PageUp => offset.saturating_sub(page),
+            Scroll::PageDown => offset + page,
+            Scroll::Top => 0,
+            Scroll::Bottom => max_offset,
+            Scroll::To(line) => line,
+        };
+
+        self.offsets.insert(id.to_string(), offset.min(max_offset));
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.offsets.remove(id);
+    }
+
+    /// Keeps the offsets of only the outputs for which `keep` is true.
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.offsets.retain(|id, _| keep(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scroll, ScrollOffsets};
+
+    #[test]
+    fn scrolls_within_the_output() {
+        let mut offsets = ScrollOffsets::default();
+        offsets.set_page_height(10);
+
+        offsets.scroll("a", Scroll::Down(3), 25);
+        assert_eq!(3, offsets.offset("a"));
+        assert_eq!(0, offsets.offset("b"));
+
+        offsets.scroll("a", Scroll::PageDown, 25);
+        assert_eq!(13, offsets.offset("a"));
+
+        offsets.scroll("a", Scroll::PageDown, 25);
+        assert_eq!(15, offsets.offset("a"));
+
+        offsets.scroll("a", Scroll::Up(20), 25);
+        assert_eq!(0, offsets.offset("a"));
+
+        offsets.scroll("a", Scroll::Bottom, 25);
+        assert_eq!(15, offsets.offset("a"));
+
+        offsets.scroll("a", Scroll::To(7), 25);
+        assert_eq!(7, offsets.offset("a"));
+
+        offsets.scroll("b", Scroll::PageDown, 4);
+        assert_eq!(0, offsets.offset("b"));
+    }
+}
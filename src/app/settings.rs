@@ -0,0 +1,14 @@
+use crate::processing::{ArtistAliases, ArtistCredits, ClassificationPolicies, TitleNormalizer};
+
+use super::SavedQueries;
+
+/// The settings curated by hand. They're stored apart from the app state, so output or
+/// history that can't be read back never takes them with it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Settings {
+    pub classification: ClassificationPolicies,
+    pub aliases: ArtistAliases,
+    pub titles: TitleNormalizer,
+    pub credits: ArtistCredits,
+    pub saved_queries: SavedQueries,
+}
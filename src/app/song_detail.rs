@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+
+use chrono::Datelike;
+
+use crate::{
+    projections::statistics::{EventProcessor, Listen},
+    track_plays::{ArtistName, TrackName},
+    utils::months_between,
+};
+
+use super::{chart::BarDataPoint, BarChart, MessageSet, Output};
+
+/// Every listen of a song, picked out of the listens of the track read from the listens
+/// table. The artist goes through the aliases and artist credits and the track through
+/// the title rules, so the song is found however it was entered. The header names the
+/// song's primary artist, not the whole credit it was listened under.
+pub fn song_detail(
+    processor: &EventProcessor,
+    listens: &[Listen],
+    artist_name: &ArtistName,
+    track_name: &TrackName,
+) -> Option<Vec<Output>> {
+    let artist_name = processor.aliases.resolve(artist_name);
+    let track_name = processor.titles.track_name(track_name);
+    let mut listens: Vec<&Listen> = listens
+        .iter()
+        .filter(|listen| listen.track_name.0.to_lowercase() == track_name.0.to_lowercase())
+        .filter(|listen| {
+            let is_artist = |name: &ArtistName| {
+                processor.aliases.resolve(name).0.to_lowercase() == artist_name.0.to_lowercase()
+            };
+            let credits = processor.credits.split(&listen.artist_name);
+            let credited = credits
+                .primary
+                .iter()
+                .chain(credits.featured.iter())
+                .any(is_artist);
+            credited || is_artist(&listen.artist_name)
+        })
+        .collect();
+    listens.sort_by_key(|listen| listen.listened_at);
+    let first = listens.first()?;
+    let primary_artist = processor
+        .credits
+        .split(&first.artist_name)
+        .primary
+        .first()
+        .map(|name| processor.aliases.resolve(name))
+        .unwrap_or_else(|| first.artist_name.clone());
+    let title = format!("{} - {}", primary_artist, first.track_name);
+
+    let plays = listens.iter().filter(|listen| !listen.skipped).count();
+    let skips = listens.len() - plays;
+    let ms_played: u64 = listens
+        .iter()
+        .filter(|listen| !listen.skipped)
+        .map(|listen| listen.ms_played)
+        .sum();
+
+    let mut messages = vec![
+        format!("Plays: {plays}"),
+        format!("Skips: {skips}"),
+        format!("Listened for {:.1} minutes", ms_played as f64 / 60_000.0),
+    ];
+    messages.extend(listens.iter().map(|listen| {
+        format!(
+            "{} - {} played on {}{}",
+            listen.listened_at.format("%Y-%m-%d %H:%M"),
+            duration_display(listen.ms_played),
+            listen.service,
+            if listen.skipped { " (skipped)" } else { "" }
+        )
+    }));
+
+    let mut month_plays: BTreeMap<(i32, u32), u64> = BTreeMap::new();
+    for listen in listens.iter().filter(|listen| !listen.skipped) {
+        *month_plays
+            .entry((listen.listened_at.year(), listen.listened_at.month()))
+            .or_default() += 1;
+    }
+    let data_points: Vec<BarDataPoint> = match (
+        month_plays.keys().next().copied(),
+        month_plays.keys().last().copied(),
+    ) {
+        (Some(first), Some(last)) => months_between(first, last)
+            .into_iter()
+            .map(|(year, month)| {
+                BarDataPoint::new(
+                    format!("{year}-{month:02}"),
+                    month_plays.get(&(year, month)).copied().unwrap_or_default(),
+                )
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Some(vec![
+        Output::MessageSet(MessageSet::with_messages(&title, messages)),
+        Output::BarChart(BarChart::with_data_points(
+            &format!("{title}: plays per month"),
+            data_points,
+        )),
+    ])
+}
+
+fn duration_display(ms: u64) -> String {
+    let seconds = ms / 1000;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::Output,
+        processing::{
+            events::{Event, EventData, TrackPlayAdded},
+            ArtistAliases, ArtistCredits, ClassificationPolicies, TitleNormalizer,
+        },
+        projections::statistics::{EventProcessor, ListenRules},
+        track_plays::{ArtistName, TrackName},
+    };
+
+    use super::{duration_display, song_detail};
+
+    fn play(version: u32, artist_name: &str, track_name: &str, ms_played: u64) -> Event {
+        Event {
+            version,
+            data: EventData::TrackPlayAdded(TrackPlayAdded {
+                artist_name: ArtistName(artist_name.to_string()),
+                track_name: TrackName(track_name.to_string()),
+                album_name: None,
+                ms_played,
+                end_time: format!("2022-1{version}-01 21:00:00"),
+                service_hint: "spotify".to_string(),
+                track_ms: None,
+                skipped: None,
+                reason_end: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn finds_every_listen_of_the_song() {
+        let mut aliases = ArtistAliases::default();
+        aliases.add("Ulcerate NZ", "Ulcerate");
+        let credits = ArtistCredits {
+            separators: ArtistCredits::parse_separators("&"),
+            exceptions: Vec::new(),
+        };
+        let rules = ListenRules {
+            classification: ClassificationPolicies::default(),
+            aliases,
+            titles: TitleNormalizer::try_from("all").unwrap(),
+        };
+        let mut processor = EventProcessor::new(
+            rules.classification.clone(),
+            rules.aliases.clone(),
+            rules.titles.clone(),
+            credits,
+        );
+        let mut rows = Vec::new();
+        for event in [
+            play(0, "Ulcerate & Mgła", "Visceral Ends", 240_000),
+            play(2, "Ulcerate NZ", "Visceral Ends - 2020 Remaster", 180_000),
+            play(2, "Ulcerate", "Visceral Ends", 5_000),
+            play(2, "Mgła feat. Ulcerate", "Visceral Ends", 120_000),
+            play(2, "Mgła", "Visceral Ends", 60_000),
+        ] {
+            processor.process_event(&event);
+            rows.extend(rules.listen(&event));
+        }
+
+        let outputs = song_detail(
+            &processor,
+            &rows,
+            &ArtistName("ulcerate nz".to_string()),
+            &TrackName("Visceral Ends (Live)".to_string()),
+        )
+        .unwrap();
+
+        let Output::MessageSet(listens) = &outputs[0] else {
+            panic!("expected the listens");
+        };
+        assert_eq!("Ulcerate - Visceral Ends", listens.title());
+        assert_eq!(
+            vec!["Plays: 3", "Skips: 1", "Listened for 9.0 minutes"],
+            listens.messages()[..3]
+        );
+        assert_eq!(7, listens.messages().len());
+        assert!(listens.messages()[3..]
+            .iter()
+            .any(|message| message.ends_with("(skipped)")));
+
+        assert!(song_detail(
+            &processor,
+            &rows,
+            &ArtistName("Ulcerate".to_string()),
+            &TrackName("Yield to Naught".to_string()),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!("3:42", duration_display(222_500));
+        assert_eq!("0:05", duration_display(5_000));
+    }
+}
@@ -1,12 +1,26 @@
-use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use chrono::{Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 
-use crate::{errors::InteractiveError, persistence::Format};
+use crate::{
+    errors::InteractiveError,
+    persistence::Format,
+    processing::{ArtistAliases, ArtistCredits, ClassificationPolicies, TitleNormalizer},
+    projections::statistics::{ListenRules, Metric, Period},
+    query,
+    track_plays::{ArtistName, SERVICE_HINTS},
+    utils::{
+        parse_date_input, parse_relative_month, parse_relative_period, parse_relative_year,
+        parse_yes_no,
+    },
+};
 
 use super::{
-    chart::BarBreakdown, CommandName, CommandParameterSpec, CommandParameters, Input, MessageSet,
-    Mode, Output,
+    chart::BarBreakdown, CommandLine, CommandName, CommandParameterSpec, CommandParameters, HasId,
+    HistoryEntry, HistorySearch, Input, MessageSet, Mode, Output, OutputDetails, OutputSearch,
+    ReportFormat, SavedQueries, ScrollOffsets, Settings,
 };
 
 #[derive(Default, Deserialize, Serialize)]
@@ -18,15 +32,101 @@ pub struct State {
     pub command_parameters: Option<CommandParameters>,
     pub current_page: usize,
     output: Vec<Output>,
+    #[serde(default)]
+    output_details: HashMap<String, OutputDetails>,
+    #[serde(skip)]
+    pub selected_output: usize,
+    #[serde(skip)]
+    pub search: Option<OutputSearch>,
+    /// The command being entered, with the parameters given so far.
+    #[serde(skip)]
+    pub command_line: Option<CommandLine>,
+    #[serde(skip)]
+    pub selected_history: usize,
+    #[serde(skip)]
+    pub history_search: Option<HistorySearch>,
     is_dirty: bool,
+    /// The settings are kept in their own store. They're only read from the state to
+    /// move them over from states saved before that.
+    #[serde(default, skip_serializing)]
+    classification: ClassificationPolicies,
+    #[serde(default, skip_serializing)]
+    aliases: ArtistAliases,
+    #[serde(default, skip_serializing)]
+    titles: TitleNormalizer,
+    #[serde(default, skip_serializing)]
+    credits: ArtistCredits,
+    #[serde(default, skip_serializing)]
+    saved_queries: SavedQueries,
+    #[serde(skip)]
+    settings_dirty: bool,
+    #[serde(skip)]
+    scroll: ScrollOffsets,
 }
 
 impl State {
+    /// Removes all of the output except what's pinned.
     pub fn clear_output(&mut self) {
         self.is_dirty = true;
-        self.input.command_history.clear();
         self.current_page = 0;
-        self.output.clear();
+        self.selected_output = 0;
+        let details = &self.output_details;
+        self.output.retain(|output| {
+            details
+                .get(output.id())
+                .is_some_and(|details| details.pinned)
+        });
+        self.output_details.retain(|_, details| details.pinned);
+        let details = &self.output_details;
+        self.scroll.retain(|id| details.contains_key(id));
+    }
+
+    pub fn push_to_history(&mut self, entry: HistoryEntry) {
+        self.is_dirty = true;
+        self.input.history.push(entry);
+    }
+
+    pub fn output_details(&self, id: &str) -> Option<&OutputDetails> {
+        self.output_details.get(id)
+    }
+
+    pub fn remove_output(&mut self, index: usize) {
+        if index >= self.output.len() {
+            return;
+        }
+
+        self.is_dirty = true;
+        let output = self.output.remove(index);
+        self.output_details.remove(output.id());
+        self.scroll.remove(output.id());
+        let last = self.output.len().saturating_sub(1);
+        if self.current_page > index || self.current_page > last {
+            self.current_page = self.current_page.saturating_sub(1);
+        }
+        self.selected_output = self.selected_output.min(last);
+    }
+
+    pub fn toggle_pinned(&mut self, index: usize) {
+        let Some(output) = self.output.get(index) else {
+            return;
+        };
+
+        self.is_dirty = true;
+        let details = self
+            .output_details
+            .entry(output.id().to_string())
+            .or_insert_with(|| OutputDetails {
+                created_at: Local::now(),
+                pinned: false,
+            });
+        details.pinned = !details.pinned;
+    }
+
+    pub fn rename_output(&mut self, index: usize, title: &str) {
+        if let Some(output) = self.output.get_mut(index) {
+            self.is_dirty = true;
+            output.set_title(title);
+        }
     }
 
     pub fn reset_dirty(&mut self) {
@@ -39,6 +139,13 @@ impl State {
 
     pub fn insert_output(&mut self, index: usize, output: Output) {
         self.is_dirty = true;
+        self.output_details.insert(
+            output.id().to_string(),
+            OutputDetails {
+                created_at: Local::now(),
+                pinned: false,
+            },
+        );
         self.output.insert(index, output);
     }
 
@@ -51,6 +158,101 @@ impl State {
         self.output.iter_mut().collect()
     }
 
+    pub fn scroll(&self) -> &ScrollOffsets {
+        &self.scroll
+    }
+
+    pub fn scroll_mut(&mut self) -> &mut ScrollOffsets {
+        &mut self.scroll
+    }
+
+    pub fn classification(&self) -> &ClassificationPolicies {
+        &self.classification
+    }
+
+    pub fn classification_mut(&mut self) -> &mut ClassificationPolicies {
+        self.settings_dirty = true;
+        &mut self.classification
+    }
+
+    pub fn aliases(&self) -> &ArtistAliases {
+        &self.aliases
+    }
+
+    pub fn aliases_mut(&mut self) -> &mut ArtistAliases {
+        self.settings_dirty = true;
+        &mut self.aliases
+    }
+
+    pub fn titles(&self) -> &TitleNormalizer {
+        &self.titles
+    }
+
+    pub fn set_titles(&mut self, titles: TitleNormalizer) {
+        self.settings_dirty = true;
+        self.titles = titles;
+    }
+
+    /// The rules listens are read with, so the listens table can be rebuilt when they change.
+    pub fn listen_rules(&self) -> ListenRules {
+        ListenRules {
+            classification: self.classification.clone(),
+            aliases: self.aliases.clone(),
+            titles: self.titles.clone(),
+        }
+    }
+
+    pub fn saved_queries(&self) -> &SavedQueries {
+        &self.saved_queries
+    }
+
+    pub fn saved_queries_mut(&mut self) -> &mut SavedQueries {
+        self.settings_dirty = true;
+        &mut self.saved_queries
+    }
+
+    pub fn credits(&self) -> &ArtistCredits {
+        &self.credits
+    }
+
+    pub fn credits_mut(&mut self) -> &mut ArtistCredits {
+        self.settings_dirty = true;
+        &mut self.credits
+    }
+
+    pub fn settings(&self) -> Settings {
+        Settings {
+            classification: self.classification.clone(),
+            aliases: self.aliases.clone(),
+            titles: self.titles.clone(),
+            credits: self.credits.clone(),
+            saved_queries: self.saved_queries.clone(),
+        }
+    }
+
+    /// Replaces the settings with ones read from the settings store.
+    pub fn set_settings(&mut self, settings: Settings) {
+        self.classification = settings.classification;
+        self.aliases = settings.aliases;
+        self.titles = settings.titles;
+        self.credits = settings.credits;
+        self.saved_queries = settings.saved_queries;
+        self.settings_dirty = false;
+    }
+
+    /// Marks the settings to be stored, as when they were only read from an older state.
+    pub fn store_settings(&mut self) {
+        self.settings_dirty = true;
+    }
+
+    pub fn reset_settings_dirty(&mut self) {
+        self.settings_dirty = false;
+    }
+
+    pub fn settings_dirty(&self) -> bool {
+        self.settings_dirty
+    }
+
     pub fn command_message_set(&self) -> MessageSet {
         let mut messages: Vec<String> = CommandName::iter()
             .map(|command| format!("{} - {}", command, command.description()))
@@ -67,17 +269,34 @@ impl State {
         self.is_dirty = true;
         match spec {
             CommandParameterSpec::Year { description: _ } => {
+                let today = Local::now().naive_local().date();
                 if let Ok(year) = text.parse::<i32>() {
                     self.add_year_parameter(year);
                     Ok(())
+                } else if let Some(year) = parse_relative_year(text, &today) {
+                    self.add_year_parameter(year);
+                    Ok(())
+                } else if parse_relative_period(text, &today).is_some() {
+                    Err(InteractiveError::ParsingIssue {
+                        message: format!("{text} is not a year, give it as from instead"),
+                    })
                 } else {
                     Ok(())
                 }
             }
             CommandParameterSpec::Month { description: _ } => {
+                let today = Local::now().naive_local().date();
                 if let Some(month) = text.parse::<u32>().ok().filter(|m| (&1..=&12).contains(&m)) {
                     self.add_month_parameter(month);
                     Ok(())
+                } else if let Some((year, month)) = parse_relative_month(text, &today) {
+                    self.add_year_parameter(year);
+                    self.add_month_parameter(month);
+                    Ok(())
+                } else if parse_relative_period(text, &today).is_some() {
+                    Err(InteractiveError::ParsingIssue {
+                        message: format!("{text} is not a month, give it as from instead"),
+                    })
                 } else {
                     Ok(())
                 }
@@ -89,6 +308,13 @@ impl State {
 
                 Ok(())
             }
+            CommandParameterSpec::MinPlays { description: _ } => {
+                if let Ok(min) = text.parse::<u64>() {
+                    self.add_min_plays_parameter(min);
+                }
+
+                Ok(())
+            }
             CommandParameterSpec::Count { description: _ } => {
                 if let Ok(count) = text.parse::<usize>() {
                     self.add_artist_count_parameter(count);
@@ -114,6 +340,20 @@ impl State {
 
                 Ok(())
             }
+            CommandParameterSpec::TrackName { description: _ } => {
+                if !text.trim().is_empty() {
+                    self.add_track_name_parameter(text.trim());
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::AlbumName { description: _ } => {
+                if !text.trim().is_empty() {
+                    self.add_album_name_parameter(text.trim());
+                }
+
+                Ok(())
+            }
             CommandParameterSpec::InputFolder { description: _ } => {
                 if !text.is_empty() {
                     self.add_input_folder_parameter(text);
@@ -139,6 +379,343 @@ impl State {
 
                 Ok(())
             }
+            CommandParameterSpec::Service { description: _ } => {
+                let service = text.trim().to_lowercase();
+                if service.is_empty() {
+                    return Ok(());
+                }
+
+                if SERVICE_HINTS.contains(&service.as_str()) {
+                    self.add_service_parameter(&service);
+                    Ok(())
+                } else {
+                    Err(InteractiveError::ParsingIssue {
+                        message: format!("{text} is not a service ({})", SERVICE_HINTS.join(", ")),
+                    })
+                }
+            }
+            CommandParameterSpec::MinListenTime { description: _ } => {
+                if let Ok(seconds) = text.parse::<u64>() {
+                    self.add_min_listen_ms_parameter(seconds * 1000);
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::MinPercent { description: _ } => {
+                if let Some(percent) = text
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|p| (0.0..=100.0).contains(p))
+                {
+                    self.add_min_percent_parameter(percent);
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::HonourSkippedFlag { description: _ } => {
+                if let Some(honour) = parse_yes_no(text) {
+                    self.add_honour_skipped_flag_parameter(honour);
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::HonourReasonEnd { description: _ } => {
+                if let Some(honour) = parse_yes_no(text) {
+                    self.add_honour_reason_end_parameter(honour);
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::Metric { description: _ } => {
+                if let Ok(metric) = Metric::try_from(text) {
+                    self.add_metric_parameter(metric);
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::Months { description: _ } => {
+                if let Ok(months) = text.parse::<u32>() {
+                    self.add_months_parameter(months);
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::ReportFormat { description: _ } => {
+                if let Ok(report_format) = ReportFormat::try_from(text) {
+                    self.add_report_format_parameter(report_format);
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::From { description: _ } => {
+                if text.trim().is_empty() {
+                    return Ok(());
+                }
+
+                let today = Local::now().naive_local().date();
+                match (
+                    parse_date_input(text, &today),
+                    parse_relative_period(text, &today),
+                ) {
+                    (Some(from), _) => {
+                        self.add_from_parameter(from);
+                        Ok(())
+                    }
+                    (None, Some((from, to))) => {
+                        // The period only fills in the end when one wasn't given.
+                        self.add_from_parameter(from);
+                        let has_to = self
+                            .command_parameters
+                            .as_ref()
+                            .and_then(|parameters| parameters.to())
+                            .is_some();
+                        if !has_to {
+                            self.add_to_parameter(to);
+                        }
+                        Ok(())
+                    }
+                    (None, None) => Err(InteractiveError::ParsingIssue {
+                        message: format!(
+                            "{text} is not a date (YYYY-MM-DD, N days ago or a period like last month)"
+                        ),
+                    }),
+                }
+            }
+            CommandParameterSpec::To { description: _ } => {
+                if text.trim().is_empty() {
+                    return Ok(());
+                }
+
+                let today = Local::now().naive_local().date();
+                match parse_date_input(text, &today)
+                    .or_else(|| parse_relative_period(text, &today).map(|(_, to)| to))
+                {
+                    Some(to) => {
+                        self.add_to_parameter(to);
+                        Ok(())
+                    }
+                    None => Err(InteractiveError::ParsingIssue {
+                        message: format!(
+                            "{text} is not a date (YYYY-MM-DD, N days ago or a period like last month)"
+                        ),
+                    }),
+                }
+            }
+            CommandParameterSpec::FirstPeriod { description: _ } => {
+                if text.trim().is_empty() {
+                    return Ok(());
+                }
+
+                match Period::try_from(text) {
+                    Ok(period) => {
+                        self.add_first_period_parameter(period);
+                        Ok(())
+                    }
+                    Err(_) => Err(InteractiveError::ParsingIssue {
+                        message: format!("{text} is not a year, month or date range"),
+                    }),
+                }
+            }
+            CommandParameterSpec::Alias { description: _ } => {
+                if !text.trim().is_empty() {
+                    self.add_alias_parameter(text.trim());
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::TitleRules { description: _ } => {
+                if text.trim().is_empty() {
+                    return Ok(());
+                }
+
+                match TitleNormalizer::try_from(text) {
+                    Ok(rules) => {
+                        self.add_title_rules_parameter(rules);
+                        Ok(())
+                    }
+                    Err(message) => Err(InteractiveError::ParsingIssue { message }),
+                }
+            }
+            CommandParameterSpec::Separators { description: _ } => {
+                if !text.trim().is_empty() {
+                    self.add_separators_parameter(ArtistCredits::parse_separators(text));
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::CreditExceptions { description: _ } => {
+                if !text.trim().is_empty() {
+                    self.add_credit_exceptions_parameter(ArtistCredits::parse_exceptions(text));
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::SavedQueryName { description: _ } => {
+                if !text.trim().is_empty() {
+                    self.add_saved_query_name_parameter(text.trim());
+                }
+
+                Ok(())
+            }
+            CommandParameterSpec::Command { description: _ } => {
+                if text.trim().is_empty() {
+                    return Ok(());
+                }
+
+                CommandLine::parse(text)?;
+                self.add_command_parameter(text.trim());
+                Ok(())
+            }
+            CommandParameterSpec::Query { description: _ } => match query::parse(text) {
+                Ok(_) => {
+                    self.add_query_parameter(text);
+                    Ok(())
+                }
+                Err(error) => Err(InteractiveError::ParsingIssue {
+                    message: error.to_string(),
+                }),
+            },
+            CommandParameterSpec::SecondPeriod { description: _ } => {
+                if text.trim().is_empty() {
+                    return Ok(());
+                }
+
+                match Period::try_from(text) {
+                    Ok(period) => {
+                        self.add_second_period_parameter(period);
+                        Ok(())
+                    }
+                    Err(_) => Err(InteractiveError::ParsingIssue {
+                        message: format!("{text} is not a year, month or date range"),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn add_from_parameter(&mut self, from: NaiveDate) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_from_parameter(from));
+        }
+    }
+
+    fn add_to_parameter(&mut self, to: NaiveDate) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_to_parameter(to));
+        }
+    }
+
+    fn add_first_period_parameter(&mut self, period: Period) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_first_period_parameter(period));
+        }
+    }
+
+    fn add_second_period_parameter(&mut self, period: Period) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_second_period_parameter(period));
+        }
+    }
+
+    fn add_title_rules_parameter(&mut self, rules: TitleNormalizer) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_title_rules_parameter(rules));
+        }
+    }
+
+    fn add_separators_parameter(&mut self, separators: Vec<String>) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_separators_parameter(separators));
+        }
+    }
+
+    fn add_credit_exceptions_parameter(&mut self, exceptions: Vec<String>) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_credit_exceptions_parameter(exceptions));
+        }
+    }
+
+    fn add_track_name_parameter(&mut self, track_name: &str) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_track_name_parameter(track_name));
+        }
+    }
+
+    fn add_album_name_parameter(&mut self, album_name: &str) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_album_name_parameter(album_name));
+        }
+    }
+
+    fn add_alias_parameter(&mut self, alias: &str) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_alias_parameter(alias));
+        }
+    }
+
+    fn add_saved_query_name_parameter(&mut self, name: &str) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_saved_query_name_parameter(name));
+        }
+    }
+
+    fn add_command_parameter(&mut self, command: &str) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_command_parameter(command));
+        }
+    }
+
+    fn add_query_parameter(&mut self, query: &str) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_query_parameter(query));
+        }
+    }
+
+    fn add_report_format_parameter(&mut self, report_format: ReportFormat) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_report_format_parameter(report_format));
+        }
+    }
+
+    fn add_months_parameter(&mut self, months: u32) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_months_parameter(months));
+        }
+    }
+
+    fn add_metric_parameter(&mut self, metric: Metric) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_metric_parameter(metric));
+        }
+    }
+
+    fn add_service_parameter(&mut self, service: &str) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_service_parameter(service));
+        }
+    }
+
+    fn add_min_listen_ms_parameter(&mut self, min_listen_ms: u64) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_min_listen_ms_parameter(min_listen_ms));
+        }
+    }
+
+    fn add_min_percent_parameter(&mut self, min_percent: f64) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_min_percent_parameter(min_percent));
+        }
+    }
+
+    fn add_honour_skipped_flag_parameter(&mut self, honour: bool) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_honour_skipped_flag_parameter(honour));
+        }
+    }
+
+    fn add_honour_reason_end_parameter(&mut self, honour: bool) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_honour_reason_end_parameter(honour));
         }
     }
 
@@ -184,6 +761,12 @@ impl State {
         }
     }
 
+    fn add_min_plays_parameter(&mut self, min_plays: u64) {
+        if let Some(parameters) = &self.command_parameters {
+            self.command_parameters = Some(parameters.with_min_plays_parameter(min_plays));
+        }
+    }
+
     fn add_artist_count_parameter(&mut self, count: usize) {
         if let Some(parameters) = &self.command_parameters {
             self.command_parameters = Some(parameters.with_count_parameter(count));
@@ -197,8 +780,9 @@ impl State {
     }
 
     fn add_name_parameter(&mut self, name: &str) {
+        let artist_name = self.aliases.resolve(&ArtistName(name.to_string()));
         if let Some(parameters) = &self.command_parameters {
-            self.command_parameters = Some(parameters.with_name_parameter(name));
+            self.command_parameters = Some(parameters.with_name_parameter(&artist_name.0));
         }
     }
 
@@ -213,7 +797,7 @@ impl State {
 
     pub fn previous_page(&mut self) {
         if self.current_page == 0 {
-            self.current_page = (self.output.len() - 1).max(0)
+            self.current_page = self.output.len().saturating_sub(1)
         } else {
             self.current_page -= 1;
         }
@@ -225,6 +809,7 @@ impl State {
         }
         self.current_page = 0;
         self.command_parameters = None;
+        self.command_line = None;
         self.input.clear();
         self.command_parameter_inputs.clear();
     }
@@ -236,3 +821,128 @@ impl State {
         self.mode = Mode::CommandParameters;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, NaiveDate};
+
+    use crate::{
+        app::{CommandName, CommandParameterSpec, HasId, MessageSet, Output, Scroll},
+        processing::ArtistAliases,
+    };
+
+    use super::State;
+
+    fn output(title: &str) -> Output {
+        Output::MessageSet(MessageSet::with_messages(title, Vec::new()))
+    }
+
+    fn titles(state: &State) -> Vec<&str> {
+        state.output().iter().map(|output| output.title()).collect()
+    }
+
+    #[test]
+    fn keeps_pinned_output_when_cleared() {
+        let mut state = State::default();
+        state.insert_output(0, output("Top artists"));
+        state.insert_output(0, output("Top songs"));
+        state.insert_output(0, output("Top albums"));
+
+        state.toggle_pinned(1);
+        state.rename_output(1, "Songs of the year");
+        state.scroll_mut().set_page_height(2);
+        for index in 0..3 {
+            let id = state.output()[index].id().to_string();
+            state.scroll_mut().scroll(&id, Scroll::Down(2), 10);
+        }
+        state.clear_output();
+        assert_eq!(vec!["Songs of the year"], titles(&state));
+        let pinned_id = state.output()[0].id().to_string();
+        assert_eq!(2, state.scroll().offset(&pinned_id));
+
+        state.insert_output(0, output("Top albums"));
+        state.current_page = 1;
+        state.remove_output(0);
+        assert_eq!(vec!["Songs of the year"], titles(&state));
+        assert_eq!(0, state.current_page);
+    }
+
+    #[test]
+    fn reads_settings_from_older_states_without_saving_them() {
+        let mut aliases = ArtistAliases::default();
+        aliases.add("Mgla", "Mgła");
+        let mut data = serde_json::to_value(State::default()).unwrap();
+        data["aliases"] = serde_json::to_value(&aliases).unwrap();
+
+        let state: State = serde_json::from_value(data).unwrap();
+        assert_eq!(aliases, state.settings().aliases);
+
+        let data = serde_json::to_value(&state).unwrap();
+        assert!(data.get("aliases").is_none());
+    }
+
+    fn spec(name: &CommandName, key: &str) -> CommandParameterSpec {
+        name.parameters()
+            .into_iter()
+            .find(|spec| spec.key() == key)
+            .unwrap()
+    }
+
+    #[test]
+    fn rejects_relative_periods_that_are_not_a_year_or_month() {
+        let name = CommandName::TopArtists;
+        let mut state = State::default();
+        state.setup_for_command(&name);
+
+        assert!(state
+            .insert_command_parameter("last 30 days", &spec(&name, "month"))
+            .is_err());
+        assert!(state
+            .insert_command_parameter("last month", &spec(&name, "year"))
+            .is_err());
+        assert!(state
+            .insert_command_parameter("last year", &spec(&name, "year"))
+            .is_ok());
+        assert!(state
+            .insert_command_parameter("this month", &spec(&name, "month"))
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_services() {
+        let name = CommandName::Reclassify;
+        let mut state = State::default();
+        state.setup_for_command(&name);
+
+        assert!(state
+            .insert_command_parameter("spotfy", &spec(&name, "service"))
+            .is_err());
+        assert!(state
+            .insert_command_parameter(" Apple_Music ", &spec(&name, "service"))
+            .is_ok());
+    }
+
+    #[test]
+    fn keeps_an_explicit_to_when_from_is_a_period() {
+        let name = CommandName::TopArtists;
+        let to = NaiveDate::from_ymd_opt(2030, 6, 1).unwrap();
+        let mut state = State::default();
+        state.setup_for_command(&name);
+        state
+            .insert_command_parameter("2030-06-01", &spec(&name, "to"))
+            .unwrap();
+        state
+            .insert_command_parameter("this year", &spec(&name, "from"))
+            .unwrap();
+        let parameters = state.command_parameters.as_ref().unwrap();
+        assert_eq!(Some(&to), parameters.to());
+
+        let today = Local::now().naive_local().date();
+        state.setup_for_command(&name);
+        state
+            .insert_command_parameter("this year", &spec(&name, "from"))
+            .unwrap();
+        let parameters = state.command_parameters.as_ref().unwrap();
+        assert_eq!(Some(&today), parameters.to());
+    }
+}
@@ -0,0 +1,350 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::projections::statistics::{
+    ArtistAndAlbumCount, ArtistAndSongCount, ArtistSongCounter, Metric,
+};
+
+use super::HasId;
+
+/// One value in a table, kept typed so numbers sort as numbers and export as they are.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Cell {
+    Text(String),
+    Count(u64),
+    Minutes(f64),
+}
+
+impl Cell {
+    fn compare(&self, other: &Cell) -> Ordering {
+        match (self, other) {
+            (Cell::Text(a), Cell::Text(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+            (Cell::Count(a), Cell::Count(b)) => a.cmp(b),
+            (Cell::Minutes(a), Cell::Minutes(b)) => a.total_cmp(b),
+            _ => self.to_string().cmp(&other.to_string()),
+        }
+    }
+
+    /// Milliseconds played as minutes. They're only rounded when shown, so exports keep
+    /// the whole value.
+    pub fn minutes(time_ms: u64) -> Self {
+        Cell::Minutes(time_ms as f64 / 60_000.0)
+    }
+
+    /// The value as it's exported, without the rounding used to show it.
+    pub fn value(&self) -> String {
+        match self {
+            Cell::Minutes(minutes) => minutes.to_string(),
+            cell => cell.to_string(),
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self, Cell::Text(_))
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Text(text) => f.write_str(text),
+            Cell::Count(count) => write!(f, "{count}"),
+            Cell::Minutes(minutes) => write!(f, "{minutes:.1}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TableSort {
+    pub column: usize,
+    pub descending: bool,
+}
+
+/// Results with a value per column, shown as a table that can be sorted by any column
+/// and exported with the values themselves rather than a formatted line.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Table {
+    id: String,
+    title: String,
+    columns: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+    #[serde(default)]
+    sort: Option<TableSort>,
+}
+
+impl HasId for Table {
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Table {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
+    pub fn columns(&self) -> &Vec<String> {
+        &self.columns
+    }
+
+    pub fn sort(&self) -> &Option<TableSort> {
+        &self.sort
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The rows in the chosen sort order, or as they were ranked if no column was chosen.
+    pub fn rows(&self) -> Vec<&Vec<Cell>> {
+        let mut rows: Vec<&Vec<Cell>> = self.rows.iter().collect();
+        if let Some(sort) = &self.sort {
+            rows.sort_by(|a, b| {
+                let ordering = match (a.get(sort.column), b.get(sort.column)) {
+                    (Some(a), Some(b)) => a.compare(b),
+                    _ => Ordering::Equal,
+                };
+                if sort.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        rows
+    }
+
+    /// Sorts by the column, numbers largest first and text alphabetically. Sorting by the
+    /// same column again reverses the order.
+    pub fn sort_by(&mut self, column: usize) {
+        if column >= self.columns.len() {
+            return;
+        }
+
+        self.sort = match &self.sort {
+            Some(sort) if sort.column == column => Some(TableSort {
+                column,
+                descending: !sort.descending,
+            }),
+            _ => Some(TableSort {
+                column,
+                descending: self
+                    .rows
+                    .first()
+                    .and_then(|row| row.get(column))
+                    .is_some_and(Cell::is_numeric),
+            }),
+        };
+    }
+
+    /// Each row as a line of text, for copying to the clipboard.
+    pub fn messages(&self) -> Vec<String> {
+        self.rows()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" - ")
+            })
+            .collect()
+    }
+
+    pub fn with_rows(title: &str, columns: &[&str], rows: Vec<Vec<Cell>>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: title.to_string(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows,
+            sort: None,
+        }
+    }
+
+    pub fn ranked_artists(title: &str, counters: &[ArtistSongCounter], metric: &Metric) -> Self {
+        let has_featured = counters.iter().any(|counter| counter.featured_plays > 0);
+        let mut columns = vec!["Rank", "Artist", "Plays", "Minutes"];
+        if has_featured {
+            columns.push("Featured plays");
+        }
+        let rows = counters
+            .iter()
+            .enumerate()
+            .map(|(index, counter)| {
+                let mut row = vec![
+                    Cell::Count(index as u64 + 1),
+                    Cell::Text(counter.artist_name.0.clone()),
+                    Cell::Count(counter.total_song_plays()),
+                    Cell::minutes(counter.play_details.total_time_played().time_ms),
+                ];
+                if has_featured {
+                    row.push(Cell::Count(counter.featured_plays));
+                }
+                row
+            })
+            .collect();
+
+        Self::with_rows(title, &columns, rows).sorted_by_metric(metric)
+    }
+
+    pub fn ranked_songs(title: &str, counts: &[ArtistAndSongCount], metric: &Metric) -> Self {
+        Self::ranked_songs_with_count(title, counts, "Plays", metric)
+    }
+
+    /// Songs ranked by something other than plays, like skips, with the count column named
+    /// for it.
+    pub fn ranked_songs_with_count(
+        title: &str,
+        counts: &[ArtistAndSongCount],
+        count_column: &str,
+        metric: &Metric,
+    ) -> Self {
+        let rows = counts
+            .iter()
+            .enumerate()
+            .map(|(index, count)| {
+                vec![
+                    Cell::Count(index as u64 + 1),
+                    Cell::Text(count.artist_name.0.clone()),
+                    Cell::Text(count.song_count.0 .0.clone()),
+                    Cell::Count(count.song_count.1),
+                    Cell::minutes(count.song_count.2),
+                ]
+            })
+            .collect();
+
+        Self::with_rows(
+            title,
+            &["Rank", "Artist", "Song", count_column, "Minutes"],
+            rows,
+        )
+        .sorted_by_metric(metric)
+    }
+
+    pub fn ranked_albums(title: &str, counts: &[ArtistAndAlbumCount], metric: &Metric) -> Self {
+        let rows = counts
+            .iter()
+            .enumerate()
+            .map(|(index, count)| {
+                vec![
+                    Cell::Count(index as u64 + 1),
+                    Cell::Text(count.artist_name.0.clone()),
+                    Cell::Text(count.album_count.0 .0.clone()),
+                    Cell::Count(count.album_count.1),
+                    Cell::minutes(count.album_count.2),
+                ]
+            })
+            .collect();
+
+        Self::with_rows(
+            title,
+            &["Rank", "Artist", "Album", "Plays", "Minutes"],
+            rows,
+        )
+        .sorted_by_metric(metric)
+    }
+
+    /// The rows come ranked already, this only marks the column they were ranked by.
+    fn sorted_by_metric(self, metric: &Metric) -> Self {
+        let name = match metric {
+            Metric::Plays => return self,
+            Metric::Time => "Minutes",
+        };
+        let column = self.columns.iter().position(|column| column == name);
+
+        Self {
+            sort: column.map(|column| TableSort {
+                column,
+                descending: true,
+            }),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cell, Table};
+
+    fn table() -> Table {
+        Table::with_rows(
+            "Top artists",
+            &["Rank", "Artist", "Plays"],
+            vec![
+                vec![
+                    Cell::Count(1),
+                    Cell::Text("Ulcerate".to_string()),
+                    Cell::Count(12),
+                ],
+                vec![
+                    Cell::Count(2),
+                    Cell::Text("gorguts".to_string()),
+                    Cell::Count(9),
+                ],
+                vec![
+                    Cell::Count(3),
+                    Cell::Text("Blut Aus Nord".to_string()),
+                    Cell::Count(100),
+                ],
+            ],
+        )
+    }
+
+    #[test]
+    fn rounds_minutes_only_when_shown() {
+        let minutes = Cell::minutes(2_914_500);
+        assert_eq!("48.6", minutes.to_string());
+        assert_eq!("48.575", minutes.value());
+        assert_eq!("12", Cell::Count(12).value());
+    }
+
+    fn artists(table: &Table) -> Vec<String> {
+        table.rows().iter().map(|row| row[1].to_string()).collect()
+    }
+
+    #[test]
+    fn sorts_by_column_and_reverses() {
+        let mut table = table();
+        assert_eq!(
+            vec!["Ulcerate", "gorguts", "Blut Aus Nord"],
+            artists(&table)
+        );
+
+        table.sort_by(1);
+        assert_eq!(
+            vec!["Blut Aus Nord", "gorguts", "Ulcerate"],
+            artists(&table)
+        );
+
+        table.sort_by(2);
+        assert_eq!(
+            vec!["Blut Aus Nord", "Ulcerate", "gorguts"],
+            artists(&table)
+        );
+
+        table.sort_by(2);
+        assert_eq!(
+            vec!["gorguts", "Ulcerate", "Blut Aus Nord"],
+            artists(&table)
+        );
+
+        table.sort_by(7);
+        assert_eq!(
+            vec![
+                "2 - gorguts - 9",
+                "1 - Ulcerate - 12",
+                "3 - Blut Aus Nord - 100"
+            ],
+            table.messages()
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use chrono::{Month, NaiveDate, Weekday};
+
+use crate::{
+    projections::statistics::{order_in_week, ArtistsCounts, EventProcessor, Metric},
+    track_plays::ArtistName,
+};
+
+use super::{MessageSet, Report};
+
+pub fn wrapped_report(processor: &EventProcessor, year: i32, count: usize) -> Option<Report> {
+    let year_counts = processor.year_count(year)?;
+    let counts = &year_counts.artists_counts;
+    let previous = processor
+        .year_count(year - 1)
+        .map(|year_counts| &year_counts.artists_counts);
+
+    let mut month_plays: Vec<(u32, u64)> = year_counts
+        .month_counts()
+        .iter()
+        .map(|month_counts| {
+            (
+                month_counts.month,
+                month_counts.artists_counts.total_count(&None),
+            )
+        })
+        .collect();
+    month_plays.sort_by_key(|(month, _)| *month);
+
+    let mut weekday_plays: HashMap<Weekday, u64> = HashMap::new();
+    for month_counts in year_counts.month_counts() {
+        for day_counts in month_counts.day_counts() {
+            *weekday_plays.entry(day_counts.weekday).or_default() +=
+                day_counts.artists_counts.total_count(&None);
+        }
+    }
+    let mut weekday_plays: Vec<(Weekday, u64)> = weekday_plays.into_iter().collect();
+    weekday_plays.sort_by_key(|(weekday, _)| order_in_week(weekday));
+
+    let pages = vec![
+        overview_page(year, counts),
+        year_over_year_page(year, counts, previous),
+        top_artists_page(year, count, counts, previous),
+        top_songs_page(year, count, counts),
+        top_albums_page(year, count, counts),
+        discoveries_page(year, count, processor, counts),
+        calendar_page(year, &month_plays, &weekday_plays),
+        streak_page(year, processor),
+        most_skipped_page(year, count, counts),
+    ];
+
+    Some(Report {
+        title: format!("Wrapped {year}"),
+        pages,
+    })
+}
+
+fn overview_page(year: i32, counts: &ArtistsCounts) -> MessageSet {
+    MessageSet::with_messages(
+        &format!("Wrapped {year}: overview"),
+        vec![
+            format!(
+                "You listened for {:.0} minutes ({:.1} hours)",
+                counts.time_played().time_min,
+                counts.time_played().time_hr
+            ),
+            format!(
+                "You played {} songs from {} artists",
+                counts.total_count(&None),
+                counts.artist_count()
+            ),
+            format!("You skipped {} songs", counts.total_skip_count()),
+        ],
+    )
+}
+
+fn year_over_year_page(
+    year: i32,
+    counts: &ArtistsCounts,
+    previous: Option<&ArtistsCounts>,
+) -> MessageSet {
+    let title = format!("Wrapped {year}: compared to {}", year - 1);
+    let messages = match previous {
+        Some(previous) => vec![
+            format!(
+                "Minutes listened: {:.0} ({})",
+                counts.time_played().time_min,
+                percent_change(
+                    counts.time_played().time_min,
+                    previous.time_played().time_min
+                )
+            ),
+            format!(
+                "Plays: {} ({})",
+                counts.total_count(&None),
+                percent_change(
+                    counts.total_count(&None) as f64,
+                    previous.total_count(&None) as f64
+                )
+            ),
+            format!(
+                "Artists: {} ({})",
+                counts.artist_count(),
+                percent_change(counts.artist_count() as f64, previous.artist_count() as f64)
+            ),
+            format!(
+                "Skips: {} ({})",
+                counts.total_skip_count(),
+                percent_change(
+                    counts.total_skip_count() as f64,
+                    previous.total_skip_count() as f64
+                )
+            ),
+        ],
+        None => vec![format!("No listens in {} to compare with", year - 1)],
+    };
+
+    MessageSet::with_messages(&title, messages)
+}
+
+fn top_artists_page(
+    year: i32,
+    count: usize,
+    counts: &ArtistsCounts,
+    previous: Option<&ArtistsCounts>,
+) -> MessageSet {
+    let previous_ranks: HashMap<ArtistName, usize> = previous
+        .map(|previous| {
+            previous
+                .top(previous.artist_count(), &Metric::Plays)
+                .into_iter()
+                .enumerate()
+                .map(|(index, counter)| (counter.artist_name, index + 1))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut messages = vec!["By plays:".to_string()];
+    for (index, counter) in counts.top(count, &Metric::Plays).iter().enumerate() {
+        let movement = match previous_ranks.get(&counter.artist_name) {
+            Some(rank) => format!("#{rank} in {}", year - 1),
+            None => "new".to_string(),
+        };
+        messages.push(format!(
+            "{}. {} ({movement})",
+            index + 1,
+            counter.metric_display(&Metric::Plays)
+        ));
+    }
+    messages.push("By minutes:".to_string());
+    messages.append(&mut ranked(
+        counts
+            .top(count, &Metric::Time)
+            .iter()
+            .map(|counter| counter.metric_display(&Metric::Time)),
+    ));
+
+    MessageSet::with_messages(&format!("Wrapped {year}: top artists"), messages)
+}
+
+fn top_songs_page(year: i32, count: usize, counts: &ArtistsCounts) -> MessageSet {
+    let mut messages = vec!["By plays:".to_string()];
+    messages.append(&mut ranked(
+        counts
+            .top_songs(count, &Metric::Plays)
+            .iter()
+            .map(|song_count| song_count.metric_display(&Metric::Plays)),
+    ));
+    messages.push("By minutes:".to_string());
+    messages.append(&mut ranked(
+        counts
+            .top_songs(count, &Metric::Time)
+            .iter()
+            .map(|song_count| song_count.metric_display(&Metric::Time)),
+    ));
+
+    MessageSet::with_messages(&format!("Wrapped {year}: top songs"), messages)
+}
+
+fn top_albums_page(year: i32, count: usize, counts: &ArtistsCounts) -> MessageSet {
+    let mut messages = vec!["By plays:".to_string()];
+    messages.append(&mut ranked(
+        counts
+            .top_albums(count, &Metric::Plays)
+            .iter()
+            .map(|album_count| album_count.metric_display(&Metric::Plays)),
+    ));
+    messages.push("By minutes:".to_string());
+    messages.append(&mut ranked(
+        counts
+            .top_albums(count, &Metric::Time)
+            .iter()
+            .map(|album_count| album_count.metric_display(&Metric::Time)),
+    ));
+
+    MessageSet::with_messages(&format!("Wrapped {year}: top albums"), messages)
+}
+
+fn discoveries_page(
+    year: i32,
+    count: usize,
+    processor: &EventProcessor,
+    counts: &ArtistsCounts,
+) -> MessageSet {
+    let discovered = processor.listen_timeline.discovered(Some(year), None);
+    let mut discovered_counters: Vec<(u64, String)> = discovered
+        .iter()
+        .filter_map(|(artist_name, span)| {
+            counts.find_artist(artist_name).map(|counter| {
+                (
+                    counter.total_song_plays(),
+                    format!(
+                        "{} (first listen {})",
+                        counter.metric_display(&Metric::Plays),
+                        span.first.format("%Y-%m-%d")
+                    ),
+                )
+            })
+        })
+        .collect();
+    discovered_counters.sort_by_key(|(plays, _)| Reverse(*plays));
+
+    let mut messages = vec![format!("You discovered {} new artists", discovered.len())];
+    messages.append(&mut ranked(
+        discovered_counters
+            .into_iter()
+            .take(count)
+            .map(|(_, message)| message),
+    ));
+
+    MessageSet::with_messages(&format!("Wrapped {year}: discoveries"), messages)
+}
+
+fn calendar_page(
+    year: i32,
+    month_plays: &[(u32, u64)],
+    weekday_plays: &[(Weekday, u64)],
+) -> MessageSet {
+    let top_month = month_plays
+        .iter()
+        .max_by_key(|(_, plays)| *plays)
+        .map(|(month, plays)| {
+            let month_name = u8::try_from(*month)
+                .ok()
+                .and_then(|m| Month::try_from(m).ok())
+                .map(|m| m.name().to_string())
+                .unwrap_or_else(|| format!("{month:02}"));
+            format!("Top month: {month_name} with {plays} plays")
+        })
+        .unwrap_or_else(|| "Top month: none".to_string());
+    let top_weekday = weekday_plays
+        .iter()
+        .max_by_key(|(_, plays)| *plays)
+        .map(|(weekday, plays)| format!("Top weekday: {weekday} with {plays} plays"))
+        .unwrap_or_else(|| "Top weekday: none".to_string());
+
+    let mut messages = vec![top_month, top_weekday, "Plays per month:".to_string()];
+    messages.append(
+        &mut month_plays
+            .iter()
+            .map(|(month, plays)| format!("{month:02} - {plays}"))
+            .collect(),
+    );
+
+    MessageSet::with_messages(&format!("Wrapped {year}: calendar"), messages)
+}
+
+fn streak_page(year: i32, processor: &EventProcessor) -> MessageSet {
+    let last_day = NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or_default();
+    let streaks = processor.listen_streaks(Some(year), &last_day);
+
+    let mut messages = vec![
+        streaks
+            .longest
+            .as_ref()
+            .map(|streak| format!("Longest streak: {streak}"))
+            .unwrap_or_else(|| "Longest streak: none".to_string()),
+        streaks
+            .busiest_day
+            .map(|(date, plays)| {
+                format!(
+                    "Busiest day: {} with {plays} plays",
+                    date.format("%Y-%m-%d")
+                )
+            })
+            .unwrap_or_else(|| "Busiest day: none".to_string()),
+    ];
+    if let Some((artist_name, streak)) = streaks.artist_streaks.first() {
+        messages.push(format!("Longest artist streak: {artist_name} - {streak}"));
+    }
+
+    MessageSet::with_messages(&format!("Wrapped {year}: streaks"), messages)
+}
+
+fn most_skipped_page(year: i32, count: usize, counts: &ArtistsCounts) -> MessageSet {
+    let most_skipped = counts.top_skipped_songs(count, &None);
+    let messages = if most_skipped.is_empty() {
+        vec!["No skipped songs found".to_string()]
+    } else {
+        ranked(
+            most_skipped
+                .iter()
+                .map(|song_count| format!("{song_count}")),
+        )
+    };
+
+    MessageSet::with_messages(&format!("Wrapped {year}: most skipped"), messages)
+}
+
+fn ranked(messages: impl Iterator<Item = String>) -> Vec<String> {
+    messages
+        .enumerate()
+        .map(|(index, message)| format!("{}. {message}", index + 1))
+        .collect()
+}
+
+fn percent_change(current: f64, previous: f64) -> String {
+    if previous == 0.0 {
+        return "no previous listens".to_string();
+    }
+
+    let change = (current - previous) / previous * 100.0;
+    format!("{change:+.1}%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::percent_change;
+
+    #[test]
+    fn formats_percent_change() {
+        assert_eq!("+50.0%", percent_change(150.0, 100.0));
+        assert_eq!("-25.0%", percent_change(75.0, 100.0));
+        assert_eq!("no previous listens", percent_change(10.0, 0.0));
+    }
+}
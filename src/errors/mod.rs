@@ -1,9 +1,11 @@
 mod event_errors;
 mod interactive_error;
+mod query_error;
 mod read_error;
 mod write_error;
 
 pub use event_errors::{AddEventError, GetEventsError};
 pub use interactive_error::InteractiveError;
+pub use query_error::QueryError;
 pub use read_error::ReadError;
 pub use write_error::WriteError;
@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum QueryError {
+    #[error("Query ended early, expected {expected:?}")]
+    UnexpectedEnd { expected: String },
+    #[error("Unexpected {token:?} in query, expected {expected:?}")]
+    UnexpectedToken { token: String, expected: String },
+    #[error("Unknown field {field:?}")]
+    UnknownField { field: String },
+    #[error("Invalid value {value:?} for {field:?}")]
+    InvalidValue { field: String, value: String },
+    #[error("Unterminated quote in query")]
+    UnterminatedQuote,
+}
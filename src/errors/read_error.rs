@@ -12,4 +12,8 @@ pub enum ReadError {
     NotAFile { file_name: String },
     #[error("Cannot read file contents {file_name:?} {message:?}")]
     CannotReadContents { file_name: String, message: String },
+    #[error("Cannot read listens {message:?}")]
+    CannotReadListens { message: String },
+    #[error("Cannot read the {name} setting {message:?}")]
+    CannotReadSetting { name: String, message: String },
 }
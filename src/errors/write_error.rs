@@ -8,6 +8,12 @@ pub enum WriteError {
     FailedToSerializeJson { message: String },
     #[error("Unable to serialize data with error {message:?})")]
     FailedToSerializeYaml { message: String },
+    #[error("Unable to serialize data as {format}, it can only be rendered")]
+    NotSerializable { format: String },
+    #[error("Unable to render the chart as an image with message {message:?}")]
+    CannotRenderImage { message: String },
     #[error("Unable to write to file (path {path:?}, with message {message:?})")]
     CannotWriteToFile { path: String, message: String },
+    #[error("Unable to store the settings with message {message:?}")]
+    CannotWriteSettings { message: String },
 }
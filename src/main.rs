@@ -4,6 +4,7 @@ mod logging;
 mod persistence;
 mod processing;
 mod projections;
+mod query;
 mod render;
 mod track_plays;
 mod utils;
@@ -12,7 +13,10 @@ use std::sync::Arc;
 
 use logging::setup_logging;
 use persistence::{
-    sqlite::{listen_tracker_repo, DatabaseConfig, SqliteEventStore, SqliteStateStore},
+    sqlite::{
+        listen_tracker_repo, DatabaseConfig, SqliteEventStore, SqliteListensTable,
+        SqliteSettingsStore, SqliteStateStore,
+    },
     OutputFolder,
 };
 use tokio::sync::Mutex;
@@ -33,8 +37,10 @@ async fn main() -> Result<(), std::io::Error> {
     let repository = Arc::new(Mutex::new(
         listen_tracker_repo(20_000, &pool, store.clone()).await,
     ));
+    let listens = Arc::new(Mutex::new(SqliteListensTable::from(pool.clone())));
     let state_store = Arc::new(Mutex::new(SqliteStateStore::from(pool.clone())));
-    render::full_ui(store, state_store, repository)
+    let settings_store = Arc::new(Mutex::new(SqliteSettingsStore::from(pool.clone())));
+    render::full_ui(store, state_store, repository, listens, settings_store)
         .await
         .unwrap();
     Ok(())
@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum Format {
     Json,
     Yaml,
+    Csv,
+    Markdown,
+    Html,
+    Svg,
+    Png,
 }
 
 impl Format {
@@ -11,21 +16,38 @@ impl Format {
         match *self {
             Format::Json => "json",
             Format::Yaml => "yaml",
+            Format::Csv => "csv",
+            Format::Markdown => "md",
+            Format::Html => "html",
+            Format::Svg => "svg",
+            Format::Png => "png",
         }
     }
+
+    /// Json and Yaml write the output as it's stored, the others are rendered as tables.
+    pub fn is_serialized(&self) -> bool {
+        matches!(self, Format::Json | Format::Yaml)
+    }
+
+    /// Svg and Png write each chart to an image of its own instead of one file.
+    pub fn is_image(&self) -> bool {
+        matches!(self, Format::Svg | Format::Png)
+    }
 }
 
 impl TryFrom<String> for Format {
     type Error = ();
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let lowered = value.to_lowercase();
-        if lowered.eq("json") {
-            Ok(Format::Json)
-        } else if lowered.eq("yaml") || lowered.eq("yml") {
-            Ok(Format::Yaml)
-        } else {
-            Err(())
+        match value.trim().to_lowercase().as_str() {
+            "json" => Ok(Format::Json),
+            "yaml" | "yml" => Ok(Format::Yaml),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            "svg" => Ok(Format::Svg),
+            "png" => Ok(Format::Png),
+            _ => Err(()),
         }
     }
 }
@@ -24,6 +24,49 @@ impl From<Box<dyn Folder + Sync>> for FileWriter {
     }
 }
 
+impl FileWriter {
+    pub fn write_text(
+        &self,
+        contents: &str,
+        name: &str,
+        extension: &str,
+    ) -> Result<String, WriteError> {
+        self.write_bytes(contents.as_bytes(), name, extension)
+    }
+
+    pub fn write_bytes(
+        &self,
+        contents: &[u8],
+        name: &str,
+        extension: &str,
+    ) -> Result<String, WriteError> {
+        self.folder.create_if_necessary();
+
+        let mut path_buf = PathBuf::new();
+        path_buf.push(self.folder.path());
+        path_buf.push(name);
+        path_buf.set_extension(extension);
+
+        let mut file = match File::create(&path_buf) {
+            Ok(it) => it,
+            Err(e) => {
+                return Err(WriteError::CannotCreateFile {
+                    path: format!("{}", path_buf.display()),
+                    message: e.to_string(),
+                })
+            }
+        };
+
+        match file.write_all(contents) {
+            Ok(_) => Ok(format!("{}", path_buf.display())),
+            Err(e) => Err(WriteError::CannotWriteToFile {
+                path: format!("{}", path_buf.display()),
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
 #[async_trait]
 impl Writer for FileWriter {
     async fn write<T: Serialize + Sync>(
@@ -32,12 +75,15 @@ impl Writer for FileWriter {
         name: &str,
         format: Format,
     ) -> Result<bool, WriteError> {
+        if !format.is_serialized() {
+            return Err(WriteError::NotSerializable {
+                format: format.extension_display().to_string(),
+            });
+        }
+
         self.folder.create_if_necessary();
 
-        let file_extension = match format {
-            Format::Json => "json",
-            Format::Yaml => "yaml",
-        };
+        let file_extension = format.extension_display();
 
         let mut path_buf = PathBuf::new();
         path_buf.push(self.folder.path());
@@ -48,7 +94,7 @@ impl Writer for FileWriter {
             Ok(it) => it,
             Err(e) => {
                 return Err(WriteError::CannotCreateFile {
-                    path: format!("{}", &path_buf.display()),
+                    path: format!("{}", path_buf.display()),
                     message: e.to_string(),
                 })
             }
@@ -72,6 +118,7 @@ impl Writer for FileWriter {
                     })
                 }
             },
+            Format::Csv | Format::Markdown | Format::Html | Format::Svg | Format::Png => {}
         }
 
         match writer.flush() {
@@ -2,6 +2,7 @@ mod event_store;
 mod format;
 pub mod fs;
 mod output_folder;
+mod settings_store;
 pub mod sqlite;
 mod state_store;
 mod writer;
@@ -9,6 +10,7 @@ mod writer;
 pub use event_store::EventStore;
 pub use format::Format;
 pub use output_folder::OutputFolder;
+pub use settings_store::SettingsStore;
 
 pub use state_store::StateStore;
 pub use writer::Writer;
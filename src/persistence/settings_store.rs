@@ -0,0 +1,11 @@
+use crate::{
+    app::Settings,
+    errors::{ReadError, WriteError},
+};
+
+#[async_trait::async_trait]
+pub trait SettingsStore {
+    async fn push(&mut self, settings: &Settings) -> Result<(), WriteError>;
+    /// `None` until settings have been stored for the first time.
+    async fn get(&self) -> Result<Option<Settings>, ReadError>;
+}
@@ -0,0 +1,381 @@
+use sqlx::{Pool, Sqlite, Transaction};
+
+use crate::{
+    errors::ReadError,
+    processing::events::Event,
+    projections::{
+        statistics::{Listen, ListenRules},
+        ListensRepository,
+    },
+    track_plays::{AlbumName, ArtistName, TrackName},
+    utils::parse_formatted_end_time,
+};
+
+const SNAPSHOT_NAME: &str = "listens_table";
+const SELECT_LISTENS: &str = "select artist_name, track_name, album_name, end_time, ms_played, service, skipped from listens";
+
+type ListenRow = (String, String, Option<String>, String, i64, String, bool);
+
+/// Keeps a plain `listens` table in step with the event stream, so the listens can be
+/// queried with `sqlite3` or other tools without digging through the JSON event data.
+///
+/// Rows hold the listens as the statistics see them: artists resolved through the aliases
+/// and titles normalised. The rules are kept with the table's snapshot, so the table is
+/// rebuilt whenever the classification, aliases or title rules change.
+pub struct SqliteListensTable {
+    pool: Pool<Sqlite>,
+}
+
+impl From<Pool<Sqlite>> for SqliteListensTable {
+    fn from(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ListensRepository for SqliteListensTable {
+    async fn project_events(&mut self, events: &[Event], rules: &ListenRules) {
+        let Some(version) = events.iter().map(|event| event.version).max() else {
+            return;
+        };
+
+        if let Err(e) = self.write(events, version, rules, false).await {
+            log::error!("{e:?}");
+        }
+    }
+
+    async fn sync(&mut self, events: &[Event], rules: &ListenRules) {
+        let version = events.iter().map(|event| event.version).max().unwrap_or(0);
+        let listen_count = events
+            .iter()
+            .filter(|event| Listen::from_event(event, &rules.classification).is_some())
+            .count();
+
+        match self.is_current(version, listen_count, rules).await {
+            Ok(true) => {}
+            Ok(false) => {
+                if let Err(e) = self.write(events, version, rules, true).await {
+                    log::error!("{e:?}");
+                }
+            }
+            Err(e) => log::error!("{e:?}"),
+        }
+    }
+
+    async fn listens(&self) -> Result<Vec<Listen>, ReadError> {
+        let rows: Vec<ListenRow> = sqlx::query_as(&format!("{SELECT_LISTENS} order by position"))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(read_error)?;
+
+        Ok(rows.into_iter().filter_map(listen_from_row).collect())
+    }
+
+    async fn album_listens(&self, album_name: &AlbumName) -> Result<Vec<Listen>, ReadError> {
+        let rows: Vec<ListenRow> = sqlx::query_as(&format!(
+            "{SELECT_LISTENS} where album_name = $1 collate nocase order by position"
+        ))
+        .bind(&album_name.0)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(read_error)?;
+
+        Ok(rows.into_iter().filter_map(listen_from_row).collect())
+    }
+
+    async fn track_listens(&self, track_name: &TrackName) -> Result<Vec<Listen>, ReadError> {
+        let rows: Vec<ListenRow> = sqlx::query_as(&format!(
+            "{SELECT_LISTENS} where track_name = $1 collate nocase order by position"
+        ))
+        .bind(&track_name.0)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(read_error)?;
+
+        Ok(rows.into_iter().filter_map(listen_from_row).collect())
+    }
+}
+
+impl SqliteListensTable {
+    /// The table only needs rebuilding if it's behind the stream, rows have gone
+    /// missing, or the listens were read under different rules.
+    async fn is_current(
+        &self,
+        version: u32,
+        listen_count: usize,
+        rules: &ListenRules,
+    ) -> Result<bool, sqlx::Error> {
+        let snapshot: Option<(String, u32)> =
+            sqlx::query_as("select data, version from snapshots where name = $1")
+                .bind(SNAPSHOT_NAME)
+                .fetch_optional(&self.pool)
+                .await?;
+        let (row_count,): (i64,) = sqlx::query_as("select count(*) from listens")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(match snapshot {
+            Some((data, snapshot_version)) => {
+                snapshot_version == version
+                    && row_count as usize == listen_count
+                    && serde_json::from_str::<ListenRules>(&data).ok().as_ref() == Some(rules)
+            }
+            None => version == 0 && row_count == 0,
+        })
+    }
+
+    async fn write(
+        &self,
+        events: &[Event],
+        version: u32,
+        rules: &ListenRules,
+        replace_all: bool,
+    ) -> Result<(), sqlx::Error> {
+        let data = serde_json::to_string(rules).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+        let mut transaction = self.pool.begin().await?;
+
+        if replace_all {
+            sqlx::query("delete from listens")
+                .execute(&mut *transaction)
+                .await?;
+        } else {
+            // New events can only be added on to a table that's caught up to just before
+            // them. Otherwise the table is left behind for the next sync to rebuild.
+            let snapshot: Option<(u32,)> =
+                sqlx::query_as("select version from snapshots where name = $1")
+                    .bind(SNAPSHOT_NAME)
+                    .fetch_optional(&mut *transaction)
+                    .await?;
+            let table_version = snapshot.map(|(version,)| version).unwrap_or(0);
+            let first_version = events.iter().map(|event| event.version).min().unwrap_or(0);
+            if first_version > table_version + 1 {
+                log::warn!(
+                    "listens table is at version {table_version}, not projecting events from {first_version}"
+                );
+                return Ok(());
+            }
+        }
+
+        for event in events.iter() {
+            if let Some(listen) = rules.listen(event) {
+                insert_listen(&mut transaction, event.version, &listen).await?;
+            }
+        }
+
+        sqlx::query("insert or replace into snapshots (name, version, data) values ($1, $2, $3)")
+            .bind(SNAPSHOT_NAME)
+            .bind(version)
+            .bind(data)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await
+    }
+}
+
+async fn insert_listen(
+    transaction: &mut Transaction<'_, Sqlite>,
+    position: u32,
+    listen: &Listen,
+) -> Result<(), sqlx::Error> {
+    let query = "insert or replace into listens (position, artist_name, track_name, album_name, end_time, ms_played, service, skipped) values ($1, $2, $3, $4, $5, $6, $7, $8)";
+
+    sqlx::query(query)
+        .bind(position)
+        .bind(&listen.artist_name.0)
+        .bind(&listen.track_name.0)
+        .bind(listen.album_name.as_ref().map(|album_name| &album_name.0))
+        .bind(listen.listened_at.format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind(listen.ms_played as i64)
+        .bind(&listen.service)
+        .bind(listen.skipped)
+        .execute(&mut **transaction)
+        .await?;
+
+    Ok(())
+}
+
+fn listen_from_row(row: ListenRow) -> Option<Listen> {
+    let (artist_name, track_name, album_name, end_time, ms_played, service, skipped) = row;
+
+    parse_formatted_end_time(&end_time)
+        .ok()
+        .map(|listened_at| Listen {
+            artist_name: ArtistName(artist_name),
+            track_name: TrackName(track_name),
+            album_name: album_name.map(AlbumName),
+            service,
+            listened_at,
+            ms_played: ms_played as u64,
+            skipped,
+        })
+}
+
+fn read_error(e: sqlx::Error) -> ReadError {
+    ReadError::CannotReadListens {
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+
+    use crate::{
+        processing::events::{Event, EventData, TrackPlayAdded},
+        projections::{statistics::ListenRules, ListensRepository},
+        track_plays::{AlbumName, ArtistName, TrackName},
+    };
+
+    use super::SqliteListensTable;
+
+    async fn memory_pool() -> Pool<Sqlite> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        pool
+    }
+
+    fn event(version: u32, ms_played: u64) -> Event {
+        Event {
+            version,
+            data: EventData::TrackPlayAdded(TrackPlayAdded {
+                artist_name: ArtistName("Ulcerate".to_string()),
+                track_name: TrackName("Yield to Naught".to_string()),
+                album_name: None,
+                ms_played,
+                end_time: format!("2022-10-0{version} 21:00:00"),
+                service_hint: "spotify".to_string(),
+                track_ms: None,
+                skipped: None,
+                reason_end: None,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn syncs_and_rebuilds_from_events() {
+        let pool = memory_pool().await;
+        let mut table = SqliteListensTable::from(pool.clone());
+        let mut rules = ListenRules::default();
+        let events = vec![event(1, 240_000), event(2, 5_000)];
+
+        table.project_events(&events[..1], &rules).await;
+        table.sync(&events, &rules).await;
+
+        let rows: Vec<(u32, bool)> =
+            sqlx::query_as("select position, skipped from listens order by position")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(vec![(1, false), (2, true)], rows);
+
+        rules.classification.policy_mut(&None).min_listen_ms = 1_000;
+        table.sync(&events, &rules).await;
+
+        let (skips,): (i64,) = sqlx::query_as("select count(*) from listens where skipped")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(0, skips);
+    }
+
+    #[tokio::test]
+    async fn stores_resolved_names_and_rebuilds_when_aliases_change() {
+        let pool = memory_pool().await;
+        let mut table = SqliteListensTable::from(pool.clone());
+        let mut rules = ListenRules::default();
+        let events = [event(1, 240_000)];
+
+        table.sync(&events, &rules).await;
+        rules.aliases.add("Ulcerate", "Ulcerate (NZ)");
+        table.sync(&events, &rules).await;
+
+        let (artist_name,): (String,) = sqlx::query_as("select artist_name from listens")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!("Ulcerate (NZ)", artist_name);
+    }
+
+    #[tokio::test]
+    async fn reads_listens_back() {
+        let pool = memory_pool().await;
+        let mut table = SqliteListensTable::from(pool.clone());
+        let rules = ListenRules::default();
+        let mut events = vec![event(1, 240_000), event(2, 5_000)];
+        if let EventData::TrackPlayAdded(added) = &mut events[1].data {
+            added.album_name = Some(AlbumName("Stare into Death and Be Still".to_string()));
+        }
+
+        table.sync(&events, &rules).await;
+
+        let listens = table.listens().await.unwrap();
+        assert_eq!(2, listens.len());
+        assert_eq!("Yield to Naught", listens[0].track_name.0);
+        assert!(listens[1].skipped);
+
+        let album_listens = table
+            .album_listens(&AlbumName("stare into death and be still".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(1, album_listens.len());
+        assert_eq!(5_000, album_listens[0].ms_played);
+
+        let track_listens = table
+            .track_listens(&TrackName("YIELD TO NAUGHT".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(2, track_listens.len());
+    }
+
+    async fn positions(pool: &Pool<Sqlite>) -> Vec<u32> {
+        sqlx::query_as("select position from listens order by position")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(position,)| position)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn projects_new_events_onto_a_synced_table() {
+        let pool = memory_pool().await;
+        let mut table = SqliteListensTable::from(pool.clone());
+        let rules = ListenRules::default();
+        let events = [event(1, 240_000), event(2, 240_000), event(3, 240_000)];
+
+        table.sync(&events[..2], &rules).await;
+        assert_eq!(vec![1, 2], positions(&pool).await);
+
+        table.project_events(&events[2..], &rules).await;
+        assert_eq!(vec![1, 2, 3], positions(&pool).await);
+        assert!(table.is_current(3, 3, &rules).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn leaves_a_table_that_is_behind_for_the_next_sync() {
+        let pool = memory_pool().await;
+        let mut table = SqliteListensTable::from(pool.clone());
+        let rules = ListenRules::default();
+        let events = vec![
+            event(1, 240_000),
+            event(2, 240_000),
+            event(3, 240_000),
+            event(4, 240_000),
+        ];
+
+        table.project_events(&events[..1], &rules).await;
+        table.project_events(&events[2..], &rules).await;
+        assert_eq!(vec![1], positions(&pool).await);
+        assert!(!table.is_current(4, 4, &rules).await.unwrap());
+
+        table.sync(&events, &rules).await;
+        assert_eq!(vec![1, 2, 3, 4], positions(&pool).await);
+        assert!(table.is_current(4, 4, &rules).await.unwrap());
+    }
+}
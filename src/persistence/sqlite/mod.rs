@@ -1,11 +1,15 @@
 mod config;
 mod listen_tracker_repository;
+mod listens_table;
 mod pool;
+mod settings_store;
 mod state_store;
 mod store;
 
 pub use config::DatabaseConfig;
 pub use listen_tracker_repository::listen_tracker_repo;
+pub use listens_table::SqliteListensTable;
 pub use pool::build_pool_and_migrate;
+pub use settings_store::SqliteSettingsStore;
 pub use state_store::SqliteStateStore;
 pub use store::SqliteEventStore;
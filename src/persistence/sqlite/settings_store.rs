@@ -0,0 +1,209 @@
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::{Pool, Sqlite, Transaction};
+
+use crate::{
+    app::{SavedQueries, Settings},
+    errors::{ReadError, WriteError},
+    persistence::SettingsStore,
+    processing::ArtistAliases,
+};
+
+const CLASSIFICATION: &str = "classification";
+const TITLES: &str = "titles";
+const CREDITS: &str = "credits";
+
+/// Keeps the aliases and saved queries in tables of their own and every other setting in
+/// its own row, so one setting that can't be read doesn't cost the rest.
+pub struct SqliteSettingsStore {
+    pool: Pool<Sqlite>,
+}
+
+impl From<Pool<Sqlite>> for SqliteSettingsStore {
+    fn from(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl SettingsStore for SqliteSettingsStore {
+    async fn push(&mut self, settings: &Settings) -> Result<(), WriteError> {
+        self.write(settings).await.map_err(|e| match e {
+            sqlx::Error::Encode(e) => WriteError::FailedToSerializeJson {
+                message: e.to_string(),
+            },
+            e => WriteError::CannotWriteSettings {
+                message: e.to_string(),
+            },
+        })
+    }
+
+    async fn get(&self) -> Result<Option<Settings>, ReadError> {
+        let rows: Vec<(String, String)> = sqlx::query_as("select name, data from settings")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| read_error("settings", e.to_string()))?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let aliases: Vec<(String, String)> =
+            sqlx::query_as("select alias, artist_name from artist_aliases")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| read_error("aliases", e.to_string()))?;
+        let saved_queries: Vec<(String, String)> =
+            sqlx::query_as("select name, command from saved_queries")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| read_error("saved queries", e.to_string()))?;
+        let mut settings = Settings {
+            aliases: ArtistAliases::default(),
+            classification: setting(&rows, CLASSIFICATION)?,
+            titles: setting(&rows, TITLES)?,
+            credits: setting(&rows, CREDITS)?,
+            saved_queries: SavedQueries::default(),
+        };
+        for (alias, artist_name) in aliases {
+            settings.aliases.add(&alias, &artist_name);
+        }
+        for (name, command) in saved_queries {
+            settings.saved_queries.save(&name, &command);
+        }
+
+        Ok(Some(settings))
+    }
+}
+
+impl SqliteSettingsStore {
+    async fn write(&self, settings: &Settings) -> Result<(), sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+
+        sqlx::query("delete from artist_aliases")
+            .execute(&mut *transaction)
+            .await?;
+        for alias in settings.aliases.iter() {
+            sqlx::query("insert into artist_aliases (alias, artist_name) values ($1, $2)")
+                .bind(&alias.alias.0)
+                .bind(&alias.artist_name.0)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        sqlx::query("delete from saved_queries")
+            .execute(&mut *transaction)
+            .await?;
+        for (name, command) in settings.saved_queries.iter() {
+            sqlx::query("insert into saved_queries (name, command) values ($1, $2)")
+                .bind(name)
+                .bind(command)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        write_setting(&mut transaction, CLASSIFICATION, &settings.classification).await?;
+        write_setting(&mut transaction, TITLES, &settings.titles).await?;
+        write_setting(&mut transaction, CREDITS, &settings.credits).await?;
+
+        transaction.commit().await
+    }
+}
+
+async fn write_setting<T: Serialize>(
+    transaction: &mut Transaction<'_, Sqlite>,
+    name: &str,
+    value: &T,
+) -> Result<(), sqlx::Error> {
+    let data = serde_json::to_string(value).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+    sqlx::query("insert or replace into settings (name, data) values ($1, $2)")
+        .bind(name)
+        .bind(data)
+        .execute(&mut **transaction)
+        .await?;
+
+    Ok(())
+}
+
+/// A setting missing from the table is left at its default, one that's there but can't
+/// be read is an error so it isn't quietly replaced.
+fn setting<T: DeserializeOwned + Default>(
+    rows: &[(String, String)],
+    name: &str,
+) -> Result<T, ReadError> {
+    match rows.iter().find(|(row_name, _)| row_name == name) {
+        Some((_, data)) => serde_json::from_str(data).map_err(|e| read_error(name, e.to_string())),
+        None => Ok(T::default()),
+    }
+}
+
+fn read_error(name: &str, message: String) -> ReadError {
+    ReadError::CannotReadSetting {
+        name: name.to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use crate::{
+        app::Settings,
+        errors::ReadError,
+        persistence::SettingsStore,
+        processing::{ArtistCredits, TitleNormalizer},
+    };
+
+    use super::SqliteSettingsStore;
+
+    async fn memory_store() -> SqliteSettingsStore {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        SqliteSettingsStore::from(pool)
+    }
+
+    #[tokio::test]
+    async fn stores_and_reads_settings() {
+        let mut store = memory_store().await;
+        assert_eq!(None, store.get().await.unwrap());
+
+        let mut settings = Settings {
+            titles: TitleNormalizer::try_from("all").unwrap(),
+            credits: ArtistCredits {
+                separators: ArtistCredits::parse_separators("&"),
+                exceptions: Vec::new(),
+            },
+            ..Default::default()
+        };
+        settings.aliases.add("Mgla", "Mgła");
+        settings.classification.policy_mut(&None).min_listen_ms = 1_000;
+        settings.saved_queries.save(
+            "monthly-report",
+            "top artists count=25 month=\"this month\"",
+        );
+        store.push(&settings).await.unwrap();
+        assert_eq!(Some(settings.clone()), store.get().await.unwrap());
+
+        settings.aliases.remove("Mgla");
+        store.push(&settings).await.unwrap();
+        assert_eq!(Some(settings), store.get().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn refuses_settings_that_cannot_be_read() {
+        let store = memory_store().await;
+        sqlx::query("insert into settings (name, data) values ('titles', 'not json')")
+            .execute(&store.pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            store.get().await,
+            Err(ReadError::CannotReadSetting { name, .. }) if name == "titles"
+        ));
+    }
+}
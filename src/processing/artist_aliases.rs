@@ -0,0 +1,109 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::track_plays::ArtistName;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArtistAlias {
+    pub alias: ArtistName,
+    pub artist_name: ArtistName,
+}
+
+impl Display for ArtistAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("{} -> {}", self.alias, self.artist_name))
+    }
+}
+
+/// Other spellings of an artist's name, counted as the artist when the statistics
+/// are built. Aliases are matched regardless of case.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ArtistAliases {
+    aliases: BTreeMap<String, ArtistAlias>,
+}
+
+impl ArtistAliases {
+    pub fn resolve(&self, artist_name: &ArtistName) -> ArtistName {
+        self.aliases
+            .get(&artist_name.0.to_lowercase())
+            .map(|alias| alias.artist_name.clone())
+            .unwrap_or_else(|| artist_name.clone())
+    }
+
+    /// Returns the alias that was added, or `None` if the name would only point back
+    /// at itself. Aliases that pointed at the new alias are moved on to the artist, so
+    /// names never need to be resolved more than once.
+    pub fn add(&mut self, alias: &str, artist_name: &str) -> Option<ArtistAlias> {
+        let artist_name = self.resolve(&ArtistName(artist_name.to_string()));
+        if artist_name.0 == alias {
+            return None;
+        }
+
+        let key = alias.to_lowercase();
+        for existing in self.aliases.values_mut() {
+            if existing.artist_name.0.to_lowercase() == key {
+                existing.artist_name = artist_name.clone();
+            }
+        }
+
+        let artist_alias = ArtistAlias {
+            alias: ArtistName(alias.to_string()),
+            artist_name,
+        };
+        self.aliases.insert(key, artist_alias.clone());
+
+        Some(artist_alias)
+    }
+
+    pub fn remove(&mut self, alias: &str) -> Option<ArtistAlias> {
+        self.aliases.remove(&alias.to_lowercase())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ArtistAlias> {
+        self.aliases.values()
+    }
+
+    pub fn descriptions(&self) -> Vec<String> {
+        self.aliases
+            .values()
+            .map(|alias| alias.to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::track_plays::ArtistName;
+
+    use super::ArtistAliases;
+
+    #[test]
+    fn resolves_aliases_through_merges() {
+        let mut aliases = ArtistAliases::default();
+        aliases.add("Mgla", "Mgła").unwrap();
+        aliases.add("Blut aus Nord", "Blut Aus Nord").unwrap();
+        aliases.add("The Ocean", "The Ocean Collective").unwrap();
+        aliases.add("The Ocean Collective", "The Ocean Collective (Band)");
+
+        assert_eq!(
+            ArtistName("Mgła".to_string()),
+            aliases.resolve(&ArtistName("MGLA".to_string()))
+        );
+        assert_eq!(
+            ArtistName("Blut Aus Nord".to_string()),
+            aliases.resolve(&ArtistName("Blut Aus Nord".to_string()))
+        );
+        assert_eq!(
+            ArtistName("The Ocean Collective (Band)".to_string()),
+            aliases.resolve(&ArtistName("The Ocean".to_string()))
+        );
+        assert!(aliases.add("Mgła", "Mgla").is_none());
+
+        aliases.remove("mgla");
+        assert_eq!(
+            ArtistName("Mgla".to_string()),
+            aliases.resolve(&ArtistName("Mgla".to_string()))
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+
+use crate::track_plays::ArtistName;
+
+/// How a featured artist is introduced, in an artist name or a song title.
+pub const FEATURED_PREFIXES: [&str; 4] = ["feat. ", "feat ", "ft. ", "featuring "];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreditRole {
+    Primary,
+    Featured,
+}
+
+/// The artists credited on a listen, with the first primary artist being the one the
+/// listen itself is counted under.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Credits {
+    pub primary: Vec<ArtistName>,
+    pub featured: Vec<ArtistName>,
+}
+
+/// Rules for splitting an artist string like "A & B feat. C" into the artists it credits.
+/// Splitting is off until separators are set, and exceptions are never split so bands
+/// like "Simon & Garfunkel" stay whole.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ArtistCredits {
+    pub separators: Vec<String>,
+    pub exceptions: Vec<String>,
+}
+
+impl ArtistCredits {
+    pub fn is_enabled(&self) -> bool {
+        !self.separators.is_empty()
+    }
+
+    /// Separators are entered as words, so `& , x` splits on " & ", ", " and " x ".
+    pub fn parse_separators(text: &str) -> Vec<String> {
+        if text.trim().eq_ignore_ascii_case("off") {
+            return Vec::new();
+        }
+
+        text.split_whitespace()
+            .map(|separator| match separator {
+                "," => ", ".to_string(),
+                separator => format!(" {separator} "),
+            })
+            .collect()
+    }
+
+    pub fn parse_exceptions(text: &str) -> Vec<String> {
+        if text.trim().eq_ignore_ascii_case("none") {
+            return Vec::new();
+        }
+
+        text.split(';')
+            .map(|exception| exception.trim().to_string())
+            .filter(|exception| !exception.is_empty())
+            .collect()
+    }
+
+    pub fn split(&self, artist_name: &ArtistName) -> Credits {
+        if !self.is_enabled() || self.is_exception(&artist_name.0) {
+            return Credits {
+                primary: vec![artist_name.clone()],
+                featured: Vec::new(),
+            };
+        }
+
+        let featured_markers = FEATURED_PREFIXES.map(|prefix| format!(" {prefix}"));
+        let (primary, featured) = match find_ignore_case(&artist_name.0, &featured_markers) {
+            Some((position, marker)) => (
+                &artist_name.0[..position],
+                &artist_name.0[position + marker.len()..],
+            ),
+            None => (artist_name.0.as_str(), ""),
+        };
+
+        let primary = self.split_names(primary);
+        if primary.is_empty() {
+            return Credits {
+                primary: vec![artist_name.clone()],
+                featured: Vec::new(),
+            };
+        }
+
+        Credits {
+            primary,
+            featured: self.split_names(featured),
+        }
+    }
+
+    pub fn descriptions(&self) -> Vec<String> {
+        let separators: Vec<String> = self
+            .separators
+            .iter()
+            .map(|separator| format!("{:?}", separator))
+            .collect();
+
+        vec![
+            if self.is_enabled() {
+                format!("separators: {}", separators.join(" "))
+            } else {
+                "separators: off".to_string()
+            },
+            if self.exceptions.is_empty() {
+                "exceptions: none".to_string()
+            } else {
+                format!("exceptions: {}", self.exceptions.join("; "))
+            },
+        ]
+    }
+
+    fn is_exception(&self, name: &str) -> bool {
+        self.exceptions
+            .iter()
+            .any(|exception| exception.to_lowercase() == name.trim().to_lowercase())
+    }
+
+    fn split_names(&self, names: &str) -> Vec<ArtistName> {
+        let mut remaining = names.trim();
+        let mut split = Vec::new();
+
+        while !remaining.is_empty() {
+            // An exception at the start of what's left is taken whole, separators and all.
+            if let Some(exception) = self.exceptions.iter().find(|exception| {
+                remaining
+                    .get(..exception.len())
+                    .map(|start| start.eq_ignore_ascii_case(exception))
+                    .unwrap_or(false)
+            }) {
+                split.push(remaining[..exception.len()].to_string());
+                remaining = &remaining[exception.len()..];
+            } else {
+                match find_ignore_case(remaining, &self.separators) {
+                    Some((position, _)) => {
+                        split.push(remaining[..position].to_string());
+                        remaining = &remaining[position..];
+                    }
+                    None => {
+                        split.push(remaining.to_string());
+                        remaining = "";
+                    }
+                }
+            }
+
+            if let Some(separator) = self.separators.iter().find(|separator| {
+                remaining
+                    .get(..separator.len())
+                    .map(|start| start.eq_ignore_ascii_case(separator))
+                    .unwrap_or(false)
+            }) {
+                remaining = &remaining[separator.len()..];
+            }
+            remaining = remaining.trim_start();
+        }
+
+        split
+            .into_iter()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .map(ArtistName)
+            .collect()
+    }
+}
+
+pub(super) fn find_ignore_case<'a, S: AsRef<str>>(
+    text: &str,
+    patterns: &'a [S],
+) -> Option<(usize, &'a str)> {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            let pattern = pattern.as_ref();
+            text.char_indices()
+                .map(|(position, _)| position)
+                .find(|position| {
+                    text.get(*position..*position + pattern.len())
+                        .map(|slice| slice.eq_ignore_ascii_case(pattern))
+                        .unwrap_or(false)
+                })
+                .map(|position| (position, pattern))
+        })
+        .min_by_key(|(position, _)| *position)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::track_plays::ArtistName;
+
+    use super::{ArtistCredits, Credits};
+
+    fn names(names: &[&str]) -> Vec<ArtistName> {
+        names
+            .iter()
+            .map(|name| ArtistName(name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn splits_primary_and_featured_artists() {
+        let credits = ArtistCredits {
+            separators: ArtistCredits::parse_separators("& ,"),
+            exceptions: ArtistCredits::parse_exceptions("Simon & Garfunkel; Earth, Wind & Fire"),
+        };
+
+        assert_eq!(
+            Credits {
+                primary: names(&["Ulcerate", "Blut Aus Nord"]),
+                featured: names(&["Mgła", "Ihsahn"]),
+            },
+            credits.split(&ArtistName(
+                "Ulcerate & Blut Aus Nord feat. Mgła, Ihsahn".to_string()
+            ))
+        );
+        assert_eq!(
+            names(&["Simon & Garfunkel"]),
+            credits
+                .split(&ArtistName("Simon & Garfunkel".to_string()))
+                .primary
+        );
+        assert_eq!(
+            names(&["Earth, Wind & Fire", "The Emotions"]),
+            credits
+                .split(&ArtistName("Earth, Wind & Fire & The Emotions".to_string()))
+                .primary
+        );
+    }
+
+    #[test]
+    fn does_not_split_when_off() {
+        let credits = ArtistCredits::default();
+
+        assert_eq!(
+            names(&["A & B"]),
+            credits.split(&ArtistName("A & B".to_string())).primary
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::{collections::HashMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::yes_no;
+
+pub const DEFAULT_MIN_LISTEN_MS: u64 = 1000 * 10;
+pub const DEFAULT_MIN_PERCENT: f64 = 10.0;
+
+const SKIP_REASONS: [&str; 4] = [
+    "fwdbtn",
+    "backbtn",
+    "track_skipped_forwards",
+    "track_skipped_backwards",
+];
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ClassificationPolicy {
+    pub min_listen_ms: u64,
+    pub min_percent: f64,
+    pub honour_skipped_flag: bool,
+    pub honour_reason_end: bool,
+}
+
+impl Default for ClassificationPolicy {
+    fn default() -> Self {
+        Self {
+            min_listen_ms: DEFAULT_MIN_LISTEN_MS,
+            min_percent: DEFAULT_MIN_PERCENT,
+            honour_skipped_flag: true,
+            honour_reason_end: false,
+        }
+    }
+}
+
+impl Display for ClassificationPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "min listen: {}s, min percent: {}%, honour skipped flag: {}, honour reason end: {}",
+            self.min_listen_ms / 1000,
+            self.min_percent,
+            yes_no(self.honour_skipped_flag),
+            yes_no(self.honour_reason_end)
+        ))
+    }
+}
+
+impl ClassificationPolicy {
+    pub fn is_skipped(
+        &self,
+        ms_played: u64,
+        track_ms: Option<u64>,
+        skipped: Option<bool>,
+        reason_end: &Option<String>,
+    ) -> bool {
+        if ms_played <= self.min_listen_ms {
+            return true;
+        }
+
+        if self.honour_skipped_flag && skipped.unwrap_or_default() {
+            return true;
+        }
+
+        if self.honour_reason_end && reason_end.as_deref().map(is_skip_reason).unwrap_or(false) {
+            return true;
+        }
+
+        track_ms
+            .filter(|track_ms| *track_ms > 0)
+            .map(|track_ms| (ms_played as f64 / track_ms as f64) * 100.0 < self.min_percent)
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ClassificationPolicies {
+    default: ClassificationPolicy,
+    services: HashMap<String, ClassificationPolicy>,
+}
+
+impl ClassificationPolicies {
+    pub fn for_service(&self, service_hint: &str) -> &ClassificationPolicy {
+        self.services.get(service_hint).unwrap_or(&self.default)
+    }
+
+    pub fn policy_mut(&mut self, service: &Option<String>) -> &mut ClassificationPolicy {
+        match service {
+            Some(service_hint) => self
+                .services
+                .entry(service_hint.to_string())
+                .or_insert_with(|| self.default.clone()),
+            None => &mut self.default,
+        }
+    }
+
+    pub fn descriptions(&self) -> Vec<String> {
+        let mut descriptions: Vec<String> = self
+            .services
+            .iter()
+            .map(|(service_hint, policy)| format!("{service_hint}: {policy}"))
+            .collect();
+        descriptions.sort();
+        descriptions.insert(0, format!("default: {}", self.default));
+        descriptions
+    }
+}
+
+fn is_skip_reason(reason: &str) -> bool {
+    SKIP_REASONS
+        .iter()
+        .any(|skip_reason| skip_reason.eq_ignore_ascii_case(reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClassificationPolicies, ClassificationPolicy};
+
+    #[test]
+    fn is_skipped_by_percent() {
+        let policy = ClassificationPolicy::default();
+
+        assert!(!policy.is_skipped(330994, Some(357000), Some(false), &None));
+        assert!(policy.is_skipped(30000, Some(357000), Some(false), &None));
+    }
+
+    #[test]
+    fn is_skipped_by_reason_end() {
+        let reason_end = Some("fwdbtn".to_string());
+        let mut policy = ClassificationPolicy::default();
+
+        assert!(!policy.is_skipped(60000, None, None, &reason_end));
+
+        policy.honour_reason_end = true;
+        assert!(policy.is_skipped(60000, None, None, &reason_end));
+    }
+
+    #[test]
+    fn service_policy_falls_back_to_default() {
+        let mut policies = ClassificationPolicies::default();
+        policies
+            .policy_mut(&Some("spotify".to_string()))
+            .min_listen_ms = 30000;
+
+        assert!(policies
+            .for_service("spotify")
+            .is_skipped(20000, None, None, &None));
+        assert!(!policies
+            .for_service("apple_music")
+            .is_skipped(20000, None, None, &None));
+    }
+}
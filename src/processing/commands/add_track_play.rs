@@ -1,15 +1,18 @@
 use crate::{
-    processing::events::{Event, EventData, TrackPlayAdded, TrackSkipped},
+    processing::{
+        events::{Event, EventData, TrackPlayAdded, TrackSkipped},
+        ClassificationPolicies,
+    },
     projections::HasListen,
     track_plays::{self, Normalized, TrackPlay},
 };
 
-pub struct AddTrackPlay {
+pub struct AddTrackPlay<'a> {
     pub track_play: TrackPlay,
-    pub min_listen_length: u64,
+    pub classification: &'a ClassificationPolicies,
 }
 
-impl AddTrackPlay {
+impl AddTrackPlay<'_> {
     pub fn handle(&self, tracker: &impl HasListen) -> Option<Event> {
         let normalized =
             match <TrackPlay as TryInto<Normalized>>::try_into(self.track_play.to_owned()) {
@@ -37,7 +40,9 @@ impl AddTrackPlay {
             return None;
         }
 
-        if listen.is_skipped() || listen.play_time() <= self.min_listen_length {
+        let policy = self.classification.for_service(&listen.service_hint);
+
+        if listen.is_skipped_by(policy) {
             Some(EventData::TrackPlayIgnored(TrackSkipped {
                 artist_name: listen.artist_name.clone(),
                 track_name: listen.track_name.clone(),
@@ -45,6 +50,9 @@ impl AddTrackPlay {
                 end_time: listen.formatted_end_time(),
                 ms_played: listen.play_time(),
                 service_hint: listen.service_hint.clone(),
+                track_ms: listen.track_ms,
+                skipped: listen.skipped,
+                reason_end: listen.reason_end.clone(),
             }))
         } else {
             Some(EventData::TrackPlayAdded(TrackPlayAdded {
@@ -54,6 +62,9 @@ impl AddTrackPlay {
                 end_time: listen.formatted_end_time(),
                 ms_played: listen.play_time(),
                 service_hint: listen.service_hint.clone(),
+                track_ms: listen.track_ms,
+                skipped: listen.skipped,
+                reason_end: listen.reason_end.clone(),
             }))
         }
     }
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::track_plays::{AlbumName, ArtistName, TrackName};
+use crate::{
+    processing::ClassificationPolicy,
+    track_plays::{AlbumName, ArtistName, TrackName},
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TrackPlayAdded {
@@ -10,4 +13,21 @@ pub struct TrackPlayAdded {
     pub ms_played: u64,
     pub end_time: String,
     pub service_hint: String,
+    #[serde(default)]
+    pub track_ms: Option<u64>,
+    #[serde(default)]
+    pub skipped: Option<bool>,
+    #[serde(default)]
+    pub reason_end: Option<String>,
+}
+
+impl TrackPlayAdded {
+    pub fn is_skipped_by(&self, policy: &ClassificationPolicy) -> bool {
+        policy.is_skipped(
+            self.ms_played,
+            self.track_ms,
+            self.skipped,
+            &self.reason_end,
+        )
+    }
 }
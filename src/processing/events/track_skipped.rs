@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::track_plays::{AlbumName, ArtistName, TrackName};
+use crate::{
+    processing::ClassificationPolicy,
+    track_plays::{AlbumName, ArtistName, TrackName},
+};
+
+/// The minimum listen every skip was imported with before policies were configurable.
+const LEGACY_MIN_LISTEN_MS: u64 = 1000 * 10;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TrackSkipped {
@@ -10,4 +16,88 @@ pub struct TrackSkipped {
     pub ms_played: u64,
     pub end_time: String,
     pub service_hint: String,
+    #[serde(default)]
+    pub track_ms: Option<u64>,
+    #[serde(default)]
+    pub skipped: Option<bool>,
+    #[serde(default)]
+    pub reason_end: Option<String>,
+}
+
+impl TrackSkipped {
+    pub fn is_skipped_by(&self, policy: &ClassificationPolicy) -> bool {
+        // Events recorded before the listen details were stored only kept the outcome.
+        // Apple Music skips were partly judged by the percent played, which can't be
+        // worked out again without the track length, so they stay skips.
+        if self.is_legacy() && self.service_hint != "spotify" {
+            return true;
+        }
+
+        // A Spotify listen long enough to have counted must have been skipped by its flag,
+        // which only makes it a skip under a policy that honours the flag.
+        let skipped = if self.is_legacy()
+            && policy.honour_skipped_flag
+            && self.ms_played > LEGACY_MIN_LISTEN_MS
+        {
+            Some(true)
+        } else {
+            self.skipped
+        };
+
+        policy.is_skipped(self.ms_played, self.track_ms, skipped, &self.reason_end)
+    }
+
+    fn is_legacy(&self) -> bool {
+        self.track_ms.is_none() && self.skipped.is_none() && self.reason_end.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        processing::ClassificationPolicy,
+        track_plays::{ArtistName, TrackName},
+    };
+
+    use super::TrackSkipped;
+
+    fn legacy_skip(ms_played: u64, service_hint: &str) -> TrackSkipped {
+        TrackSkipped {
+            artist_name: ArtistName("Ulcerate".to_string()),
+            track_name: TrackName("Visceral Ends".to_string()),
+            album_name: None,
+            ms_played,
+            end_time: "2022-01-01 10:00".to_string(),
+            service_hint: service_hint.to_string(),
+            track_ms: None,
+            skipped: None,
+            reason_end: None,
+        }
+    }
+
+    #[test]
+    fn legacy_skips_follow_the_policy() {
+        let long = legacy_skip(60000, "spotify");
+        let short = legacy_skip(5000, "spotify");
+        let apple_music = legacy_skip(60000, "apple_music");
+
+        let policy = ClassificationPolicy::default();
+        assert!(long.is_skipped_by(&policy));
+        assert!(short.is_skipped_by(&policy));
+
+        let policy = ClassificationPolicy {
+            honour_skipped_flag: false,
+            ..ClassificationPolicy::default()
+        };
+        assert!(!long.is_skipped_by(&policy));
+        assert!(short.is_skipped_by(&policy));
+        assert!(apple_music.is_skipped_by(&policy));
+
+        let policy = ClassificationPolicy {
+            honour_skipped_flag: false,
+            min_listen_ms: 90000,
+            ..ClassificationPolicy::default()
+        };
+        assert!(long.is_skipped_by(&policy));
+    }
 }
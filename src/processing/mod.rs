@@ -1,5 +1,13 @@
+mod artist_aliases;
+mod artist_credits;
+mod classification;
 mod commands;
 pub mod events;
 mod process;
+mod title_normalizer;
 
+pub use artist_aliases::ArtistAliases;
+pub use artist_credits::{ArtistCredits, CreditRole};
+pub use classification::{ClassificationPolicies, ClassificationPolicy};
 pub use process::process_file;
+pub use title_normalizer::TitleNormalizer;
@@ -7,14 +7,13 @@ use crate::{
     projections::ListenTrackerRepository, track_plays::read_track_plays,
 };
 
-use super::events::Event;
-
-pub const MIN_LISTEN_LENGTH: u64 = 1000 * 10;
+use super::{events::Event, ClassificationPolicies};
 
 pub async fn process_file(
     path: &PathBuf,
     store: &Arc<Mutex<dyn EventStore>>,
     repository: &Arc<Mutex<dyn ListenTrackerRepository>>,
+    classification: &ClassificationPolicies,
 ) -> Result<Vec<Event>, ReadError> {
     let track_plays = read_track_plays(path)?;
 
@@ -25,7 +24,7 @@ pub async fn process_file(
     for track_play in track_plays.iter() {
         let command = AddTrackPlay {
             track_play: track_play.clone(),
-            min_listen_length: MIN_LISTEN_LENGTH,
+            classification,
         };
         let handle_result = command.handle(repo.get());
 
@@ -0,0 +1,318 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    track_plays::{AlbumName, ArtistName, TrackName},
+    utils::yes_no,
+};
+
+use super::artist_credits::{find_ignore_case, FEATURED_PREFIXES};
+
+const FEATURED_SEPARATORS: [&str; 3] = [", ", " & ", " and "];
+
+/// Rules for folding the different versions of a song or album title into one, so
+/// "Song - 2011 Remaster", "Song (Live)" and "Song (feat. X)" all count as "Song".
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TitleNormalizer {
+    pub strip_remaster: bool,
+    pub strip_live: bool,
+    pub strip_featured: bool,
+    pub strip_edition: bool,
+    pub fold_unicode: bool,
+    pub credit_featured: bool,
+}
+
+impl TryFrom<&str> for TitleNormalizer {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut normalizer = TitleNormalizer::default();
+        if value.trim().eq_ignore_ascii_case("none") {
+            return Ok(normalizer);
+        }
+
+        for rule in value.split([',', ' ']).filter(|rule| !rule.is_empty()) {
+            match rule.to_lowercase().as_str() {
+                "all" => {
+                    normalizer = TitleNormalizer {
+                        strip_remaster: true,
+                        strip_live: true,
+                        strip_featured: true,
+                        strip_edition: true,
+                        fold_unicode: true,
+                        credit_featured: true,
+                    }
+                }
+                "remaster" => normalizer.strip_remaster = true,
+                "live" => normalizer.strip_live = true,
+                "featured" | "feat" => normalizer.strip_featured = true,
+                "edition" => normalizer.strip_edition = true,
+                "unicode" => normalizer.fold_unicode = true,
+                "credits" => normalizer.credit_featured = true,
+                _ => return Err(format!("{rule} is not a title rule")),
+            }
+        }
+
+        Ok(normalizer)
+    }
+}
+
+impl Display for TitleNormalizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules: Vec<&str> = [
+            (self.strip_remaster, "remaster"),
+            (self.strip_live, "live"),
+            (self.strip_featured, "featured"),
+            (self.strip_edition, "edition"),
+            (self.fold_unicode, "unicode"),
+            (self.credit_featured, "credits"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, rule)| enabled.then_some(rule))
+        .collect();
+
+        if rules.is_empty() {
+            f.write_str("none")
+        } else {
+            f.write_str(&rules.join(", "))
+        }
+    }
+}
+
+impl TitleNormalizer {
+    pub fn track_name(&self, track_name: &TrackName) -> TrackName {
+        TrackName(self.normalize(&track_name.0))
+    }
+
+    pub fn album_name(&self, album_name: &AlbumName) -> AlbumName {
+        AlbumName(self.normalize(&album_name.0))
+    }
+
+    /// The artists featured on a track, if featured artists should be credited.
+    pub fn featured_artists(&self, track_name: &TrackName) -> Vec<ArtistName> {
+        if !self.credit_featured {
+            return Vec::new();
+        }
+
+        let title = self.fold(&track_name.0);
+        let mut remaining = title.as_str();
+        let mut featured = Vec::new();
+        while let Some((rest, suffix)) = split_suffix(remaining) {
+            if let Some(names) = featured_names(&suffix) {
+                featured.extend(split_names(names));
+            }
+            remaining = rest;
+        }
+        if let Some(position) = inline_featured_position(remaining) {
+            let names = &remaining[position..];
+            if let Some(names) = featured_names(names.trim_start()) {
+                featured.extend(split_names(names));
+            }
+        }
+
+        featured
+    }
+
+    pub fn descriptions(&self) -> Vec<String> {
+        vec![
+            format!("strip remaster suffixes: {}", yes_no(self.strip_remaster)),
+            format!("strip live suffixes: {}", yes_no(self.strip_live)),
+            format!("strip featured artists: {}", yes_no(self.strip_featured)),
+            format!("strip edition suffixes: {}", yes_no(self.strip_edition)),
+            format!("normalise unicode: {}", yes_no(self.fold_unicode)),
+            format!("credit featured artists: {}", yes_no(self.credit_featured)),
+        ]
+    }
+
+    fn normalize(&self, title: &str) -> String {
+        let title = self.fold(title);
+        let mut normalized = title.as_str();
+
+        // Strip one suffix at a time so "Song (feat. X) - 2011 Remaster" loses both.
+        while let Some((rest, suffix)) = split_suffix(normalized) {
+            if !self.strips(&suffix) {
+                break;
+            }
+            normalized = rest;
+        }
+
+        if self.strip_featured || self.credit_featured {
+            if let Some(position) = inline_featured_position(normalized) {
+                normalized = &normalized[..position];
+            }
+        }
+
+        match normalized.trim() {
+            "" => title.trim().to_string(),
+            trimmed => trimmed.to_string(),
+        }
+    }
+
+    fn fold(&self, title: &str) -> String {
+        if !self.fold_unicode {
+            return title.to_string();
+        }
+
+        let folded: String = title
+            .nfkc()
+            .map(|c| match c {
+                '\u{2018}' | '\u{2019}' | '\u{2032}' => '\'',
+                '\u{201C}' | '\u{201D}' => '"',
+                '\u{2010}'..='\u{2015}' => '-',
+                c => c,
+            })
+            .collect();
+
+        folded.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+
+    fn strips(&self, suffix: &str) -> bool {
+        let suffix = suffix.to_lowercase();
+
+        (self.strip_remaster && suffix.contains("remaster"))
+            || (self.strip_live && (suffix == "live" || suffix.starts_with("live ")))
+            || ((self.strip_featured || self.credit_featured) && featured_names(&suffix).is_some())
+            || (self.strip_edition
+                && (suffix.contains("edition")
+                    || suffix == "deluxe"
+                    || suffix == "expanded"
+                    || suffix.contains("anniversary")
+                    || suffix.contains("bonus track")))
+    }
+}
+
+/// Splits the last bracketed group or " - " segment off a title.
+fn split_suffix(title: &str) -> Option<(&str, String)> {
+    let trimmed = title.trim_end();
+    let close = trimmed.chars().last()?;
+    let open = match close {
+        ')' => Some('('),
+        ']' => Some('['),
+        _ => None,
+    };
+
+    if let Some(open) = open {
+        let start = trimmed.rfind(open)?;
+        if start == 0 {
+            return None;
+        }
+        let inner = &trimmed[start + 1..trimmed.len() - 1];
+        return Some((&trimmed[..start], inner.trim().to_string()));
+    }
+
+    trimmed
+        .rfind(" - ")
+        .filter(|position| *position > 0)
+        .map(|position| {
+            (
+                &trimmed[..position],
+                trimmed[position + 3..].trim().to_string(),
+            )
+        })
+}
+
+fn featured_names(text: &str) -> Option<&str> {
+    FEATURED_PREFIXES.iter().find_map(|prefix| {
+        text.get(..prefix.len())
+            .filter(|start| start.eq_ignore_ascii_case(prefix))
+            .map(|_| text[prefix.len()..].trim())
+    })
+}
+
+fn inline_featured_position(title: &str) -> Option<usize> {
+    let markers: Vec<String> = FEATURED_PREFIXES
+        .iter()
+        .map(|prefix| format!(" {prefix}"))
+        .collect();
+    find_ignore_case(title, &markers)
+        .map(|(position, _)| position)
+        .filter(|position| *position > 0)
+}
+
+fn split_names(names: &str) -> Vec<ArtistName> {
+    let mut split = vec![names.to_string()];
+    for separator in FEATURED_SEPARATORS {
+        split = split
+            .iter()
+            .flat_map(|name| name.split(separator))
+            .map(|name| name.trim().to_string())
+            .collect();
+    }
+
+    split
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .map(ArtistName)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::track_plays::{AlbumName, ArtistName, TrackName};
+
+    use super::TitleNormalizer;
+
+    fn track(title: &str) -> TrackName {
+        TrackName(title.to_string())
+    }
+
+    #[test]
+    fn strips_suffixes() {
+        let normalizer = TitleNormalizer::try_from("remaster, live, featured, edition").unwrap();
+
+        assert_eq!(
+            track("Song"),
+            normalizer.track_name(&track("Song - 2011 Remaster"))
+        );
+        assert_eq!(track("Song"), normalizer.track_name(&track("Song (Live)")));
+        assert_eq!(
+            track("Song"),
+            normalizer.track_name(&track("Song (feat. X) - Remastered 2009"))
+        );
+        assert_eq!(
+            track("Song"),
+            normalizer.track_name(&track("Song ft. X & Y"))
+        );
+        assert_eq!(
+            track("Live Forever"),
+            normalizer.track_name(&track("Live Forever"))
+        );
+        assert_eq!(
+            AlbumName("Album".to_string()),
+            normalizer.album_name(&AlbumName("Album (Deluxe Edition)".to_string()))
+        );
+        assert_eq!(
+            track("Song - Interlude"),
+            normalizer.track_name(&track("Song - Interlude"))
+        );
+    }
+
+    #[test]
+    fn folds_unicode_and_credits_featured_artists() {
+        let normalizer = TitleNormalizer::try_from("unicode, credits").unwrap();
+
+        assert_eq!(
+            track("Don't"),
+            normalizer.track_name(&track("Don\u{2019}t"))
+        );
+        assert_eq!(
+            vec![
+                ArtistName("X".to_string()),
+                ArtistName("Y".to_string()),
+                ArtistName("Z".to_string())
+            ],
+            normalizer.featured_artists(&track("Song (feat. X, Y & Z)"))
+        );
+        assert_eq!(
+            track("İstanbul"),
+            normalizer.track_name(&track("İstanbul Feat. X"))
+        );
+        assert_eq!(
+            vec![ArtistName("X".to_string())],
+            normalizer.featured_artists(&track("İstanbul Feat. X"))
+        );
+        assert!(TitleNormalizer::try_from("karaoke").is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{
+    errors::ReadError,
+    processing::events::Event,
+    projections::statistics::{Listen, ListenRules},
+    track_plays::{AlbumName, TrackName},
+};
+
+#[async_trait::async_trait]
+pub trait ListensRepository {
+    async fn project_events(&mut self, events: &[Event], rules: &ListenRules);
+    async fn sync(&mut self, events: &[Event], rules: &ListenRules);
+    async fn listens(&self) -> Result<Vec<Listen>, ReadError>;
+    async fn album_listens(&self, album_name: &AlbumName) -> Result<Vec<Listen>, ReadError>;
+    async fn track_listens(&self, track_name: &TrackName) -> Result<Vec<Listen>, ReadError>;
+}
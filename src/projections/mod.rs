@@ -1,8 +1,10 @@
 mod has_listen;
 mod listen_tracker;
+mod listens_repository;
 mod repository;
 pub mod statistics;
 
 pub use has_listen::HasListen;
 pub use listen_tracker::{build_id, ListenTracker};
+pub use listens_repository::ListensRepository;
 pub use repository::ListenTrackerRepository;
@@ -2,12 +2,15 @@ use std::{cmp::Reverse, collections::HashMap};
 
 use serde::{Deserialize, Serialize};
 
-use crate::track_plays::{AlbumName, ArtistName, TrackName};
+use crate::{
+    processing::CreditRole,
+    track_plays::{AlbumName, ArtistName, TrackName},
+};
 
 use super::{
-    count::ArtistAndAlbumCount,
+    count::{AlbumCount, ArtistAndAlbumCount, ArtistAndSongSkipRate},
     counter::{AlbumCounter, ArtistSongCounter, SongCounter},
-    ArtistAndSongCount, General, TimePlayed,
+    ArtistAndSongCount, General, Metric, TimePlayed,
 };
 
 #[derive(Clone, Default, Deserialize, Serialize)]
@@ -16,6 +19,10 @@ pub struct ArtistsCounts {
     artist_album_counters: HashMap<ArtistName, AlbumCounter>,
     time_played: TimePlayed,
     skipped_artists: HashMap<ArtistName, SongCounter>,
+    #[serde(default)]
+    featured_artists: HashMap<ArtistName, SongCounter>,
+    #[serde(default)]
+    credited_songs: HashMap<ArtistName, SongCounter>,
 }
 
 impl ArtistsCounts {
@@ -32,12 +39,49 @@ impl ArtistsCounts {
                 .add(counter);
         }
 
-        // TODO: Add skipped song merging too
+        for (artist_name, counter) in other.artist_album_counters.iter() {
+            self.artist_album_counters
+                .entry(artist_name.clone())
+                .or_default()
+                .add(counter);
+        }
+
+        for (artist_name, counter) in other.skipped_artists.iter() {
+            self.skipped_artists
+                .entry(artist_name.clone())
+                .or_default()
+                .add(counter);
+        }
+
+        for (artist_name, counter) in other.credited_songs.iter() {
+            self.credited_songs
+                .entry(artist_name.clone())
+                .or_default()
+                .add(counter);
+        }
+
+        for (artist_name, counter) in other.featured_artists.iter() {
+            self.featured_artists
+                .entry(artist_name.clone())
+                .or_default()
+                .add(counter);
+        }
     }
 
+    /// Without an artist this is the number of listens, so plays credited to more than
+    /// one artist are only counted once.
     pub fn total_count(&self, artist_name: &Option<String>) -> u64 {
         let built_name = artist_name.as_ref().map(|n| ArtistName(n.clone()));
-        self.artist_song_counters
+        let credited_plays = if built_name.is_none() {
+            self.credited_songs
+                .values()
+                .map(|song_counter| song_counter.total_song_plays())
+                .sum()
+        } else {
+            0
+        };
+        let plays: u64 = self
+            .artist_song_counters
             .iter()
             .filter_map(|(artist, song_counter)| {
                 if let Some(name) = &built_name {
@@ -50,20 +94,116 @@ impl ArtistsCounts {
                     Some(song_counter.total_song_plays())
                 }
             })
+            .sum();
+
+        plays - credited_plays
+    }
+
+    /// Plays of a song when a track is given, otherwise the same as `total_count`. Like
+    /// `total_count`, a play credited to more than one artist is only counted once when
+    /// no artist is given.
+    pub fn play_count(&self, artist_name: &Option<String>, track_name: &Option<String>) -> u64 {
+        let Some(track_name) = track_name else {
+            return self.total_count(artist_name);
+        };
+
+        let built_track = TrackName(track_name.clone());
+        let credited_plays: u64 = if artist_name.is_none() {
+            self.credited_songs
+                .values()
+                .map(|song_counter| song_counter.song_plays(&built_track))
+                .sum()
+        } else {
+            0
+        };
+        let plays: u64 = self
+            .artist_song_counters
+            .iter()
+            .filter(|(artist, _)| {
+                artist_name
+                    .as_ref()
+                    .map(|name| artist.0 == *name)
+                    .unwrap_or(true)
+            })
+            .map(|(_, song_counter)| song_counter.song_plays(&built_track))
+            .sum();
+
+        plays - credited_plays
+    }
+
+    pub fn total_skip_count(&self) -> u64 {
+        self.skipped_artists
+            .values()
+            .map(|song_counter| song_counter.total_song_plays())
             .sum()
     }
 
+    /// Prefers the exact spelling, and only falls back to ignoring case when the name
+    /// hasn't been listened to as written.
     pub fn find_artist(&self, name: &ArtistName) -> Option<ArtistSongCounter> {
-        self.artist_song_counters.iter().find_map(|(n, v)| {
-            if name.0.to_lowercase() == n.0.to_lowercase() {
-                Some(ArtistSongCounter {
-                    artist_name: n.clone(),
-                    play_details: v.clone(),
-                })
-            } else {
-                None
+        self.artist_song_counters
+            .get_key_value(name)
+            .or_else(|| {
+                self.artist_song_counters
+                    .iter()
+                    .find(|(n, _)| name.0.to_lowercase() == n.0.to_lowercase())
+            })
+            .map(|(n, v)| ArtistSongCounter {
+                artist_name: n.clone(),
+                play_details: v.clone(),
+                featured_plays: 0,
+            })
+    }
+
+    pub fn find_featured_artist(&self, name: &ArtistName) -> Option<ArtistSongCounter> {
+        self.featured_artists
+            .iter()
+            .find(|(n, _)| name.0.to_lowercase() == n.0.to_lowercase())
+            .map(|(n, v)| ArtistSongCounter {
+                artist_name: n.clone(),
+                play_details: v.clone(),
+                featured_plays: 0,
+            })
+    }
+
+    /// Credits a play to another artist on the track, without counting the listen or its
+    /// time again in the totals.
+    pub fn add_credited_play(
+        &mut self,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+        time_played: u64,
+        role: &CreditRole,
+    ) {
+        let counters = match role {
+            CreditRole::Primary => {
+                self.credited_songs
+                    .entry(artist_name.clone())
+                    .or_default()
+                    .increment_song(track_name, time_played);
+                &mut self.artist_song_counters
             }
-        })
+            CreditRole::Featured => &mut self.featured_artists,
+        };
+
+        counters
+            .entry(artist_name.clone())
+            .or_default()
+            .increment_song(track_name, time_played);
+    }
+
+    pub fn artist_albums(&self, artist_name: &ArtistName) -> Vec<AlbumCount> {
+        self.artist_album_counters
+            .get(artist_name)
+            .map(|album_counter| album_counter.all_album_plays())
+            .unwrap_or_default()
+    }
+
+    pub fn artist_skip_count(&self, artist_name: &ArtistName) -> u64 {
+        self.skipped_artists
+            .get(artist_name)
+            .map(|song_counter| song_counter.total_song_plays())
+            .unwrap_or_default()
     }
 
     pub fn add_song_skip(&mut self, artist_name: &ArtistName, song_name: &TrackName) {
@@ -71,13 +211,18 @@ impl ArtistsCounts {
         artist_counts.increment_song(song_name, 0);
     }
 
-    pub fn add_album_play(&mut self, artist_name: &ArtistName, album_name: &AlbumName) {
+    pub fn add_album_play(
+        &mut self,
+        artist_name: &ArtistName,
+        album_name: &AlbumName,
+        time_played: u64,
+    ) {
         let album_counter = self
             .artist_album_counters
             .entry(artist_name.clone())
             .or_default();
 
-        album_counter.increment_album(album_name);
+        album_counter.increment_album(album_name, time_played);
     }
 
     pub fn add_song_play(
@@ -105,6 +250,7 @@ impl ArtistsCounts {
                     Some(ArtistSongCounter {
                         artist_name: name.clone(),
                         play_details: counter.clone(),
+                        featured_plays: 0,
                     })
                 }
             })
@@ -118,26 +264,45 @@ impl ArtistsCounts {
             .map(|(name, counter)| ArtistSongCounter {
                 artist_name: name.clone(),
                 play_details: counter.clone(),
+                featured_plays: 0,
             })
             .collect();
         counts.sort_by_key(|play| Reverse(play.total_song_plays()));
         counts.into_iter().collect()
     }
 
-    pub fn top(&self, count: usize) -> Vec<ArtistSongCounter> {
-        let mut counts: Vec<ArtistSongCounter> = self
+    /// Featured plays count towards an artist's rank, and are shown separately.
+    pub fn top(&self, count: usize, metric: &Metric) -> Vec<ArtistSongCounter> {
+        let mut counters: HashMap<&ArtistName, ArtistSongCounter> = self
             .artist_song_counters
             .iter()
-            .map(|(name, counter)| ArtistSongCounter {
-                artist_name: name.clone(),
-                play_details: counter.clone(),
+            .map(|(name, counter)| {
+                (
+                    name,
+                    ArtistSongCounter {
+                        artist_name: name.clone(),
+                        play_details: counter.clone(),
+                        featured_plays: 0,
+                    },
+                )
             })
             .collect();
-        counts.sort_by_key(|play| Reverse(play.total_song_plays()));
+        for (name, featured) in self.featured_artists.iter() {
+            let counter = counters.entry(name).or_insert_with(|| ArtistSongCounter {
+                artist_name: name.clone(),
+                play_details: SongCounter::default(),
+                featured_plays: 0,
+            });
+            counter.play_details.add(featured);
+            counter.featured_plays += featured.total_song_plays();
+        }
+
+        let mut counts: Vec<ArtistSongCounter> = counters.into_values().collect();
+        counts.sort_by_key(|play| Reverse(play.metric_value(metric)));
         counts.into_iter().take(count).collect()
     }
 
-    pub fn top_albums(&self, count: usize) -> Vec<ArtistAndAlbumCount> {
+    pub fn top_albums(&self, count: usize, metric: &Metric) -> Vec<ArtistAndAlbumCount> {
         let mut counts: Vec<ArtistAndAlbumCount> = self
             .artist_album_counters
             .clone()
@@ -153,21 +318,21 @@ impl ArtistsCounts {
                     .collect::<Vec<_>>()
             })
             .collect();
-        counts.sort_by_key(|song_play_count| Reverse(song_play_count.album_count.1));
+        counts.sort_by_key(|album_play_count| Reverse(album_play_count.metric_value(metric)));
         counts.into_iter().take(count).collect()
     }
 
     pub fn general_stats(&self, count: usize) -> General {
         let artist_total_plays: Vec<String> = self
-            .top(count)
+            .top(count, &Metric::Plays)
             .iter()
             .map(ArtistSongCounter::total_plays_display)
             .collect();
 
         let most_played_songs: Vec<String> = self
-            .top_songs(count)
+            .top_songs(count, &Metric::Plays)
             .iter()
-            .map(|artist_count| format!("{}", &artist_count))
+            .map(|artist_count| format!("{}", artist_count))
             .collect();
 
         let unique_artists_most_played_songs: Vec<String> = self
@@ -184,6 +349,10 @@ impl ArtistsCounts {
         }
     }
 
+    pub fn artist_names(&self) -> impl Iterator<Item = &ArtistName> {
+        self.artist_song_counters.keys()
+    }
+
     pub fn artist_count(&self) -> usize {
         self.artist_song_counters.len()
     }
@@ -195,6 +364,7 @@ impl ArtistsCounts {
             .map(|(name, counter)| ArtistSongCounter {
                 artist_name: name.clone(),
                 play_details: counter.clone(),
+                featured_plays: 0,
             })
             .collect();
 
@@ -202,31 +372,45 @@ impl ArtistsCounts {
         counts.into_iter().take(count).collect()
     }
 
-    pub fn top_songs(&self, count: usize) -> Vec<ArtistAndSongCount> {
+    /// A song credited to more than one artist is listed once, under the artist its
+    /// plays are counted under.
+    pub fn top_songs(&self, count: usize, metric: &Metric) -> Vec<ArtistAndSongCount> {
         let mut counts: Vec<ArtistAndSongCount> = self
             .artist_song_counters
             .clone()
             .into_iter()
             .flat_map(|(artist_name, play_count)| {
+                let credited = self.credited_songs.get(&artist_name);
                 play_count
                     .all_song_plays()
-                    .iter()
-                    .map(|song_count| ArtistAndSongCount {
-                        artist_name: artist_name.clone(),
-                        song_count: song_count.clone(),
+                    .into_iter()
+                    .filter_map(|song_count| {
+                        let song_count = match credited {
+                            Some(credited) => credited.subtract_from(song_count),
+                            None => song_count,
+                        };
+                        (song_count.1 > 0).then(|| ArtistAndSongCount {
+                            artist_name: artist_name.clone(),
+                            song_count,
+                        })
                     })
                     .collect::<Vec<_>>()
             })
             .collect();
-        counts.sort_by_key(|song_play_count| Reverse(song_play_count.song_count.1));
+        counts.sort_by_key(|song_play_count| Reverse(song_play_count.metric_value(metric)));
         counts.into_iter().take(count).collect()
     }
 
-    pub fn top_skipped_songs(&self, count: usize) -> Vec<ArtistAndSongCount> {
+    pub fn top_skipped_songs(
+        &self,
+        count: usize,
+        artist_name: &Option<String>,
+    ) -> Vec<ArtistAndSongCount> {
         let mut counts: Vec<ArtistAndSongCount> = self
             .skipped_artists
             .clone()
             .into_iter()
+            .filter(|(name, _)| matches_artist(name, artist_name))
             .flat_map(|(artist_name, play_count)| {
                 play_count
                     .all_song_plays()
@@ -241,4 +425,202 @@ impl ArtistsCounts {
         counts.sort_by_key(|song_play_count| Reverse(song_play_count.song_count.1));
         counts.into_iter().take(count).collect()
     }
+
+    /// Songs by how often they're skipped, leaving out ones played fewer than `min_plays`
+    /// times.
+    pub fn top_skip_rates(&self, count: usize, min_plays: u64) -> Vec<ArtistAndSongSkipRate> {
+        let mut rates: Vec<ArtistAndSongSkipRate> = self
+            .skipped_artists
+            .iter()
+            .flat_map(|(artist_name, skip_counter)| {
+                let play_counter = self.artist_song_counters.get(artist_name);
+                skip_counter
+                    .all_song_plays()
+                    .into_iter()
+                    .map(|skip_count| ArtistAndSongSkipRate {
+                        artist_name: artist_name.clone(),
+                        plays: play_counter
+                            .map(|counter| counter.song_plays(&skip_count.0))
+                            .unwrap_or_default(),
+                        track_name: skip_count.0,
+                        skips: skip_count.1,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|rate| rate.plays >= min_plays)
+            .collect();
+        rates.sort_by(|a, b| {
+            b.skip_rate()
+                .total_cmp(&a.skip_rate())
+                .then_with(|| b.skips.cmp(&a.skips))
+        });
+        rates.into_iter().take(count).collect()
+    }
+}
+
+fn matches_artist(name: &ArtistName, filter: &Option<String>) -> bool {
+    filter
+        .as_ref()
+        .map(|filter_name| name.0.to_lowercase() == filter_name.to_lowercase())
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::track_plays::{ArtistName, TrackName};
+
+    use crate::{processing::CreditRole, projections::statistics::Metric};
+
+    use super::ArtistsCounts;
+
+    #[test]
+    fn add_merges_skipped_songs() {
+        let artist_name = ArtistName("Ulcerate".to_string());
+        let track_name = TrackName("Stare Into Death and Be Still".to_string());
+
+        let mut first = ArtistsCounts::default();
+        first.add_song_skip(&artist_name, &track_name);
+        let mut second = ArtistsCounts::default();
+        second.add_song_skip(&artist_name, &track_name);
+
+        first.add(&second);
+
+        let skipped = first.top_skipped_songs(5, &None);
+        assert_eq!(1, skipped.len());
+        assert_eq!(2, skipped.first().unwrap().song_count.1);
+    }
+
+    #[test]
+    fn top_by_time_played() {
+        let short_artist = ArtistName("Full of Hell".to_string());
+        let long_artist = ArtistName("Bell Witch".to_string());
+
+        let mut counts = ArtistsCounts::default();
+        for _ in 0..5 {
+            counts.add_song_play(
+                &short_artist,
+                &TrackName("Gnawed Flesh".to_string()),
+                60_000,
+            );
+        }
+        counts.add_song_play(
+            &long_artist,
+            &TrackName("Mirror Reaper".to_string()),
+            83 * 60_000,
+        );
+
+        let by_plays = counts.top(1, &Metric::Plays);
+        assert_eq!(short_artist, by_plays.first().unwrap().artist_name);
+
+        let by_time = counts.top(1, &Metric::Time);
+        assert_eq!(long_artist, by_time.first().unwrap().artist_name);
+
+        let songs_by_time = counts.top_songs(1, &Metric::Time);
+        assert_eq!(
+            "Mirror Reaper",
+            songs_by_time.first().unwrap().song_count.0 .0
+        );
+    }
+
+    #[test]
+    fn add_merges_song_counts() {
+        let artist_name = ArtistName("Ulcerate".to_string());
+        let track_name = TrackName("Visceral Ends".to_string());
+
+        let mut first = ArtistsCounts::default();
+        first.add_song_play(&artist_name, &track_name, 1000);
+        let mut second = ArtistsCounts::default();
+        second.add_song_play(&artist_name, &track_name, 2000);
+        second.add_song_play(&artist_name, &track_name, 3000);
+
+        first.add(&second);
+
+        let songs = first.top_songs(5, &Metric::Plays);
+        assert_eq!(1, songs.len());
+        assert_eq!(3, songs.first().unwrap().song_count.1);
+        assert_eq!(6000, songs.first().unwrap().song_count.2);
+        assert_eq!(3, first.total_count(&None));
+        assert_eq!(6000, first.time_played().time_ms);
+    }
+
+    #[test]
+    fn credited_plays_rank_without_counting_twice() {
+        let primary = ArtistName("Ulcerate".to_string());
+        let credited = ArtistName("Blut Aus Nord".to_string());
+        let featured = ArtistName("Mgła".to_string());
+        let track_name = TrackName("Split".to_string());
+
+        let mut counts = ArtistsCounts::default();
+        counts.add_song_play(&primary, &track_name, 1000);
+        counts.add_credited_play(&credited, &track_name, 1000, &CreditRole::Primary);
+        counts.add_credited_play(&featured, &track_name, 1000, &CreditRole::Featured);
+
+        assert_eq!(1, counts.total_count(&None));
+        assert_eq!(1000, counts.time_played().time_ms);
+        assert_eq!(1, counts.total_count(&Some("Blut Aus Nord".to_string())));
+
+        let top = counts.top(5, &Metric::Plays);
+        assert_eq!(3, top.len());
+        let featured_counter = top
+            .iter()
+            .find(|counter| counter.artist_name == featured)
+            .unwrap();
+        assert_eq!(1, featured_counter.featured_plays);
+    }
+
+    #[test]
+    fn credited_songs_are_counted_once() {
+        let primary = ArtistName("Ulcerate".to_string());
+        let credited = ArtistName("Blut Aus Nord".to_string());
+        let split = TrackName("Split".to_string());
+        let solo = TrackName("Memoria Vetusta".to_string());
+
+        let mut counts = ArtistsCounts::default();
+        for _ in 0..2 {
+            counts.add_song_play(&primary, &split, 1000);
+            counts.add_credited_play(&credited, &split, 1000, &CreditRole::Primary);
+        }
+        counts.add_song_play(&credited, &solo, 1000);
+
+        let split_name = Some("Split".to_string());
+        assert_eq!(2, counts.play_count(&None, &split_name));
+        assert_eq!(
+            2,
+            counts.play_count(&Some("Blut Aus Nord".to_string()), &split_name)
+        );
+
+        let songs = counts.top_songs(5, &Metric::Plays);
+        assert_eq!(2, songs.len());
+        assert_eq!(primary, songs.first().unwrap().artist_name);
+        assert_eq!(2, songs.first().unwrap().song_count.1);
+        assert_eq!(credited, songs.last().unwrap().artist_name);
+        assert_eq!(solo, songs.last().unwrap().song_count.0);
+    }
+
+    #[test]
+    fn top_skip_rates() {
+        let artist_name = ArtistName("Ulcerate".to_string());
+        let liked = TrackName("Visceral Ends".to_string());
+        let disliked = TrackName("Drawn Into the Next Void".to_string());
+
+        let mut counts = ArtistsCounts::default();
+        for _ in 0..8 {
+            counts.add_song_play(&artist_name, &liked, 1000);
+        }
+        counts.add_song_skip(&artist_name, &liked);
+        counts.add_song_play(&artist_name, &disliked, 1000);
+        counts.add_song_skip(&artist_name, &disliked);
+        counts.add_song_skip(&artist_name, &disliked);
+
+        let rates = counts.top_skip_rates(5, 1);
+        assert_eq!(2, rates.len());
+        assert_eq!(disliked, rates.first().unwrap().track_name);
+        assert_eq!(1, rates.first().unwrap().plays);
+        assert_eq!(2, rates.first().unwrap().skips);
+
+        // Skips don't count towards the minimum, so three listens of one play isn't enough.
+        let rates = counts.top_skip_rates(5, 2);
+        assert_eq!(1, rates.len());
+        assert_eq!(liked, rates.first().unwrap().track_name);
+    }
 }
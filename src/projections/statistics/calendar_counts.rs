@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use chrono::{Datelike, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
 
-use crate::track_plays::{AlbumName, ArtistName, TrackName};
+use crate::{
+    processing::CreditRole,
+    track_plays::{AlbumName, ArtistName, TrackName},
+};
 
 use super::{artists_counts::ArtistsCounts, counter::ArtistSongCounter};
 
@@ -41,14 +44,16 @@ impl YearCounts {
         date: &NaiveDate,
         artist_name: &ArtistName,
         album_name: &AlbumName,
+        time_played: u64,
     ) {
         let month_count = self
             .months
             .entry(date.month())
             .or_insert_with(|| MonthCounts::from(date));
 
-        month_count.add_album_play(date, artist_name, album_name);
-        self.artists_counts.add_album_play(artist_name, album_name);
+        month_count.add_album_play(date, artist_name, album_name, time_played);
+        self.artists_counts
+            .add_album_play(artist_name, album_name, time_played);
     }
 
     pub fn add_song_play(
@@ -68,6 +73,39 @@ impl YearCounts {
             .add_song_play(artist_name, track_name, time_played);
     }
 
+    pub fn add_song_skip(
+        &mut self,
+        date: &NaiveDate,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+    ) {
+        let month_count = self
+            .months
+            .entry(date.month())
+            .or_insert_with(|| MonthCounts::from(date));
+
+        month_count.add_song_skip(date, artist_name, track_name);
+        self.artists_counts.add_song_skip(artist_name, track_name);
+    }
+
+    pub fn add_credited_play(
+        &mut self,
+        date: &NaiveDate,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+        time_played: u64,
+        role: &CreditRole,
+    ) {
+        let month_count = self
+            .months
+            .entry(date.month())
+            .or_insert_with(|| MonthCounts::from(date));
+
+        month_count.add_credited_play(date, artist_name, track_name, time_played, role);
+        self.artists_counts
+            .add_credited_play(artist_name, track_name, time_played, role);
+    }
+
     pub fn over_min_plays(&self, min: u64) -> Vec<ArtistSongCounter> {
         self.artists_counts.over_min_plays(min)
     }
@@ -139,14 +177,16 @@ impl MonthCounts {
         date: &NaiveDate,
         artist_name: &ArtistName,
         album_name: &AlbumName,
+        time_played: u64,
     ) {
         let day_counts = self
             .days
             .entry(date.day())
             .or_insert_with(|| DayCounts::from(date));
 
-        day_counts.add_album_play(artist_name, album_name);
-        self.artists_counts.add_album_play(artist_name, album_name);
+        day_counts.add_album_play(artist_name, album_name, time_played);
+        self.artists_counts
+            .add_album_play(artist_name, album_name, time_played);
     }
 
     pub fn add_song_play(
@@ -166,6 +206,39 @@ impl MonthCounts {
             .add_song_play(artist_name, track_name, time_played);
     }
 
+    pub fn add_song_skip(
+        &mut self,
+        date: &NaiveDate,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+    ) {
+        let day_counts = self
+            .days
+            .entry(date.day())
+            .or_insert_with(|| DayCounts::from(date));
+
+        day_counts.add_song_skip(artist_name, track_name);
+        self.artists_counts.add_song_skip(artist_name, track_name);
+    }
+
+    pub fn add_credited_play(
+        &mut self,
+        date: &NaiveDate,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+        time_played: u64,
+        role: &CreditRole,
+    ) {
+        let day_counts = self
+            .days
+            .entry(date.day())
+            .or_insert_with(|| DayCounts::from(date));
+
+        day_counts.add_credited_play(artist_name, track_name, time_played, role);
+        self.artists_counts
+            .add_credited_play(artist_name, track_name, time_played, role);
+    }
+
     pub fn over_min_plays(&self, min: u64) -> Vec<ArtistSongCounter> {
         self.artists_counts.over_min_plays(min)
     }
@@ -196,8 +269,14 @@ impl From<&NaiveDate> for DayCounts {
 }
 
 impl DayCounts {
-    pub fn add_album_play(&mut self, artist_name: &ArtistName, album_name: &AlbumName) {
-        self.artists_counts.add_album_play(artist_name, album_name);
+    pub fn add_album_play(
+        &mut self,
+        artist_name: &ArtistName,
+        album_name: &AlbumName,
+        time_played: u64,
+    ) {
+        self.artists_counts
+            .add_album_play(artist_name, album_name, time_played);
     }
 
     pub fn add_song_play(
@@ -210,6 +289,21 @@ impl DayCounts {
             .add_song_play(artist_name, track_name, time_played);
     }
 
+    pub fn add_song_skip(&mut self, artist_name: &ArtistName, track_name: &TrackName) {
+        self.artists_counts.add_song_skip(artist_name, track_name);
+    }
+
+    pub fn add_credited_play(
+        &mut self,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+        time_played: u64,
+        role: &CreditRole,
+    ) {
+        self.artists_counts
+            .add_credited_play(artist_name, track_name, time_played, role);
+    }
+
     pub fn artists(&self) -> Vec<ArtistSongCounter> {
         self.artists_counts.all()
     }
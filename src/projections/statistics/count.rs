@@ -4,21 +4,23 @@ use serde::{Deserialize, Serialize};
 
 use crate::track_plays::{AlbumName, ArtistName, TrackName};
 
+use super::Metric;
+
 #[derive(Default, Deserialize, Serialize, Clone, Debug)]
-pub struct SongCount(pub TrackName, pub u64);
+pub struct SongCount(pub TrackName, pub u64, pub u64);
 
 impl Display for SongCount {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&format!("{} - {}", &self.0, self.1))
+        f.write_str(&format!("{} - {}", self.0, self.1))
     }
 }
 
 #[derive(Default, Deserialize, Serialize, Clone, Debug)]
-pub struct AlbumCount(pub AlbumName, pub u64);
+pub struct AlbumCount(pub AlbumName, pub u64, pub u64);
 
 impl Display for AlbumCount {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&format!("{} - {}", &self.0, self.1))
+        f.write_str(&format!("{} - {}", self.0, self.1))
     }
 }
 
@@ -27,11 +29,26 @@ pub struct ArtistAndSongCount {
     pub song_count: SongCount,
 }
 
+impl ArtistAndSongCount {
+    pub fn metric_value(&self, metric: &Metric) -> u64 {
+        metric.value(self.song_count.1, self.song_count.2)
+    }
+
+    pub fn metric_display(&self, metric: &Metric) -> String {
+        format!(
+            "{} - {} - {}",
+            self.artist_name,
+            self.song_count.0,
+            metric.display_value(self.song_count.1, self.song_count.2)
+        )
+    }
+}
+
 impl Display for ArtistAndSongCount {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&format!(
             "{} - {} - {}",
-            &self.artist_name, &self.song_count.0, self.song_count.1
+            self.artist_name, self.song_count.0, self.song_count.1
         ))
     }
 }
@@ -41,11 +58,60 @@ pub struct ArtistAndAlbumCount {
     pub album_count: AlbumCount,
 }
 
+impl ArtistAndAlbumCount {
+    pub fn metric_value(&self, metric: &Metric) -> u64 {
+        metric.value(self.album_count.1, self.album_count.2)
+    }
+
+    pub fn metric_display(&self, metric: &Metric) -> String {
+        format!(
+            "{} - {} - {}",
+            self.artist_name,
+            self.album_count.0,
+            metric.display_value(self.album_count.1, self.album_count.2)
+        )
+    }
+}
+
 impl Display for ArtistAndAlbumCount {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&format!(
             "{} - {} - {}",
-            &self.artist_name, &self.album_count.0, self.album_count.1
+            self.artist_name, self.album_count.0, self.album_count.1
+        ))
+    }
+}
+
+pub struct ArtistAndSongSkipRate {
+    pub artist_name: ArtistName,
+    pub track_name: TrackName,
+    pub plays: u64,
+    pub skips: u64,
+}
+
+impl ArtistAndSongSkipRate {
+    pub fn total_listens(&self) -> u64 {
+        self.plays + self.skips
+    }
+
+    pub fn skip_rate(&self) -> f64 {
+        if self.total_listens() == 0 {
+            0.0
+        } else {
+            self.skips as f64 / self.total_listens() as f64
+        }
+    }
+}
+
+impl Display for ArtistAndSongSkipRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "{} - {} - {:.1}% ({} skips, {} plays)",
+            self.artist_name,
+            self.track_name,
+            self.skip_rate() * 100.0,
+            self.skips,
+            self.plays
         ))
     }
 }
@@ -2,12 +2,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::track_plays::{AlbumName, ArtistName, TrackName};
 
-use super::{count::AlbumCount, SongCount, TimePlayed};
+use super::{count::AlbumCount, Metric, SongCount, TimePlayed};
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct ArtistSongCounter {
     pub artist_name: ArtistName,
     pub play_details: SongCounter,
+    #[serde(default)]
+    pub featured_plays: u64,
 }
 
 impl ArtistSongCounter {
@@ -16,13 +18,37 @@ impl ArtistSongCounter {
     }
 
     pub fn total_plays_display(&self) -> String {
-        format!("{} - {}", &self.artist_name, self.total_song_plays())
+        format!("{} - {}", self.artist_name, self.total_song_plays())
+    }
+
+    pub fn metric_value(&self, metric: &Metric) -> u64 {
+        metric.value(
+            self.total_song_plays(),
+            self.play_details.total_time_played().time_ms,
+        )
+    }
+
+    pub fn metric_display(&self, metric: &Metric) -> String {
+        let display = format!(
+            "{} - {}",
+            self.artist_name,
+            metric.display_value(
+                self.total_song_plays(),
+                self.play_details.total_time_played().time_ms
+            )
+        );
+
+        if self.featured_plays > 0 {
+            format!("{display} ({} plays featured)", self.featured_plays)
+        } else {
+            display
+        }
     }
 
     pub fn max_song_display(&self) -> String {
         format!(
             "{} - {}",
-            &self.artist_name,
+            self.artist_name,
             self.play_details.max_song_play()
         )
     }
@@ -32,12 +58,6 @@ impl ArtistSongCounter {
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
-pub struct ArtistAlbumCounter {
-    pub artist_name: ArtistName,
-    pub play_details: AlbumCounter,
-}
-
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct AlbumCounter {
     total_plays: u64,
@@ -45,15 +65,35 @@ pub struct AlbumCounter {
 }
 
 impl AlbumCounter {
-    pub fn increment_album(&mut self, album_name: &AlbumName) {
+    pub fn add(&mut self, other: &AlbumCounter) {
+        for other_count in other.album_counts.iter() {
+            match self
+                .album_counts
+                .iter_mut()
+                .find(|album_count| album_count.0.eq_ignore_ascii_case(&other_count.0))
+            {
+                Some(it) => {
+                    it.1 += other_count.1;
+                    it.2 += other_count.2;
+                }
+                _ => self.album_counts.push(other_count.clone()),
+            }
+        }
+        self.total_plays += other.total_plays;
+    }
+
+    pub fn increment_album(&mut self, album_name: &AlbumName, time_played: u64) {
         match self
             .album_counts
             .iter_mut()
             .find(|album_count| album_count.0.eq_ignore_ascii_case(album_name))
         {
-            Some(it) => it.1 += 1,
+            Some(it) => {
+                it.1 += 1;
+                it.2 += time_played;
+            }
             _ => {
-                let album_count = AlbumCount(album_name.clone(), 1);
+                let album_count = AlbumCount(album_name.clone(), 1, time_played);
                 self.album_counts.push(album_count);
             }
         }
@@ -74,32 +114,64 @@ pub struct SongCounter {
 
 impl SongCounter {
     pub fn add(&mut self, other: &SongCounter) {
+        for song_count in other.song_counts.iter() {
+            self.add_song_count(song_count);
+        }
         self.total_song_plays += other.total_song_plays;
         self.total_time_played
             .add_ms(other.total_time_played.time_ms);
-        for song_count in other.song_counts.iter() {
-            self.increment_song(&song_count.0, song_count.1);
-        }
     }
 
     pub fn total_song_plays(&self) -> u64 {
         self.total_song_plays
     }
 
+    pub fn total_time_played(&self) -> &TimePlayed {
+        &self.total_time_played
+    }
+
     pub fn increment_song(&mut self, track_name: &TrackName, time_played: u64) {
+        self.add_song_count(&SongCount(track_name.clone(), 1, time_played));
+        self.total_song_plays += 1;
+        self.total_time_played.add_ms(time_played);
+    }
+
+    fn add_song_count(&mut self, song_count: &SongCount) {
         match self
             .song_counts
             .iter_mut()
-            .find(|song_play_count| song_play_count.0.eq_ignore_ascii_case(track_name))
+            .find(|song_play_count| song_play_count.0.eq_ignore_ascii_case(&song_count.0))
         {
-            Some(it) => it.1 += 1,
-            _ => {
-                let song_count = SongCount(track_name.clone(), 1);
-                self.song_counts.push(song_count);
+            Some(it) => {
+                it.1 += song_count.1;
+                it.2 += song_count.2;
             }
+            _ => self.song_counts.push(song_count.clone()),
+        }
+    }
+
+    pub fn song_plays(&self, track_name: &TrackName) -> u64 {
+        self.song_counts
+            .iter()
+            .find(|song_count| song_count.0.eq_ignore_ascii_case(track_name))
+            .map(|song_count| song_count.1)
+            .unwrap_or_default()
+    }
+
+    /// The plays and time of the song left once these counts are taken off.
+    pub fn subtract_from(&self, song_count: SongCount) -> SongCount {
+        match self
+            .song_counts
+            .iter()
+            .find(|it| it.0.eq_ignore_ascii_case(&song_count.0))
+        {
+            Some(it) => SongCount(
+                song_count.0,
+                song_count.1.saturating_sub(it.1),
+                song_count.2.saturating_sub(it.2),
+            ),
+            None => song_count,
         }
-        self.total_song_plays += 1;
-        self.total_time_played.add_ms(time_played);
     }
 
     pub fn all_song_plays(&self) -> Vec<SongCount> {
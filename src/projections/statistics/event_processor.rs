@@ -1,27 +1,54 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    processing::events::{Event, EventData},
-    track_plays::ArtistName,
+    processing::{
+        events::{Event, EventData},
+        ArtistAliases, ArtistCredits, ClassificationPolicies, CreditRole, TitleNormalizer,
+    },
+    track_plays::{AlbumName, ArtistName, TrackName},
     utils::parse_formatted_end_time,
 };
 
 use super::{
-    calendar_counts::YearCounts, counter::ArtistSongCounter, ArtistAndSongCount, ArtistsCounts,
-    MonthCounts,
+    calendar_counts::{DayCounts, YearCounts},
+    counter::ArtistSongCounter,
+    ArtistAndSongCount, ArtistAndSongSkipRate, ArtistsCounts, ListenStreaks, ListenTimeline,
+    MonthCounts, Period,
 };
 
 #[derive(Default, Deserialize, Serialize)]
 pub struct EventProcessor {
     pub years: HashMap<i32, YearCounts>,
     pub artists_counts: ArtistsCounts,
-    pub artist_names: HashSet<ArtistName>,
+    pub listen_timeline: ListenTimeline,
+    pub classification: ClassificationPolicies,
+    #[serde(default)]
+    pub aliases: ArtistAliases,
+    #[serde(default)]
+    pub titles: TitleNormalizer,
+    #[serde(default)]
+    pub credits: ArtistCredits,
 }
 
 impl EventProcessor {
+    pub fn new(
+        classification: ClassificationPolicies,
+        aliases: ArtistAliases,
+        titles: TitleNormalizer,
+        credits: ArtistCredits,
+    ) -> Self {
+        Self {
+            classification,
+            aliases,
+            titles,
+            credits,
+            ..Default::default()
+        }
+    }
+
     pub fn artists_on_day(&self, date: NaiveDate) -> Vec<ArtistSongCounter> {
         self.years
             .get(&date.year())
@@ -49,53 +76,348 @@ impl EventProcessor {
         self.years.get(&year)
     }
 
+    /// Artist streaks count a day for every primary artist played that day, the same
+    /// artists the calendar counts hold.
+    pub fn listen_streaks(&self, year: Option<i32>, today: &NaiveDate) -> ListenStreaks {
+        let mut day_plays: Vec<(NaiveDate, u64)> = Vec::new();
+        let mut artist_days: HashMap<ArtistName, Vec<NaiveDate>> = HashMap::new();
+
+        for year_counts in self
+            .years
+            .values()
+            .filter(|year_counts| year.map(|y| y == year_counts.year).unwrap_or(true))
+        {
+            for month_counts in year_counts.month_counts() {
+                for day_counts in month_counts.day_counts() {
+                    let plays = day_counts.artists_counts.total_count(&None);
+                    let date = NaiveDate::from_ymd_opt(
+                        year_counts.year,
+                        month_counts.month,
+                        day_counts.day_of_month,
+                    );
+                    if let Some(date) = date.filter(|_| plays > 0) {
+                        day_plays.push((date, plays));
+                        for artist_name in day_counts.artists_counts.artist_names() {
+                            artist_days
+                                .entry(artist_name.clone())
+                                .or_default()
+                                .push(date);
+                        }
+                    }
+                }
+            }
+        }
+
+        ListenStreaks::build(&day_plays, artist_days, today)
+    }
+
     pub fn artist_song_counter(&self, artist_name: &ArtistName) -> Option<ArtistSongCounter> {
-        self.artists_counts.find_artist(artist_name)
+        self.artists_counts
+            .find_artist(&self.aliases.resolve(artist_name))
     }
 
+    /// Artist names are resolved through the aliases and titles normalised here, so every
+    /// projection counts the different spellings together without the events being changed.
     pub fn process_event(&mut self, event: &Event) {
         match &event.data {
             EventData::TrackPlayAdded(listen) => {
-                let calendar_day_result =
-                    parse_formatted_end_time(listen.end_time.as_str()).map(|e| e.date());
-
-                if let Ok(date) = calendar_day_result {
-                    let year_counts = self
-                        .years
-                        .entry(date.year())
-                        .or_insert_with(|| YearCounts::from(&date));
-                    year_counts.add_song_play(
-                        &date,
-                        &listen.artist_name,
-                        &listen.track_name,
-                        listen.ms_played,
-                    );
-
-                    if let Some(album_name) = &listen.album_name {
-                        year_counts.add_album_play(&date, &listen.artist_name, album_name);
-                    }
-                }
-
-                self.artists_counts.add_song_play(
+                let policy = self.classification.for_service(&listen.service_hint);
+                self.count_listen(
                     &listen.artist_name,
                     &listen.track_name,
+                    &listen.album_name,
                     listen.ms_played,
+                    &listen.end_time,
+                    listen.is_skipped_by(policy),
                 );
+            }
+            EventData::TrackPlayIgnored(ignored) => {
+                let policy = self.classification.for_service(&ignored.service_hint);
+                self.count_listen(
+                    &ignored.artist_name,
+                    &ignored.track_name,
+                    &ignored.album_name,
+                    ignored.ms_played,
+                    &ignored.end_time,
+                    ignored.is_skipped_by(policy),
+                );
+            }
+        };
+    }
+
+    fn count_listen(
+        &mut self,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+        album_name: &Option<AlbumName>,
+        ms_played: u64,
+        end_time: &str,
+        skipped: bool,
+    ) {
+        let credits = self.credits.split(artist_name);
+        let mut primary: Vec<ArtistName> = Vec::new();
+        for name in credits
+            .primary
+            .iter()
+            .map(|name| self.aliases.resolve(name))
+        {
+            if !primary.contains(&name) {
+                primary.push(name);
+            }
+        }
+        let artist_name = primary[0].clone();
+        let normalized_track = self.titles.track_name(track_name);
+        let album_name = album_name
+            .as_ref()
+            .map(|album_name| self.titles.album_name(album_name));
+
+        if skipped {
+            self.add_song_skip(&artist_name, &normalized_track, end_time);
+            return;
+        }
+
+        self.add_song_play(
+            &artist_name,
+            &normalized_track,
+            &album_name,
+            ms_played,
+            end_time,
+        );
+
+        // The other credited artists are counted separately, so the play isn't counted
+        // more than once in the totals.
+        let mut featured: Vec<ArtistName> = Vec::new();
+        for name in credits
+            .featured
+            .into_iter()
+            .chain(self.titles.featured_artists(track_name))
+            .map(|name| self.aliases.resolve(&name))
+        {
+            if !primary.contains(&name) && !featured.contains(&name) {
+                featured.push(name);
+            }
+        }
+
+        for name in &primary[1..] {
+            self.add_credited_play(
+                name,
+                &normalized_track,
+                ms_played,
+                end_time,
+                &CreditRole::Primary,
+            );
+            // Every primary artist is listened to, so discoveries and gaps count the
+            // play for each of them.
+            if let Ok(listened_at) = parse_formatted_end_time(end_time) {
+                self.listen_timeline
+                    .add_listen(name, &normalized_track, &album_name, listened_at);
+            }
+        }
+        for name in &featured {
+            self.add_credited_play(
+                name,
+                &normalized_track,
+                ms_played,
+                end_time,
+                &CreditRole::Featured,
+            );
+        }
+    }
+
+    fn add_credited_play(
+        &mut self,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+        ms_played: u64,
+        end_time: &str,
+        role: &CreditRole,
+    ) {
+        if let Ok(listened_at) = parse_formatted_end_time(end_time) {
+            let date = listened_at.date();
+            self.years
+                .entry(date.year())
+                .or_insert_with(|| YearCounts::from(&date))
+                .add_credited_play(&date, artist_name, track_name, ms_played, role);
+        }
+
+        self.artists_counts
+            .add_credited_play(artist_name, track_name, ms_played, role);
+    }
 
-                if let Some(album_name) = &listen.album_name {
-                    self.artists_counts
-                        .add_album_play(&listen.artist_name, album_name);
+    fn add_song_play(
+        &mut self,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+        album_name: &Option<AlbumName>,
+        ms_played: u64,
+        end_time: &str,
+    ) {
+        let end_time_result = parse_formatted_end_time(end_time);
+
+        if let Ok(listened_at) = end_time_result {
+            let date = listened_at.date();
+            let year_counts = self
+                .years
+                .entry(date.year())
+                .or_insert_with(|| YearCounts::from(&date));
+            year_counts.add_song_play(&date, artist_name, track_name, ms_played);
+
+            if let Some(album_name) = album_name {
+                year_counts.add_album_play(&date, artist_name, album_name, ms_played);
+            }
+
+            self.listen_timeline
+                .add_listen(artist_name, track_name, album_name, listened_at);
+        }
+
+        self.artists_counts
+            .add_song_play(artist_name, track_name, ms_played);
+
+        if let Some(album_name) = album_name {
+            self.artists_counts
+                .add_album_play(artist_name, album_name, ms_played);
+        }
+    }
+
+    fn add_song_skip(&mut self, artist_name: &ArtistName, track_name: &TrackName, end_time: &str) {
+        let calendar_day_result = parse_formatted_end_time(end_time).map(|e| e.date());
+
+        if let Ok(date) = calendar_day_result {
+            self.years
+                .entry(date.year())
+                .or_insert_with(|| YearCounts::from(&date))
+                .add_song_skip(&date, artist_name, track_name);
+        }
+
+        self.artists_counts.add_song_skip(artist_name, track_name);
+    }
+
+    pub fn artists_counts_for(
+        &self,
+        year: Option<i32>,
+        month: Option<u32>,
+    ) -> Option<ArtistsCounts> {
+        match (year, month) {
+            (None, None) => Some(self.artists_counts.clone()),
+            (None, Some(m)) => {
+                let month_counts = self.month_counts(m);
+                if month_counts.is_empty() {
+                    None
+                } else {
+                    Some(MonthCounts::merge_to_counts(month_counts))
                 }
-                self.artist_names.insert(listen.artist_name.to_owned());
             }
-            EventData::TrackPlayIgnored(ignored) => {
-                self.artists_counts
-                    .add_song_skip(&ignored.artist_name, &ignored.track_name);
+            (Some(y), None) => self
+                .year_count(y)
+                .map(|year_counts| year_counts.artists_counts.clone()),
+            (Some(y), Some(m)) => self
+                .year_count(y)
+                .and_then(|year_counts| year_counts.month_count(m))
+                .map(|month_counts| month_counts.artists_counts.clone()),
+        }
+    }
+
+    /// Merges the calendar counts within the period, using whole years and months
+    /// where the period covers them and falling back to single days otherwise.
+    pub fn artists_counts_in(&self, period: &Period) -> ArtistsCounts {
+        let mut counts = ArtistsCounts::default();
+
+        for year_counts in self
+            .years
+            .values()
+            .filter(|year_counts| period.overlaps_year(year_counts.year))
+        {
+            if period.covers_year(year_counts.year) {
+                counts.add(&year_counts.artists_counts);
+                continue;
             }
+
+            for month_counts in year_counts
+                .month_counts()
+                .into_iter()
+                .filter(|month_counts| period.overlaps_month(year_counts.year, month_counts.month))
+            {
+                if period.covers_month(year_counts.year, month_counts.month) {
+                    counts.add(&month_counts.artists_counts);
+                    continue;
+                }
+
+                for day_counts in month_counts.day_counts() {
+                    let in_period = NaiveDate::from_ymd_opt(
+                        year_counts.year,
+                        month_counts.month,
+                        day_counts.day_of_month,
+                    )
+                    .map(|date| period.contains(&date))
+                    .unwrap_or(false);
+                    if in_period {
+                        counts.add(&day_counts.artists_counts);
+                    }
+                }
+            }
+        }
+
+        counts
+    }
+
+    pub fn day_counts_in(&self, period: &Period) -> Vec<(NaiveDate, &DayCounts)> {
+        let mut day_counts: Vec<(NaiveDate, &DayCounts)> = Vec::new();
+
+        for year_counts in self
+            .years
+            .values()
+            .filter(|year_counts| period.overlaps_year(year_counts.year))
+        {
+            for month_counts in year_counts
+                .month_counts()
+                .into_iter()
+                .filter(|month_counts| period.overlaps_month(year_counts.year, month_counts.month))
+            {
+                for day_count in month_counts.day_counts() {
+                    if let Some(date) = NaiveDate::from_ymd_opt(
+                        year_counts.year,
+                        month_counts.month,
+                        day_count.day_of_month,
+                    )
+                    .filter(|date| period.contains(date))
+                    {
+                        day_counts.push((date, day_count));
+                    }
+                }
+            }
+        }
+
+        day_counts.sort_by_key(|(date, _)| *date);
+        day_counts
+    }
+
+    pub fn top_skipped(
+        &self,
+        count: usize,
+        year: Option<i32>,
+        month: Option<u32>,
+        period: &Option<Period>,
+        artist_name: &Option<String>,
+    ) -> Vec<ArtistAndSongCount> {
+        let counts = match period {
+            Some(period) => Some(self.artists_counts_in(period)),
+            None => self.artists_counts_for(year, month),
         };
+
+        counts
+            .map(|counts| counts.top_skipped_songs(count, artist_name))
+            .unwrap_or_default()
     }
 
-    pub fn top_skipped(&self, count: usize) -> Vec<ArtistAndSongCount> {
-        self.artists_counts.top_skipped_songs(count)
+    pub fn top_skip_rates(
+        &self,
+        count: usize,
+        min_plays: u64,
+        year: Option<i32>,
+        month: Option<u32>,
+    ) -> Vec<ArtistAndSongSkipRate> {
+        self.artists_counts_for(year, month)
+            .map(|counts| counts.top_skip_rates(count, min_plays))
+            .unwrap_or_default()
     }
 }
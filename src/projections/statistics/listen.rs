@@ -0,0 +1,88 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    processing::{
+        events::{Event, EventData},
+        ArtistAliases, ClassificationPolicies, TitleNormalizer,
+    },
+    track_plays::{AlbumName, ArtistName, TrackName},
+    utils::parse_formatted_end_time,
+};
+
+/// A single classified listen, kept so listens can be filtered individually rather
+/// than only through the calendar counts.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Listen {
+    pub artist_name: ArtistName,
+    pub track_name: TrackName,
+    pub album_name: Option<AlbumName>,
+    pub service: String,
+    pub listened_at: NaiveDateTime,
+    pub ms_played: u64,
+    pub skipped: bool,
+}
+
+/// Everything a listen is read with besides its event: how it's classified, the aliases
+/// its artist resolves through and how its titles are normalised.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ListenRules {
+    pub classification: ClassificationPolicies,
+    pub aliases: ArtistAliases,
+    pub titles: TitleNormalizer,
+}
+
+impl ListenRules {
+    pub fn listen(&self, event: &Event) -> Option<Listen> {
+        Listen::from_event(event, &self.classification)
+            .map(|listen| listen.resolved(&self.aliases, &self.titles))
+    }
+}
+
+impl Listen {
+    /// Builds the listen for an event under the current classification, `None` if the
+    /// end time can't be read.
+    pub fn from_event(event: &Event, classification: &ClassificationPolicies) -> Option<Listen> {
+        match &event.data {
+            EventData::TrackPlayAdded(added) => {
+                let policy = classification.for_service(&added.service_hint);
+                parse_formatted_end_time(&added.end_time)
+                    .ok()
+                    .map(|listened_at| Listen {
+                        artist_name: added.artist_name.clone(),
+                        track_name: added.track_name.clone(),
+                        album_name: added.album_name.clone(),
+                        service: added.service_hint.clone(),
+                        listened_at,
+                        ms_played: added.ms_played,
+                        skipped: added.is_skipped_by(policy),
+                    })
+            }
+            EventData::TrackPlayIgnored(ignored) => {
+                let policy = classification.for_service(&ignored.service_hint);
+                parse_formatted_end_time(&ignored.end_time)
+                    .ok()
+                    .map(|listened_at| Listen {
+                        artist_name: ignored.artist_name.clone(),
+                        track_name: ignored.track_name.clone(),
+                        album_name: ignored.album_name.clone(),
+                        service: ignored.service_hint.clone(),
+                        listened_at,
+                        ms_played: ignored.ms_played,
+                        skipped: ignored.is_skipped_by(policy),
+                    })
+            }
+        }
+    }
+
+    /// Resolves the artist through the aliases and normalises the titles, so different
+    /// spellings of the same thing read the same.
+    pub fn resolved(mut self, aliases: &ArtistAliases, titles: &TitleNormalizer) -> Listen {
+        self.artist_name = aliases.resolve(&self.artist_name);
+        self.track_name = titles.track_name(&self.track_name);
+        self.album_name = self
+            .album_name
+            .map(|album_name| titles.album_name(&album_name));
+        self
+    }
+}
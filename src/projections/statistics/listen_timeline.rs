@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    track_plays::{AlbumName, ArtistName, TrackName},
+    utils::months_between,
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct ListenSpan {
+    pub first: NaiveDateTime,
+    pub last: NaiveDateTime,
+}
+
+impl From<NaiveDateTime> for ListenSpan {
+    fn from(listened_at: NaiveDateTime) -> Self {
+        Self {
+            first: listened_at,
+            last: listened_at,
+        }
+    }
+}
+
+impl ListenSpan {
+    pub fn add(&mut self, listened_at: NaiveDateTime) {
+        self.first = self.first.min(listened_at);
+        self.last = self.last.max(listened_at);
+    }
+
+    pub fn first_in(&self, year: Option<i32>, month: Option<u32>) -> bool {
+        year.map(|y| self.first.year() == y).unwrap_or(true)
+            && month.map(|m| self.first.month() == m).unwrap_or(true)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArtistTimeline {
+    pub artist_name: ArtistName,
+    pub span: ListenSpan,
+    pub albums: HashMap<AlbumName, ListenSpan>,
+    pub tracks: HashMap<TrackName, ListenSpan>,
+}
+
+impl ArtistTimeline {
+    fn new(artist_name: &ArtistName, listened_at: NaiveDateTime) -> Self {
+        Self {
+            artist_name: artist_name.clone(),
+            span: ListenSpan::from(listened_at),
+            albums: HashMap::new(),
+            tracks: HashMap::new(),
+        }
+    }
+}
+
+/// First and last listens of each artist. Every primary artist of a credit is counted,
+/// featured artists are not. Artists are told apart ignoring case, like the song
+/// counters, and keep the spelling they were first listened to under.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ListenTimeline {
+    artists: HashMap<String, ArtistTimeline>,
+}
+
+impl ListenTimeline {
+    pub fn add_listen(
+        &mut self,
+        artist_name: &ArtistName,
+        track_name: &TrackName,
+        album_name: &Option<AlbumName>,
+        listened_at: NaiveDateTime,
+    ) {
+        let artist = self
+            .artists
+            .entry(artist_key(artist_name))
+            .and_modify(|timeline| timeline.span.add(listened_at))
+            .or_insert_with(|| ArtistTimeline::new(artist_name, listened_at));
+
+        artist
+            .tracks
+            .entry(track_name.clone())
+            .and_modify(|span| span.add(listened_at))
+            .or_insert_with(|| ListenSpan::from(listened_at));
+
+        if let Some(album_name) = album_name {
+            artist
+                .albums
+                .entry(album_name.clone())
+                .and_modify(|span| span.add(listened_at))
+                .or_insert_with(|| ListenSpan::from(listened_at));
+        }
+    }
+
+    pub fn artists(&self) -> impl Iterator<Item = &ArtistTimeline> {
+        self.artists.values()
+    }
+
+    pub fn artist_names(&self) -> impl Iterator<Item = &ArtistName> {
+        self.artists.values().map(|timeline| &timeline.artist_name)
+    }
+
+    pub fn artist_span(&self, artist_name: &ArtistName) -> Option<&ListenSpan> {
+        self.artists
+            .get(&artist_key(artist_name))
+            .map(|timeline| &timeline.span)
+    }
+
+    pub fn artist_spans(&self) -> Vec<(ArtistName, ListenSpan)> {
+        self.artists
+            .values()
+            .map(|timeline| (timeline.artist_name.clone(), timeline.span))
+            .collect()
+    }
+
+    pub fn discovered(
+        &self,
+        year: Option<i32>,
+        month: Option<u32>,
+    ) -> Vec<(ArtistName, ListenSpan)> {
+        let mut discovered: Vec<(ArtistName, ListenSpan)> = self
+            .artist_spans()
+            .into_iter()
+            .filter(|(_, span)| span.first_in(year, month))
+            .collect();
+        discovered.sort_by_key(|(_, span)| span.first);
+        discovered
+    }
+
+    /// How many artists were first listened to in each month, as year, month and count.
+    /// Every month of the year is included, or every month from the first discovery to
+    /// the last without a year, so months without any show as zero.
+    pub fn discoveries_per_month(&self, year: Option<i32>) -> Vec<(i32, u32, u64)> {
+        let mut counts: HashMap<(i32, u32), u64> = HashMap::new();
+        for (_, span) in self.discovered(year, None) {
+            *counts
+                .entry((span.first.year(), span.first.month()))
+                .or_default() += 1;
+        }
+
+        let (Some(first), Some(last)) = (counts.keys().min(), counts.keys().max()) else {
+            return Vec::new();
+        };
+        let (first, last) = match year {
+            Some(year) => ((year, 1), (year, 12)),
+            None => (*first, *last),
+        };
+
+        months_between(first, last)
+            .into_iter()
+            .map(|(y, m)| (y, m, counts.get(&(y, m)).copied().unwrap_or_default()))
+            .collect()
+    }
+
+    pub fn not_listened_since(&self, date: &NaiveDate) -> Vec<(ArtistName, ListenSpan)> {
+        self.artist_spans()
+            .into_iter()
+            .filter(|(_, span)| span.last.date() < *date)
+            .collect()
+    }
+}
+
+fn artist_key(artist_name: &ArtistName) -> String {
+    artist_name.0.to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::track_plays::{ArtistName, TrackName};
+
+    use super::ListenTimeline;
+
+    #[test]
+    fn tracks_first_and_last_listens() {
+        let artist_name = ArtistName("Blut Aus Nord".to_string());
+        let track_name = TrackName("Epitome XIV".to_string());
+        let first = NaiveDate::from_ymd_opt(2021, 3, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let last = NaiveDate::from_ymd_opt(2023, 5, 9)
+            .unwrap()
+            .and_hms_opt(8, 30, 0)
+            .unwrap();
+
+        let mut timeline = ListenTimeline::default();
+        timeline.add_listen(&artist_name, &track_name, &None, last);
+        timeline.add_listen(&artist_name, &track_name, &None, first);
+
+        let span = timeline.artist_span(&artist_name).unwrap();
+        assert_eq!(first, span.first);
+        assert_eq!(last, span.last);
+
+        assert_eq!(1, timeline.discovered(Some(2021), None).len());
+        assert!(timeline.discovered(Some(2023), None).is_empty());
+        assert!(timeline
+            .not_listened_since(&NaiveDate::from_ymd_opt(2023, 1, 1).unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn counts_discoveries_per_month_with_empty_months() {
+        let listen = |y, m| {
+            NaiveDate::from_ymd_opt(y, m, 10)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+        };
+        let track_name = TrackName("Track".to_string());
+        let mut timeline = ListenTimeline::default();
+        timeline.add_listen(
+            &ArtistName("Mgła".to_string()),
+            &track_name,
+            &None,
+            listen(2021, 11),
+        );
+        timeline.add_listen(
+            &ArtistName("Ulcerate".to_string()),
+            &track_name,
+            &None,
+            listen(2022, 2),
+        );
+        timeline.add_listen(
+            &ArtistName("Gorguts".to_string()),
+            &track_name,
+            &None,
+            listen(2022, 2),
+        );
+        timeline.add_listen(
+            &ArtistName("GORGUTS".to_string()),
+            &track_name,
+            &None,
+            listen(2022, 2),
+        );
+
+        assert_eq!(
+            vec![(2021, 11, 1), (2021, 12, 0), (2022, 1, 0), (2022, 2, 2)],
+            timeline.discoveries_per_month(None)
+        );
+
+        let months = timeline.discoveries_per_month(Some(2022));
+        assert_eq!(12, months.len());
+        assert_eq!((2022, 1, 0), months[0]);
+        assert_eq!((2022, 2, 2), months[1]);
+        assert!(timeline.discoveries_per_month(Some(2020)).is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub enum Metric {
+    #[default]
+    Plays,
+    Time,
+}
+
+impl Metric {
+    pub fn value(&self, plays: u64, time_ms: u64) -> u64 {
+        match self {
+            Metric::Plays => plays,
+            Metric::Time => time_ms,
+        }
+    }
+
+    pub fn display_value(&self, plays: u64, time_ms: u64) -> String {
+        match self {
+            Metric::Plays => format!("{plays}"),
+            Metric::Time => format!("{:.1} min", time_ms as f64 / 60_000.0),
+        }
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match *self {
+            Metric::Plays => "plays",
+            Metric::Time => "minutes",
+        };
+
+        f.write_str(value)
+    }
+}
+
+impl TryFrom<&str> for Metric {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "plays" | "count" => Ok(Self::Plays),
+            "minutes" | "time" => Ok(Self::Time),
+            _ => Err(()),
+        }
+    }
+}
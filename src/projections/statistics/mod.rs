@@ -4,12 +4,25 @@ mod count;
 mod counter;
 mod event_processor;
 mod general;
+mod listen;
+mod listen_timeline;
+mod metric;
+mod period;
+mod rank_movement;
+mod streaks;
 mod time_played;
 
 pub use artists_counts::ArtistsCounts;
 pub use calendar_counts::{order_in_week, MonthCounts};
-pub use count::{ArtistAndSongCount, SongCount};
+pub use count::{ArtistAndAlbumCount, ArtistAndSongCount, ArtistAndSongSkipRate, SongCount};
+pub use counter::ArtistSongCounter;
 
 pub use event_processor::EventProcessor;
 pub use general::General;
+pub use listen::{Listen, ListenRules};
+pub use listen_timeline::{ArtistTimeline, ListenSpan, ListenTimeline};
+pub use metric::Metric;
+pub use period::Period;
+pub use rank_movement::RankComparison;
+pub use streaks::ListenStreaks;
 pub use time_played::TimePlayed;
@@ -0,0 +1,167 @@
+use std::fmt::Display;
+
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InteractiveError;
+
+/// An inclusive range of days, entered as a year (`2022`), a month (`2022-06`)
+/// or a range of dates (`2022-01-01..2022-03-31`).
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Period {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Period {
+    pub fn year(year: i32) -> Option<Self> {
+        Some(Self {
+            start: NaiveDate::from_ymd_opt(year, 1, 1)?,
+            end: NaiveDate::from_ymd_opt(year, 12, 31)?,
+        })
+    }
+
+    pub fn month(year: i32, month: u32) -> Option<Self> {
+        let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let end = start.checked_add_months(Months::new(1))?.pred_opt()?;
+        Some(Self { start, end })
+    }
+
+    /// An open-ended bound is treated as the start or end of all listens. A start after
+    /// the end is an error rather than an empty period.
+    pub fn between(
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Option<Self>, InteractiveError> {
+        if from.is_none() && to.is_none() {
+            return Ok(None);
+        }
+
+        let start = from.unwrap_or(NaiveDate::MIN);
+        let end = to.unwrap_or(NaiveDate::MAX);
+        if start > end {
+            return Err(InteractiveError::ParsingIssue {
+                message: format!("The from date {start} is after the to date {end}"),
+            });
+        }
+
+        Ok(Some(Self { start, end }))
+    }
+
+    pub fn contains(&self, date: &NaiveDate) -> bool {
+        self.start <= *date && *date <= self.end
+    }
+
+    pub fn covers_year(&self, year: i32) -> bool {
+        Self::year(year)
+            .map(|year_period| self.covers(&year_period))
+            .unwrap_or(false)
+    }
+
+    pub fn covers_month(&self, year: i32, month: u32) -> bool {
+        Self::month(year, month)
+            .map(|month_period| self.covers(&month_period))
+            .unwrap_or(false)
+    }
+
+    pub fn overlaps_year(&self, year: i32) -> bool {
+        self.start.year() <= year && year <= self.end.year()
+    }
+
+    pub fn overlaps_month(&self, year: i32, month: u32) -> bool {
+        Self::month(year, month)
+            .map(|month_period| self.start <= month_period.end && month_period.start <= self.end)
+            .unwrap_or(false)
+    }
+
+    fn covers(&self, other: &Period) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if Self::year(self.start.year()).as_ref() == Some(self) {
+            f.write_str(&format!("{}", self.start.year()))
+        } else if Self::month(self.start.year(), self.start.month()).as_ref() == Some(self) {
+            f.write_str(&format!("{}", self.start.format("%Y-%m")))
+        } else {
+            let start = if self.start == NaiveDate::MIN {
+                String::new()
+            } else {
+                self.start.format("%Y-%m-%d").to_string()
+            };
+            let end = if self.end == NaiveDate::MAX {
+                String::new()
+            } else {
+                self.end.format("%Y-%m-%d").to_string()
+            };
+            f.write_str(&format!("{start}..{end}"))
+        }
+    }
+}
+
+impl TryFrom<&str> for Period {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+
+        if let Some((start, end)) = value.split_once("..") {
+            let start = NaiveDate::parse_from_str(start.trim(), "%Y-%m-%d").map_err(|_| ())?;
+            let end = NaiveDate::parse_from_str(end.trim(), "%Y-%m-%d").map_err(|_| ())?;
+            return if start <= end {
+                Ok(Self { start, end })
+            } else {
+                Err(())
+            };
+        }
+
+        if let Some((year, month)) = value.split_once('-') {
+            let year = year.parse::<i32>().map_err(|_| ())?;
+            let month = month.parse::<u32>().map_err(|_| ())?;
+            return Self::month(year, month).ok_or(());
+        }
+
+        value.parse::<i32>().ok().and_then(Self::year).ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::Period;
+
+    #[test]
+    fn parses_years_months_and_ranges() {
+        let year = Period::try_from("2022").unwrap();
+        assert_eq!(NaiveDate::from_ymd_opt(2022, 12, 31).unwrap(), year.end);
+        assert_eq!("2022", year.to_string());
+
+        let month = Period::try_from("2024-02").unwrap();
+        assert_eq!(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(), month.end);
+        assert_eq!("2024-02", month.to_string());
+
+        let range = Period::try_from("2022-01-15..2022-03-01").unwrap();
+        assert!(range.covers_month(2022, 2));
+        assert!(!range.covers_month(2022, 1));
+        assert!(range.overlaps_month(2022, 1));
+        assert_eq!("2022-01-15..2022-03-01", range.to_string());
+
+        let open_ended = Period::between(NaiveDate::from_ymd_opt(2021, 6, 15), None)
+            .unwrap()
+            .unwrap();
+        assert!(open_ended.covers_year(2023));
+        assert_eq!("2021-06-15..", open_ended.to_string());
+        assert!(Period::between(None, None).unwrap().is_none());
+        assert!(Period::between(
+            NaiveDate::from_ymd_opt(2022, 3, 1),
+            NaiveDate::from_ymd_opt(2022, 1, 15)
+        )
+        .is_err());
+
+        assert!(Period::try_from("2022-03-01..2022-01-15").is_err());
+        assert!(Period::try_from("2022-13").is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use std::{collections::HashMap, fmt::Display};
+
+#[derive(Debug, PartialEq)]
+pub struct RankMovement {
+    pub name: String,
+    pub rank: usize,
+    pub previous_rank: Option<usize>,
+}
+
+impl RankMovement {
+    pub fn is_new(&self) -> bool {
+        self.previous_rank.is_none()
+    }
+
+    /// Positive when the entry climbed, negative when it fell.
+    pub fn change(&self) -> Option<i64> {
+        self.previous_rank
+            .map(|previous_rank| previous_rank as i64 - self.rank as i64)
+    }
+}
+
+impl Display for RankMovement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let movement = match (self.change(), self.previous_rank) {
+            (Some(change), Some(previous_rank)) if change > 0 => {
+                format!("up {change} from #{previous_rank}")
+            }
+            (Some(change), Some(previous_rank)) if change < 0 => {
+                format!("down {} from #{previous_rank}", change.abs())
+            }
+            (Some(_), _) => "no change".to_string(),
+            (None, _) => "new".to_string(),
+        };
+
+        f.write_str(&format!("{}. {} ({movement})", self.rank, self.name))
+    }
+}
+
+pub struct RankComparison {
+    pub ranked: Vec<RankMovement>,
+    pub dropped_out: Vec<(String, usize)>,
+}
+
+impl RankComparison {
+    /// Compares the first `count` entries of two full rankings. Previous ranks are
+    /// looked up in the full previous ranking, so an entry climbing into the top
+    /// shows where it came from. Names are matched ignoring case and extra spaces, the
+    /// same as the projections merge them. Names that match keep their best rank.
+    pub fn compare(previous: &[String], current: &[String], count: usize) -> Self {
+        let mut previous_ranks: HashMap<String, usize> = HashMap::new();
+        for (index, name) in previous.iter().enumerate() {
+            previous_ranks.entry(rank_key(name)).or_insert(index + 1);
+        }
+        let current_top: Vec<String> = current
+            .iter()
+            .take(count)
+            .map(|name| rank_key(name))
+            .collect();
+
+        let ranked: Vec<RankMovement> = current
+            .iter()
+            .take(count)
+            .enumerate()
+            .map(|(index, name)| RankMovement {
+                name: name.to_string(),
+                rank: index + 1,
+                previous_rank: previous_ranks.get(&rank_key(name)).copied(),
+            })
+            .collect();
+
+        let dropped_out: Vec<(String, usize)> = previous
+            .iter()
+            .take(count)
+            .enumerate()
+            .filter(|(_, name)| !current_top.contains(&rank_key(name)))
+            .map(|(index, name)| (name.to_string(), index + 1))
+            .collect();
+
+        Self {
+            ranked,
+            dropped_out,
+        }
+    }
+
+    pub fn new_entries(&self) -> Vec<&RankMovement> {
+        self.ranked
+            .iter()
+            .filter(|movement| movement.is_new())
+            .collect()
+    }
+
+    pub fn climbers(&self) -> Vec<&RankMovement> {
+        let mut climbers: Vec<&RankMovement> = self
+            .ranked
+            .iter()
+            .filter(|movement| movement.change().unwrap_or_default() > 0)
+            .collect();
+        climbers.sort_by_key(|movement| -movement.change().unwrap_or_default());
+        climbers
+    }
+}
+
+fn rank_key(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RankComparison;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn finds_new_entries_climbers_and_drop_outs() {
+        let previous = names(&["Ulcerate", "Gorguts", "Portal", "Immolation"]);
+        let current = names(&["Immolation", "Ulcerate", "Artificial Brain", "Gorguts"]);
+
+        let comparison = RankComparison::compare(&previous, &current, 3);
+
+        assert_eq!(3, comparison.ranked.len());
+        assert_eq!(
+            "1. Immolation (up 3 from #4)",
+            comparison.ranked[0].to_string()
+        );
+        assert_eq!(
+            "2. Ulcerate (down 1 from #1)",
+            comparison.ranked[1].to_string()
+        );
+        assert_eq!(
+            vec!["Artificial Brain"],
+            comparison
+                .new_entries()
+                .iter()
+                .map(|movement| movement.name.as_str())
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(1, comparison.climbers().len());
+        assert_eq!(
+            vec![("Gorguts".to_string(), 2), ("Portal".to_string(), 3)],
+            comparison.dropped_out
+        );
+    }
+
+    #[test]
+    fn matches_songs_ignoring_case() {
+        let previous = names(&["Mgła - Exercises in Futility I", "Ulcerate - Visceral Ends"]);
+        let current = names(&[
+            "Ulcerate - Visceral ends",
+            "MGŁA - Exercises in  Futility I",
+        ]);
+
+        let comparison = RankComparison::compare(&previous, &current, 2);
+
+        assert!(comparison.new_entries().is_empty());
+        assert!(comparison.dropped_out.is_empty());
+        assert_eq!(
+            "1. Ulcerate - Visceral ends (up 1 from #2)",
+            comparison.ranked[0].to_string()
+        );
+    }
+
+    #[test]
+    fn keeps_the_best_previous_rank_of_matching_names() {
+        let previous = names(&["Ulcerate", "Gorguts", "ulcerate"]);
+        let current = names(&["Gorguts", "Ulcerate"]);
+
+        let comparison = RankComparison::compare(&previous, &current, 2);
+
+        assert_eq!(Some(1), comparison.ranked[1].previous_rank);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{cmp::Reverse, collections::HashMap, fmt::Display};
+
+use chrono::NaiveDate;
+
+use crate::track_plays::ArtistName;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Streak {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Streak {
+    pub fn days(&self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+}
+
+impl Display for Streak {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "{} days ({} to {})",
+            self.days(),
+            self.start.format("%Y-%m-%d"),
+            self.end.format("%Y-%m-%d")
+        ))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gap {
+    pub last_listen: NaiveDate,
+    pub next_listen: NaiveDate,
+}
+
+impl Gap {
+    pub fn days(&self) -> i64 {
+        (self.next_listen - self.last_listen).num_days()
+    }
+}
+
+impl Display for Gap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "{} days ({} to {})",
+            self.days(),
+            self.last_listen.format("%Y-%m-%d"),
+            self.next_listen.format("%Y-%m-%d")
+        ))
+    }
+}
+
+pub struct ListenStreaks {
+    pub longest: Option<Streak>,
+    pub current: Option<Streak>,
+    pub busiest_day: Option<(NaiveDate, u64)>,
+    pub artist_streaks: Vec<(ArtistName, Streak)>,
+    pub artist_gaps: Vec<(ArtistName, Gap)>,
+}
+
+impl ListenStreaks {
+    pub fn build(
+        day_plays: &[(NaiveDate, u64)],
+        artist_days: HashMap<ArtistName, Vec<NaiveDate>>,
+        today: &NaiveDate,
+    ) -> Self {
+        let mut days: Vec<NaiveDate> = day_plays.iter().map(|(date, _)| *date).collect();
+        days.sort();
+        days.dedup();
+
+        let busiest_day = day_plays
+            .iter()
+            .max_by_key(|(date, plays)| (*plays, Reverse(*date)))
+            .cloned();
+
+        let mut artist_streaks: Vec<(ArtistName, Streak)> = Vec::new();
+        let mut artist_gaps: Vec<(ArtistName, Gap)> = Vec::new();
+        for (artist_name, mut dates) in artist_days.into_iter() {
+            dates.sort();
+            dates.dedup();
+            if let Some(streak) = longest_streak(&dates) {
+                artist_streaks.push((artist_name.clone(), streak));
+            }
+            if let Some(gap) = longest_gap(&dates) {
+                artist_gaps.push((artist_name, gap));
+            }
+        }
+        artist_streaks.sort_by_key(|(artist_name, streak)| {
+            (Reverse(streak.days()), artist_name.0.to_lowercase())
+        });
+        artist_gaps
+            .sort_by_key(|(artist_name, gap)| (Reverse(gap.days()), artist_name.0.to_lowercase()));
+
+        Self {
+            longest: longest_streak(&days),
+            current: current_streak(&days, today),
+            busiest_day,
+            artist_streaks,
+            artist_gaps,
+        }
+    }
+}
+
+/// Expects the dates to be sorted and without duplicates.
+pub fn longest_streak(dates: &[NaiveDate]) -> Option<Streak> {
+    streaks(dates)
+        .into_iter()
+        .max_by_key(|streak| (streak.days(), Reverse(streak.start)))
+}
+
+/// A streak is current if it ends today, or yesterday since today isn't over yet.
+pub fn current_streak(dates: &[NaiveDate], today: &NaiveDate) -> Option<Streak> {
+    streaks(dates)
+        .pop()
+        .filter(|streak| (*today - streak.end).num_days() <= 1)
+}
+
+/// Expects the dates to be sorted and without duplicates.
+pub fn longest_gap(dates: &[NaiveDate]) -> Option<Gap> {
+    dates
+        .windows(2)
+        .map(|window| Gap {
+            last_listen: window[0],
+            next_listen: window[1],
+        })
+        .filter(|gap| gap.days() > 1)
+        .max_by_key(|gap| (gap.days(), Reverse(gap.last_listen)))
+}
+
+fn streaks(dates: &[NaiveDate]) -> Vec<Streak> {
+    let mut streaks: Vec<Streak> = Vec::new();
+
+    for date in dates {
+        match streaks.last_mut() {
+            Some(streak) if (*date - streak.end).num_days() == 1 => streak.end = *date,
+            _ => streaks.push(Streak {
+                start: *date,
+                end: *date,
+            }),
+        }
+    }
+
+    streaks
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{current_streak, longest_gap, longest_streak};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, month, day).unwrap()
+    }
+
+    #[test]
+    fn finds_longest_streak() {
+        let dates = vec![date(1, 1), date(1, 2), date(1, 5), date(1, 6), date(1, 7)];
+
+        let streak = longest_streak(&dates).unwrap();
+        assert_eq!(date(1, 5), streak.start);
+        assert_eq!(3, streak.days());
+    }
+
+    #[test]
+    fn current_streak_ends_today_or_yesterday() {
+        let dates = vec![date(1, 1), date(1, 3), date(1, 4)];
+
+        assert_eq!(2, current_streak(&dates, &date(1, 5)).unwrap().days());
+        assert!(current_streak(&dates, &date(1, 6)).is_none());
+    }
+
+    #[test]
+    fn finds_longest_gap() {
+        let dates = vec![date(1, 1), date(1, 2), date(3, 1), date(3, 10)];
+
+        let gap = longest_gap(&dates).unwrap();
+        assert_eq!(date(1, 2), gap.last_listen);
+        assert_eq!(date(3, 1), gap.next_listen);
+        assert!(longest_gap(&[date(1, 1), date(1, 2)]).is_none());
+    }
+}
@@ -11,20 +11,20 @@ pub struct TimePlayed {
 impl TimePlayed {
     pub fn add_ms(&mut self, additional_ms: u64) {
         self.time_ms += additional_ms;
-        self.time_sec = self.calculate_time_sec(additional_ms);
-        self.time_min = self.calculate_time_min(additional_ms);
-        self.time_hr = self.calculate_time_hr(additional_ms);
+        self.time_sec = self.calculate_time_sec();
+        self.time_min = self.calculate_time_min();
+        self.time_hr = self.calculate_time_hr();
     }
 
-    fn calculate_time_sec(&self, additional_ms: u64) -> f64 {
-        (self.time_ms + additional_ms) as f64 / 1000.0
+    fn calculate_time_sec(&self) -> f64 {
+        self.time_ms as f64 / 1000.0
     }
 
-    fn calculate_time_min(&self, additional_ms: u64) -> f64 {
-        (self.time_ms + additional_ms) as f64 / 60_000.0
+    fn calculate_time_min(&self) -> f64 {
+        self.time_ms as f64 / 60_000.0
     }
 
-    fn calculate_time_hr(&self, additional_ms: u64) -> f64 {
-        (self.time_ms + additional_ms) as f64 / (60_000.0 * 60.0)
+    fn calculate_time_hr(&self) -> f64 {
+        self.time_ms as f64 / (60_000.0 * 60.0)
     }
 }
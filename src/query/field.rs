@@ -0,0 +1,216 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike, Weekday};
+
+use crate::{errors::QueryError, projections::statistics::Listen, utils::parse_yes_no};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Field {
+    Artist,
+    Track,
+    Album,
+    Service,
+    Date,
+    Year,
+    Month,
+    Hour,
+    Time,
+    Weekday,
+    Skipped,
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match *self {
+            Field::Artist => "artist",
+            Field::Track => "track",
+            Field::Album => "album",
+            Field::Service => "service",
+            Field::Date => "date",
+            Field::Year => "year",
+            Field::Month => "month",
+            Field::Hour => "hour",
+            Field::Time => "time",
+            Field::Weekday => "weekday",
+            Field::Skipped => "skipped",
+        };
+
+        f.write_str(value)
+    }
+}
+
+impl TryFrom<&str> for Field {
+    type Error = QueryError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "artist" => Ok(Field::Artist),
+            "track" | "song" => Ok(Field::Track),
+            "album" => Ok(Field::Album),
+            "service" => Ok(Field::Service),
+            "date" => Ok(Field::Date),
+            "year" => Ok(Field::Year),
+            "month" => Ok(Field::Month),
+            "hour" => Ok(Field::Hour),
+            "time" => Ok(Field::Time),
+            "weekday" | "day" => Ok(Field::Weekday),
+            "skipped" => Ok(Field::Skipped),
+            _ => Err(QueryError::UnknownField {
+                field: value.to_string(),
+            }),
+        }
+    }
+}
+
+/// A comparable value pulled out of a listen, or parsed from the query text.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Text(String),
+    Number(i64),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Weekday(Weekday),
+    Flag(bool),
+    Missing,
+}
+
+impl Value {
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Text(a), Value::Text(b)) => Some(a.to_lowercase().cmp(&b.to_lowercase())),
+            (Value::Number(a), Value::Number(b)) => Some(a.cmp(b)),
+            (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+            (Value::Time(a), Value::Time(b)) => Some(a.cmp(b)),
+            (Value::Weekday(a), Value::Weekday(b)) => {
+                Some(a.num_days_from_monday().cmp(&b.num_days_from_monday()))
+            }
+            (Value::Flag(a), Value::Flag(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Text(text) => f.write_str(text),
+            Value::Number(number) => f.write_str(&format!("{number}")),
+            Value::Date(date) => f.write_str(&date.format("%Y-%m-%d").to_string()),
+            Value::Time(time) => f.write_str(&time.format("%H:%M").to_string()),
+            Value::Weekday(weekday) => f.write_str(&weekday.to_string()),
+            Value::Flag(flag) => f.write_str(if *flag { "skipped" } else { "played" }),
+            Value::Missing => f.write_str("None"),
+        }
+    }
+}
+
+impl Field {
+    pub fn value(&self, listen: &Listen) -> Value {
+        match self {
+            Field::Artist => Value::Text(listen.artist_name.0.clone()),
+            Field::Track => Value::Text(listen.track_name.0.clone()),
+            Field::Album => listen
+                .album_name
+                .as_ref()
+                .map(|album_name| Value::Text(album_name.0.clone()))
+                .unwrap_or(Value::Missing),
+            Field::Service => Value::Text(listen.service.clone()),
+            Field::Date => Value::Date(listen.listened_at.date()),
+            Field::Year => Value::Number(listen.listened_at.year() as i64),
+            Field::Month => Value::Number(listen.listened_at.month() as i64),
+            Field::Hour => Value::Number(listen.listened_at.hour() as i64),
+            Field::Time => Value::Time(listen.listened_at.time()),
+            Field::Weekday => Value::Weekday(listen.listened_at.weekday()),
+            Field::Skipped => Value::Flag(listen.skipped),
+        }
+    }
+
+    /// Tracks and albums are grouped together with their artist, since the same
+    /// title can belong to many artists.
+    pub fn group_key(&self, listen: &Listen) -> Value {
+        match (self, self.value(listen)) {
+            (Field::Track | Field::Album, Value::Text(title)) => {
+                Value::Text(format!("{} - {title}", listen.artist_name))
+            }
+            (_, value) => value,
+        }
+    }
+
+    pub fn parse_value(&self, text: &str) -> Result<Value, QueryError> {
+        let invalid = || QueryError::InvalidValue {
+            field: self.to_string(),
+            value: text.to_string(),
+        };
+
+        match self {
+            Field::Artist | Field::Track | Field::Album | Field::Service => {
+                Ok(Value::Text(text.to_string()))
+            }
+            Field::Year | Field::Month | Field::Hour => text
+                .parse::<i64>()
+                .map(Value::Number)
+                .map_err(|_| invalid()),
+            Field::Date => NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .map(Value::Date)
+                .map_err(|_| invalid()),
+            Field::Time => NaiveTime::parse_from_str(text, "%H:%M")
+                .map(Value::Time)
+                .map_err(|_| invalid()),
+            Field::Weekday => Weekday::from_str(text)
+                .map(Value::Weekday)
+                .map_err(|_| invalid()),
+            Field::Skipped => parse_yes_no(text).map(Value::Flag).ok_or_else(invalid),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operator {
+    Equals,
+    NotEquals,
+    Contains,
+    GreaterThan,
+    GreaterOrEqual,
+    LessThan,
+    LessOrEqual,
+    In,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    pub field: Field,
+    pub operator: Operator,
+    pub values: Vec<Value>,
+}
+
+impl Filter {
+    pub fn matches(&self, listen: &Listen) -> bool {
+        let value = self.field.value(listen);
+        let Some(expected) = self.values.first() else {
+            return false;
+        };
+
+        match self.operator {
+            Operator::Equals => value.compare(expected) == Some(Ordering::Equal),
+            Operator::NotEquals => value.compare(expected) != Some(Ordering::Equal),
+            Operator::Contains => value
+                .to_string()
+                .to_lowercase()
+                .contains(&expected.to_string().to_lowercase()),
+            Operator::GreaterThan => value.compare(expected) == Some(Ordering::Greater),
+            Operator::GreaterOrEqual => matches!(
+                value.compare(expected),
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+            Operator::LessThan => value.compare(expected) == Some(Ordering::Less),
+            Operator::LessOrEqual => matches!(
+                value.compare(expected),
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+            Operator::In => self
+                .values
+                .iter()
+                .any(|expected| value.compare(expected) == Some(Ordering::Equal)),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{errors::QueryError, projections::statistics::Listen};
+
+use super::field::{Field, Filter, Value};
+
+pub const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrderKey {
+    Count,
+    Minutes,
+    Name,
+    Time,
+}
+
+impl TryFrom<&str> for OrderKey {
+    type Error = QueryError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "count" | "plays" => Ok(OrderKey::Count),
+            "minutes" => Ok(OrderKey::Minutes),
+            "name" => Ok(OrderKey::Name),
+            "time" | "date" => Ok(OrderKey::Time),
+            _ => Err(QueryError::InvalidValue {
+                field: "order by".to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Direction {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug)]
+pub struct ListenQuery {
+    pub filters: Vec<Filter>,
+    pub group_by: Option<Field>,
+    pub order_by: Option<(OrderKey, Option<Direction>)>,
+    pub limit: usize,
+}
+
+struct Group {
+    key: Value,
+    count: u64,
+    ms_played: u64,
+}
+
+impl ListenQuery {
+    /// Skips are left out unless the query filters on them, so the results line
+    /// up with the plays counted everywhere else.
+    fn matches(&self, listen: &Listen) -> bool {
+        let filters_skipped = self
+            .filters
+            .iter()
+            .any(|filter| filter.field == Field::Skipped);
+
+        (filters_skipped || !listen.skipped)
+            && self.filters.iter().all(|filter| filter.matches(listen))
+    }
+
+    pub fn run(&self, listens: &[Listen]) -> Vec<String> {
+        let matching: Vec<&Listen> = listens
+            .iter()
+            .filter(|listen| self.matches(listen))
+            .collect();
+
+        match &self.group_by {
+            Some(field) => self.grouped(field, matching),
+            None => self.ungrouped(matching),
+        }
+    }
+
+    fn grouped(&self, field: &Field, listens: Vec<&Listen>) -> Vec<String> {
+        let mut groups: HashMap<String, Group> = HashMap::new();
+        for listen in listens {
+            let key = field.group_key(listen);
+            if key == Value::Missing {
+                continue;
+            }
+
+            let group = groups
+                .entry(key.to_string().to_lowercase())
+                .or_insert_with(|| Group {
+                    key,
+                    count: 0,
+                    ms_played: 0,
+                });
+            group.count += 1;
+            group.ms_played += listen.ms_played;
+        }
+
+        let mut groups: Vec<Group> = groups.into_values().collect();
+        let (key, direction) = self.order_by.clone().unwrap_or((OrderKey::Count, None));
+        groups.sort_by(|a, b| {
+            let ordering = match key {
+                OrderKey::Count => a.count.cmp(&b.count),
+                OrderKey::Minutes => a.ms_played.cmp(&b.ms_played),
+                OrderKey::Name | OrderKey::Time => a.key.compare(&b.key).unwrap_or(Ordering::Equal),
+            };
+            apply_direction(ordering, &key, &direction)
+        });
+
+        groups
+            .into_iter()
+            .take(self.limit)
+            .map(|group| {
+                format!(
+                    "{} - {} listens ({:.1} min)",
+                    group.key,
+                    group.count,
+                    group.ms_played as f64 / 60_000.0
+                )
+            })
+            .collect()
+    }
+
+    fn ungrouped(&self, mut listens: Vec<&Listen>) -> Vec<String> {
+        let (key, direction) = self.order_by.clone().unwrap_or((OrderKey::Time, None));
+        listens.sort_by(|a, b| {
+            let ordering = match key {
+                OrderKey::Count | OrderKey::Minutes => a.ms_played.cmp(&b.ms_played),
+                OrderKey::Name => (
+                    a.artist_name.0.to_lowercase(),
+                    a.track_name.0.to_lowercase(),
+                )
+                    .cmp(&(
+                        b.artist_name.0.to_lowercase(),
+                        b.track_name.0.to_lowercase(),
+                    )),
+                OrderKey::Time => a.listened_at.cmp(&b.listened_at),
+            };
+            apply_direction(ordering, &key, &direction)
+        });
+
+        listens
+            .into_iter()
+            .take(self.limit)
+            .map(|listen| {
+                format!(
+                    "{} - {} - {} ({}, {}s{})",
+                    listen.listened_at.format("%Y-%m-%d %H:%M"),
+                    listen.artist_name,
+                    listen.track_name,
+                    listen.service,
+                    listen.ms_played / 1000,
+                    if listen.skipped { ", skipped" } else { "" }
+                )
+            })
+            .collect()
+    }
+}
+
+/// Counts and minutes read best largest first, names and times in order.
+fn apply_direction(ordering: Ordering, key: &OrderKey, direction: &Option<Direction>) -> Ordering {
+    let descending = match direction {
+        Some(direction) => *direction == Direction::Descending,
+        None => matches!(key, OrderKey::Count | OrderKey::Minutes),
+    };
+
+    if descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::{
+        projections::statistics::Listen,
+        query::parse,
+        track_plays::{ArtistName, TrackName},
+    };
+
+    fn listen(artist: &str, track: &str, day: u32, hour: u32, skipped: bool) -> Listen {
+        Listen {
+            artist_name: ArtistName(artist.to_string()),
+            track_name: TrackName(track.to_string()),
+            album_name: None,
+            service: "spotify".to_string(),
+            listened_at: NaiveDate::from_ymd_opt(2022, 10, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap(),
+            ms_played: 240_000,
+            skipped,
+        }
+    }
+
+    #[test]
+    fn filters_and_groups_listens() {
+        // 2022-10-01 and 2022-10-02 are a Saturday and Sunday.
+        let listens = vec![
+            listen("Death", "Crystal Mountain", 1, 1, false),
+            listen("Death", "Crystal Mountain", 2, 2, false),
+            listen("Death", "Zero Tolerance", 2, 3, false),
+            listen("Death", "Zero Tolerance", 2, 4, true),
+            listen("Deathspell Omega", "Drought", 3, 1, false),
+            listen("Ulcerate", "Yield to Naught", 1, 1, false),
+        ];
+
+        let query = parse("artist ~ death and weekday in (sat, sun) group by track").unwrap();
+        assert_eq!(
+            vec![
+                "Death - Crystal Mountain - 2 listens (8.0 min)".to_string(),
+                "Death - Zero Tolerance - 1 listens (4.0 min)".to_string(),
+            ],
+            query.run(&listens)
+        );
+
+        let skipped = parse("skipped = yes").unwrap();
+        assert_eq!(1, skipped.run(&listens).len());
+    }
+}
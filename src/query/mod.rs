@@ -0,0 +1,5 @@
+mod field;
+mod listen_query;
+mod parser;
+
+pub use parser::parse;
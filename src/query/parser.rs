@@ -0,0 +1,349 @@
+use std::{iter::Peekable, vec::IntoIter};
+
+use crate::errors::QueryError;
+
+use super::{
+    field::{Field, Filter, Operator},
+    listen_query::{Direction, ListenQuery, OrderKey, DEFAULT_LIMIT},
+};
+
+const KEYWORDS: [&str; 5] = ["and", "group", "order", "limit", "in"];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Operator(String),
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn display(&self) -> String {
+        match self {
+            Token::Word(text) | Token::Quoted(text) | Token::Operator(text) => text.to_string(),
+            Token::OpenParen => "(".to_string(),
+            Token::CloseParen => ")".to_string(),
+            Token::Comma => ",".to_string(),
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<ListenQuery, QueryError> {
+    let mut tokens = tokenize(text)?.into_iter().peekable();
+    let mut query = ListenQuery {
+        filters: Vec::new(),
+        group_by: None,
+        order_by: None,
+        limit: DEFAULT_LIMIT,
+    };
+
+    if tokens
+        .peek()
+        .map(|token| {
+            !token.is_keyword("group") && !token.is_keyword("order") && !token.is_keyword("limit")
+        })
+        .unwrap_or(false)
+    {
+        query.filters.push(parse_filter(&mut tokens)?);
+        while tokens
+            .peek()
+            .map(|token| token.is_keyword("and"))
+            .unwrap_or(false)
+        {
+            tokens.next();
+            query.filters.push(parse_filter(&mut tokens)?);
+        }
+    }
+
+    if tokens
+        .peek()
+        .map(|token| token.is_keyword("group"))
+        .unwrap_or(false)
+    {
+        tokens.next();
+        expect_keyword(&mut tokens, "by")?;
+        query.group_by = Some(Field::try_from(
+            next_word(&mut tokens, "a field")?.as_str(),
+        )?);
+    }
+
+    if tokens
+        .peek()
+        .map(|token| token.is_keyword("order"))
+        .unwrap_or(false)
+    {
+        tokens.next();
+        expect_keyword(&mut tokens, "by")?;
+        let key =
+            OrderKey::try_from(next_word(&mut tokens, "count, minutes, name or time")?.as_str())?;
+        let direction = match tokens.peek() {
+            Some(token) if token.is_keyword("asc") => {
+                tokens.next();
+                Some(Direction::Ascending)
+            }
+            Some(token) if token.is_keyword("desc") => {
+                tokens.next();
+                Some(Direction::Descending)
+            }
+            _ => None,
+        };
+        query.order_by = Some((key, direction));
+    }
+
+    if tokens
+        .peek()
+        .map(|token| token.is_keyword("limit"))
+        .unwrap_or(false)
+    {
+        tokens.next();
+        let limit = next_word(&mut tokens, "a number")?;
+        query.limit = limit
+            .parse::<usize>()
+            .map_err(|_| QueryError::InvalidValue {
+                field: "limit".to_string(),
+                value: limit,
+            })?;
+    }
+
+    match tokens.next() {
+        Some(token) => Err(QueryError::UnexpectedToken {
+            token: token.display(),
+            expected: "and, group by, order by or limit".to_string(),
+        }),
+        None => Ok(query),
+    }
+}
+
+fn parse_filter(tokens: &mut Peekable<IntoIter<Token>>) -> Result<Filter, QueryError> {
+    let field = Field::try_from(next_word(tokens, "a field")?.as_str())?;
+
+    let operator = match tokens.next() {
+        Some(Token::Operator(operator)) => match operator.as_str() {
+            "=" => Operator::Equals,
+            "!=" => Operator::NotEquals,
+            "~" => Operator::Contains,
+            ">" => Operator::GreaterThan,
+            ">=" => Operator::GreaterOrEqual,
+            "<" => Operator::LessThan,
+            "<=" => Operator::LessOrEqual,
+            _ => {
+                return Err(QueryError::UnexpectedToken {
+                    token: operator,
+                    expected: "an operator".to_string(),
+                })
+            }
+        },
+        Some(token) if token.is_keyword("in") => Operator::In,
+        Some(token) => {
+            return Err(QueryError::UnexpectedToken {
+                token: token.display(),
+                expected: "an operator".to_string(),
+            })
+        }
+        None => {
+            return Err(QueryError::UnexpectedEnd {
+                expected: "an operator".to_string(),
+            })
+        }
+    };
+
+    let values = if operator == Operator::In {
+        match tokens.next() {
+            Some(Token::OpenParen) => {}
+            Some(token) => {
+                return Err(QueryError::UnexpectedToken {
+                    token: token.display(),
+                    expected: "(".to_string(),
+                })
+            }
+            None => {
+                return Err(QueryError::UnexpectedEnd {
+                    expected: "(".to_string(),
+                })
+            }
+        }
+
+        let mut values = vec![field.parse_value(&next_value(tokens)?)?];
+        loop {
+            match tokens.next() {
+                Some(Token::Comma) => values.push(field.parse_value(&next_value(tokens)?)?),
+                Some(Token::CloseParen) => break,
+                Some(token) => {
+                    return Err(QueryError::UnexpectedToken {
+                        token: token.display(),
+                        expected: ", or )".to_string(),
+                    })
+                }
+                None => {
+                    return Err(QueryError::UnexpectedEnd {
+                        expected: ")".to_string(),
+                    })
+                }
+            }
+        }
+        values
+    } else {
+        vec![field.parse_value(&next_value(tokens)?)?]
+    };
+
+    Ok(Filter {
+        field,
+        operator,
+        values,
+    })
+}
+
+/// A value is either quoted, or every bare word up to the next keyword or symbol,
+/// so `artist = Blut Aus Nord` doesn't need quotes.
+fn next_value(tokens: &mut Peekable<IntoIter<Token>>) -> Result<String, QueryError> {
+    match tokens.next() {
+        Some(Token::Quoted(text)) => Ok(text),
+        Some(Token::Word(word)) => {
+            let mut words = vec![word];
+            while let Some(Token::Word(next)) = tokens.peek() {
+                if KEYWORDS
+                    .iter()
+                    .any(|keyword| next.eq_ignore_ascii_case(keyword))
+                {
+                    break;
+                }
+                words.push(next.to_string());
+                tokens.next();
+            }
+            Ok(words.join(" "))
+        }
+        Some(token) => Err(QueryError::UnexpectedToken {
+            token: token.display(),
+            expected: "a value".to_string(),
+        }),
+        None => Err(QueryError::UnexpectedEnd {
+            expected: "a value".to_string(),
+        }),
+    }
+}
+
+fn next_word(tokens: &mut Peekable<IntoIter<Token>>, expected: &str) -> Result<String, QueryError> {
+    match tokens.next() {
+        Some(Token::Word(word)) => Ok(word),
+        Some(token) => Err(QueryError::UnexpectedToken {
+            token: token.display(),
+            expected: expected.to_string(),
+        }),
+        None => Err(QueryError::UnexpectedEnd {
+            expected: expected.to_string(),
+        }),
+    }
+}
+
+fn expect_keyword(tokens: &mut Peekable<IntoIter<Token>>, keyword: &str) -> Result<(), QueryError> {
+    match tokens.next() {
+        Some(token) if token.is_keyword(keyword) => Ok(()),
+        Some(token) => Err(QueryError::UnexpectedToken {
+            token: token.display(),
+            expected: keyword.to_string(),
+        }),
+        None => Err(QueryError::UnexpectedEnd {
+            expected: keyword.to_string(),
+        }),
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::OpenParen),
+            ')' => tokens.push(Token::CloseParen),
+            ',' => tokens.push(Token::Comma),
+            '"' | '\'' => {
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => quoted.push(next),
+                        None => return Err(QueryError::UnterminatedQuote),
+                    }
+                }
+                tokens.push(Token::Quoted(quoted));
+            }
+            '=' | '~' => tokens.push(Token::Operator(c.to_string())),
+            '!' | '<' | '>' => {
+                if chars.peek() == Some(&'=') {
+                    chars.next();
+                    tokens.push(Token::Operator(format!("{c}=")));
+                } else {
+                    tokens.push(Token::Operator(c.to_string()));
+                }
+            }
+            _ => {
+                let mut word = c.to_string();
+                while let Some(next) = chars.peek() {
+                    if next.is_whitespace() || "()=,~!<>\"'".contains(*next) {
+                        break;
+                    }
+                    word.push(*next);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::QueryError,
+        query::field::{Field, Operator, Value},
+    };
+
+    use super::parse;
+
+    #[test]
+    fn parses_filters_grouping_and_ordering() {
+        let query = parse(
+            "artist ~ death and weekday in (sat, sun) and hour < 6 group by track order by minutes desc limit 10",
+        )
+        .unwrap();
+
+        assert_eq!(3, query.filters.len());
+        assert_eq!(Operator::Contains, query.filters[0].operator);
+        assert_eq!(2, query.filters[1].values.len());
+        assert_eq!(vec![Value::Number(6)], query.filters[2].values);
+        assert_eq!(Some(Field::Track), query.group_by);
+        assert_eq!(10, query.limit);
+    }
+
+    #[test]
+    fn joins_bare_words_into_values() {
+        let query = parse("artist = Blut Aus Nord and year = 2022").unwrap();
+
+        assert_eq!(
+            vec![Value::Text("Blut Aus Nord".to_string())],
+            query.filters[0].values
+        );
+    }
+
+    #[test]
+    fn reports_unknown_fields() {
+        assert_eq!(
+            Err(QueryError::UnknownField {
+                field: "genre".to_string()
+            }),
+            parse("genre = metal").map(|_| ())
+        );
+        assert!(parse("year = twenty").is_err());
+        assert!(parse("artist = 'Ulcerate").is_err());
+    }
+}
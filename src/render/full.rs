@@ -1,24 +1,30 @@
 use std::{io, sync::Arc, time::Duration};
 
 use crossterm::{
-    event::{self, poll, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, poll, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers,
+        MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
-    prelude::{Backend, Constraint, CrosstermBackend, Direction, Layout, Rect},
+    prelude::{Alignment, Backend, Constraint, CrosstermBackend, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{BarChart, Block, Borders, List, ListItem, Paragraph},
+    widgets::{
+        BarChart, Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row,
+        Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
+    },
     Frame, Terminal,
 };
 use tokio::sync::Mutex;
 
 use crate::{
-    app::{Application, MessageSet, Mode, Output},
+    app::{Application, Completion, MessageSet, Mode, Output, Scroll},
     errors::InteractiveError,
-    persistence::{EventStore, StateStore},
-    projections::ListenTrackerRepository,
+    persistence::{EventStore, SettingsStore, StateStore},
+    projections::{ListenTrackerRepository, ListensRepository},
 };
 
 use unicode_width::UnicodeWidthStr;
@@ -27,8 +33,10 @@ pub async fn full_ui(
     store: Arc<Mutex<dyn EventStore>>,
     state_store: Arc<Mutex<dyn StateStore>>,
     repository: Arc<Mutex<dyn ListenTrackerRepository>>,
+    listens: Arc<Mutex<dyn ListensRepository>>,
+    settings_store: Arc<Mutex<dyn SettingsStore>>,
 ) -> Result<(), InteractiveError> {
-    let mut app = Application::new(store, repository, state_store);
+    let mut app = Application::new(store, repository, listens, state_store, settings_store);
     app.initialize().await?;
 
     println!("Loading...");
@@ -78,12 +86,22 @@ pub async fn full_ui(
 
 async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: Application) -> io::Result<()> {
     loop {
-        terminal.draw(|f| ui(f, &app))?;
+        terminal.draw(|f| ui(f, &mut app))?;
 
         // Poll for an event, we do this so that we don't block on waiting for an event.
         // If we blocked, then we wouldn't tick the app to the next stage.
         if poll(Duration::from_millis(15))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = &event {
+                if matches!(app.state.mode, Mode::Normal) {
+                    match mouse.kind {
+                        MouseEventKind::ScrollDown => app.scroll_output(Scroll::Down(3)),
+                        MouseEventKind::ScrollUp => app.scroll_output(Scroll::Up(3)),
+                        _ => {}
+                    }
+                }
+            }
+            if let Event::Key(key) = event {
                 match app.state.mode {
                     Mode::Normal => match key.code {
                         KeyCode::Char('e') => {
@@ -95,12 +113,51 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: Application) -
                         KeyCode::Char('c') => {
                             app.copy_to_clipboard();
                         }
+                        KeyCode::Char('o') => {
+                            app.start_output_navigation();
+                        }
+                        KeyCode::Char('h') => {
+                            app.start_history_navigation();
+                        }
+                        KeyCode::Char('/') => {
+                            app.start_search();
+                        }
+                        KeyCode::Char('n') => {
+                            app.next_match();
+                        }
+                        KeyCode::Char('N') => {
+                            app.previous_match();
+                        }
+                        KeyCode::Esc => {
+                            app.cancel_search();
+                        }
                         KeyCode::Right => {
                             app.go_to_next_page();
                         }
                         KeyCode::Left => {
                             app.go_to_previous_page();
                         }
+                        KeyCode::Char(c @ '1'..='9') => {
+                            app.sort_current_output(c as usize - '1' as usize);
+                        }
+                        KeyCode::Down => {
+                            app.scroll_output(Scroll::Down(1));
+                        }
+                        KeyCode::Up => {
+                            app.scroll_output(Scroll::Up(1));
+                        }
+                        KeyCode::PageDown => {
+                            app.scroll_output(Scroll::PageDown);
+                        }
+                        KeyCode::PageUp => {
+                            app.scroll_output(Scroll::PageUp);
+                        }
+                        KeyCode::Home => {
+                            app.scroll_output(Scroll::Top);
+                        }
+                        KeyCode::End => {
+                            app.scroll_output(Scroll::Bottom);
+                        }
                         _ => {}
                     },
                     Mode::EnterCommand => match key.code {
@@ -113,6 +170,9 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: Application) -
                         KeyCode::Down => {
                             app.next_command();
                         }
+                        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.search_history();
+                        }
                         KeyCode::Char(c) => {
                             app.push_input_char(c);
                         }
@@ -127,6 +187,99 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: Application) -
                         }
                         _ => {}
                     },
+                    Mode::Outputs => match key.code {
+                        KeyCode::Enter => {
+                            app.jump_to_selected_output();
+                        }
+                        KeyCode::Up => {
+                            app.select_previous_output();
+                        }
+                        KeyCode::Down => {
+                            app.select_next_output();
+                        }
+                        KeyCode::Char('x') | KeyCode::Delete => {
+                            app.close_selected_output();
+                        }
+                        KeyCode::Char('p') => {
+                            app.toggle_selected_output_pinned();
+                        }
+                        KeyCode::Char('r') => {
+                            app.start_output_rename();
+                        }
+                        KeyCode::Esc | KeyCode::Char('o') => {
+                            app.stop_output_navigation();
+                        }
+                        _ => {}
+                    },
+                    Mode::History => match key.code {
+                        KeyCode::Enter => {
+                            app.rerun_selected_history();
+                        }
+                        KeyCode::Up => {
+                            app.select_previous_history();
+                        }
+                        KeyCode::Down => {
+                            app.select_next_history();
+                        }
+                        KeyCode::Char('e') => {
+                            app.edit_selected_history();
+                        }
+                        KeyCode::Esc | KeyCode::Char('h') => {
+                            app.stop_history_navigation();
+                        }
+                        _ => {}
+                    },
+                    Mode::HistorySearch => match key.code {
+                        KeyCode::Enter => {
+                            app.history_search_entered();
+                        }
+                        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.search_history();
+                        }
+                        KeyCode::Tab | KeyCode::Right => {
+                            app.edit_history_search_match();
+                        }
+                        KeyCode::Char(c) => {
+                            app.push_input_char(c);
+                        }
+                        KeyCode::Backspace => {
+                            app.pop_input_char();
+                        }
+                        KeyCode::Esc => {
+                            app.cancel_history_search();
+                        }
+                        _ => {}
+                    },
+                    Mode::Search => match key.code {
+                        KeyCode::Enter => {
+                            app.search_entered();
+                        }
+                        KeyCode::Char(c) => {
+                            app.push_input_char(c);
+                        }
+                        KeyCode::Backspace => {
+                            app.pop_input_char();
+                        }
+                        KeyCode::Esc => {
+                            app.cancel_search();
+                        }
+                        _ => {}
+                    },
+                    Mode::RenameOutput => match key.code {
+                        KeyCode::Enter => {
+                            app.output_rename_entered();
+                        }
+                        KeyCode::Char(c) => {
+                            app.push_input_char(c);
+                        }
+                        KeyCode::Backspace => {
+                            app.pop_input_char();
+                        }
+                        KeyCode::Esc => {
+                            app.cancel_output_rename();
+                        }
+                        _ => {}
+                    },
                     Mode::CommandParameters => match key.code {
                         KeyCode::Enter => {
                             app.command_input_entered();
@@ -156,7 +309,7 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: Application) -
     }
 }
 
-fn ui(f: &mut Frame, app: &Application) {
+fn ui(f: &mut Frame, app: &mut Application) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
@@ -180,8 +333,18 @@ fn ui(f: &mut Frame, app: &Application) {
                 Span::raw(" to enter command, "),
                 Span::styled("c", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to copy all output, "),
+                Span::styled("o", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to list the outputs, "),
+                Span::styled("h", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to show the command history, "),
+                Span::styled("/", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to search, "),
                 Span::styled("< and >", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to go back and forward in output."),
+                Span::raw(" to go back and forward in output, "),
+                Span::styled("arrows", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to scroll, "),
+                Span::styled("1-9", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to sort a table by a column."),
             ],
             Style::default().add_modifier(Modifier::RAPID_BLINK),
         ),
@@ -191,7 +354,76 @@ fn ui(f: &mut Frame, app: &Application) {
                 Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to stop editing, "),
                 Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to execute the command"),
+                Span::raw(" to execute the command, "),
+                Span::styled("Ctrl-R", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to search the history"),
+            ],
+            Style::default(),
+        ),
+        Mode::History => (
+            vec![
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to run again, "),
+                Span::styled("e", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to edit and run, "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to go back"),
+            ],
+            Style::default(),
+        ),
+        Mode::HistorySearch => {
+            let found = match app.history_search_match() {
+                Some(entry) => Span::styled(
+                    entry.command().to_string(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                None => Span::styled("no match", Style::default().fg(Color::DarkGray)),
+            };
+            (
+                vec![
+                    Span::raw("Reverse search: "),
+                    found,
+                    Span::raw("  "),
+                    Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(" to run, "),
+                    Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(" to edit, "),
+                    Span::styled("Ctrl-R", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(" for older"),
+                ],
+                Style::default(),
+            )
+        }
+        Mode::Outputs => (
+            vec![
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to show, "),
+                Span::styled("x", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to close, "),
+                Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to rename, "),
+                Span::styled("p", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to pin so it's kept when output is cleared, "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to go back"),
+            ],
+            Style::default(),
+        ),
+        Mode::Search => (
+            vec![
+                Span::raw("Type to search the output, "),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to keep the matches, "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to stop searching"),
+            ],
+            Style::default(),
+        ),
+        Mode::RenameOutput => (
+            vec![
+                Span::raw("Enter a new title, "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to keep the old one"),
             ],
             Style::default(),
         ),
@@ -210,14 +442,22 @@ fn ui(f: &mut Frame, app: &Application) {
 
     let input = Paragraph::new(app.current_input())
         .style(match app.mode() {
-            Mode::EnterCommand | Mode::CommandParameters => Style::default().fg(Color::Yellow),
+            Mode::EnterCommand
+            | Mode::CommandParameters
+            | Mode::RenameOutput
+            | Mode::Search
+            | Mode::HistorySearch => Style::default().fg(Color::Yellow),
             _ => Style::default(),
         })
         .block(Block::default().borders(Borders::ALL).title("Input"));
     f.render_widget(input, chunks[1]);
 
     match app.mode() {
-        Mode::EnterCommand | Mode::CommandParameters => {
+        Mode::EnterCommand
+        | Mode::CommandParameters
+        | Mode::RenameOutput
+        | Mode::Search
+        | Mode::HistorySearch => {
             // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
             f.set_cursor(
                 // Put cursor past the end of the input text
@@ -231,40 +471,153 @@ fn ui(f: &mut Frame, app: &Application) {
             {}
     }
 
+    let output_chunk = match app.mode() {
+        Mode::Outputs | Mode::RenameOutput => {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(44), Constraint::Min(1)].as_ref())
+                .split(chunks[2]);
+            render_outputs_sidebar(f, columns[0], app);
+            columns[1]
+        }
+        Mode::History => {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(60), Constraint::Min(1)].as_ref())
+                .split(chunks[2]);
+            render_history_sidebar(f, columns[0], app);
+            columns[1]
+        }
+        _ => chunks[2],
+    };
+
+    // The borders, and the error or the table header, take up lines of the output area.
+    let reserved_lines = match (app.current_output(), app.error_message()) {
+        (Some(Output::Table(_)), _) | (_, Some(_)) => 3,
+        _ => 2,
+    };
+    app.set_page_height(output_chunk.height.saturating_sub(reserved_lines) as usize);
+
     match app.current_output() {
-        Some(Output::MessageSet(message_set)) => render_message_set(
-            f,
-            chunks[2],
-            &message_set,
-            app.error_message(),
-            app.mode(),
-            app.current_page_display(),
-            app.num_pages(),
-        ),
+        Some(Output::MessageSet(message_set)) => {
+            render_message_set(f, output_chunk, &message_set, app)
+        }
         Some(Output::BarChart(bar_chart)) => {
             render_chart(
                 f,
-                chunks[2],
+                output_chunk,
                 bar_chart,
                 app.current_page_display(),
                 app.num_pages(),
             );
         }
-        None => render_empty(f, chunks[2], app.error_message(), app.mode()),
+        Some(Output::Table(table)) => render_table(f, output_chunk, &table, app),
+        None => render_empty(f, output_chunk, app.error_message(), app.mode()),
+    }
+
+    if let Some(completion) = app.completion() {
+        render_completion(f, chunks[2], completion);
     }
 }
 
-fn render_message_set(
-    f: &mut Frame,
-    chunk: Rect,
-    message_set: &MessageSet,
-    error_message: &Option<String>,
-    mode: &Mode,
-    current_page: usize,
-    total_pages: usize,
-) {
+/// Every output with when it was added, the one on screen in bold and pinned ones marked.
+fn render_outputs_sidebar(f: &mut Frame, chunk: Rect, app: &Application) {
+    let current_page = app.current_page_display().saturating_sub(1);
+    let items: Vec<ListItem> = app
+        .output_summaries()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (title, details))| {
+            let pinned = details.is_some_and(|details| details.pinned);
+            let title_style = if index == current_page {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let mut spans = vec![
+                Span::raw(if pinned { "* " } else { "  " }),
+                Span::styled(title.to_string(), title_style),
+            ];
+            if let Some(details) = details {
+                spans.push(Span::styled(
+                    format!(" {}", details.created_at.format("%m-%d %H:%M")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Outputs"))
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+    let mut list_state = ListState::default().with_selected(Some(app.selected_output()));
+
+    f.render_stateful_widget(list, chunk, &mut list_state);
+}
+
+/// The commands run, newest first, with when they ran.
+fn render_history_sidebar(f: &mut Frame, chunk: Rect, app: &Application) {
+    let items: Vec<ListItem> = app
+        .history_summaries()
+        .into_iter()
+        .map(|entry| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    match entry.ran_at() {
+                        Some(ran_at) => format!("{} ", ran_at.format("%m-%d %H:%M")),
+                        None => format!("{:12}", ""),
+                    },
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(entry.command().to_string()),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("History"))
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+    let mut list_state = ListState::default().with_selected(Some(app.selected_history()));
+
+    f.render_stateful_widget(list, chunk, &mut list_state);
+}
+
+fn render_completion(f: &mut Frame, chunk: Rect, completion: &Completion) {
+    let width = completion
+        .candidates()
+        .iter()
+        .map(|candidate| candidate.width() as u16)
+        .max()
+        .unwrap_or_default()
+        .saturating_add(4)
+        .max(30)
+        .min(chunk.width);
+    let height = (completion.candidates().len() as u16 + 2).min(chunk.height);
+    let area = Rect::new(chunk.x, chunk.y, width, height);
+
+    let items: Vec<ListItem> = completion
+        .candidates()
+        .iter()
+        .map(|candidate| ListItem::new(Line::from(Span::raw(candidate.as_str()))))
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Tab for the next match"),
+        )
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+    let mut list_state = ListState::default().with_selected(Some(completion.selected_index()));
+
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn render_message_set(f: &mut Frame, chunk: Rect, message_set: &MessageSet, app: &Application) {
+    let mode = app.mode();
+    let scroll_offset = app.scroll_offset();
+    let (current_page, total_pages) = (app.current_page_display(), app.num_pages());
     let mut messages: Vec<ListItem> = Vec::new();
-    if let Some(error_message) = error_message {
+    if let Some(error_message) = app.error_message() {
         let content = vec![Line::from(Span::styled(
             format!("Error: {error_message}"),
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
@@ -274,23 +627,34 @@ fn render_message_set(
 
     let include_number = !matches!(mode, Mode::EnterCommand);
 
-    for (i, m) in message_set.messages().iter().enumerate() {
+    for (i, m) in message_set
+        .messages()
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+    {
         let message = if include_number {
             format!("{i}: {m}")
         } else {
             m.to_string()
         };
         let content = vec![Line::from(Span::raw(message))];
-        messages.push(ListItem::new(content));
+        messages.push(ListItem::new(content).style(search_style(app, i, m)));
     }
 
     let body_title = match mode {
         Mode::CommandParameters => "".to_string(),
-        Mode::EnterCommand => "Enter Command".to_string(),
-        Mode::Normal | Mode::Processing => {
+        Mode::EnterCommand | Mode::HistorySearch => "Enter Command".to_string(),
+        Mode::Normal
+        | Mode::Processing
+        | Mode::Outputs
+        | Mode::RenameOutput
+        | Mode::Search
+        | Mode::History => {
             format!(
-                "{} page {current_page} of {total_pages} overflow not shown, copy or export to see",
-                message_set.title()
+                "{} page {current_page} of {total_pages}{}",
+                message_set.title(),
+                search_display(app, message_set.messages())
             )
         }
     };
@@ -298,6 +662,119 @@ fn render_message_set(
     let blocks =
         List::new(messages).block(Block::default().borders(Borders::ALL).title(body_title));
     f.render_widget(blocks, chunk);
+    render_scrollbar(f, chunk, message_set.messages().len(), scroll_offset);
+}
+
+/// Matching lines are picked out in yellow, and the current match highlighted.
+fn search_style(app: &Application, index: usize, line: &str) -> Style {
+    match app.output_search() {
+        Some(search) if search.current_line() == Some(index) && search.is_match(line) => {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        }
+        Some(search) if search.is_match(line) => Style::default().fg(Color::Yellow),
+        _ => Style::default(),
+    }
+}
+
+fn search_display(app: &Application, lines: &[String]) -> String {
+    match app.output_search() {
+        Some(search) if !search.query().is_empty() => format!(
+            ", {} matches for \"{}\", n and N to jump",
+            search.matches(lines).len(),
+            search.query()
+        ),
+        _ => String::new(),
+    }
+}
+
+/// Shows how far through the output is scrolled, along the right border, if it doesn't
+/// all fit.
+fn render_scrollbar(f: &mut Frame, chunk: Rect, lines: usize, scroll_offset: usize) {
+    if lines <= chunk.height.saturating_sub(2) as usize {
+        return;
+    }
+
+    let mut scrollbar_state = ScrollbarState::new(lines).position(scroll_offset);
+    f.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight),
+        chunk.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut scrollbar_state,
+    );
+}
+
+fn render_table(f: &mut Frame, chunk: Rect, table: &crate::app::Table, app: &Application) {
+    let scroll_offset = app.scroll_offset();
+    let (current_page, total_pages) = (app.current_page_display(), app.num_pages());
+    let header: Vec<String> = table
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(index, column)| match table.sort() {
+            Some(sort) if sort.column == index => {
+                format!("{column} {}", if sort.descending { "▼" } else { "▲" })
+            }
+            _ => column.to_string(),
+        })
+        .collect();
+    let rows = table.rows();
+    let widths: Vec<Constraint> = header
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let width = rows
+                .iter()
+                .filter_map(|row| row.get(index))
+                .map(|cell| cell.to_string().width())
+                .chain([column.width()])
+                .max()
+                .unwrap_or_default();
+            Constraint::Length(width.min(40) as u16)
+        })
+        .collect();
+    let lines = table.messages();
+    let rows: Vec<Row> = rows
+        .iter()
+        .zip(lines.iter())
+        .enumerate()
+        .skip(scroll_offset)
+        .map(|(index, (row, line))| {
+            Row::new(row.iter().map(|cell| {
+                let text = Text::from(cell.to_string());
+                if cell.is_numeric() {
+                    Cell::from(text.alignment(Alignment::Right))
+                } else {
+                    Cell::from(text)
+                }
+            }))
+            .style(search_style(app, index, line))
+        })
+        .collect();
+
+    let mut title: Vec<Span> = Vec::new();
+    if let Some(error_message) = app.error_message() {
+        title.push(Span::styled(
+            format!("Error: {error_message} "),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    title.push(Span::raw(format!(
+        "{} page {current_page} of {total_pages}, 1-9 to sort{}",
+        table.title(),
+        search_display(app, &lines)
+    )));
+    let widget = Table::new(rows, widths)
+        .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+        .column_spacing(2)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title)),
+        );
+    f.render_widget(widget, chunk);
+    render_scrollbar(f, chunk, table.len(), scroll_offset);
 }
 
 fn render_empty(f: &mut Frame, chunk: Rect, error_message: &Option<String>, mode: &Mode) {
@@ -312,8 +789,13 @@ fn render_empty(f: &mut Frame, chunk: Rect, error_message: &Option<String>, mode
 
     let body_title = match mode {
         Mode::CommandParameters => "".to_string(),
-        Mode::EnterCommand => "Enter Command".to_string(),
-        Mode::Normal | Mode::Processing => "Output".to_string(),
+        Mode::EnterCommand | Mode::HistorySearch => "Enter Command".to_string(),
+        Mode::Normal
+        | Mode::Processing
+        | Mode::Outputs
+        | Mode::RenameOutput
+        | Mode::Search
+        | Mode::History => "Output".to_string(),
     };
 
     let blocks =
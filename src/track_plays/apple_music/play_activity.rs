@@ -21,6 +21,8 @@ pub struct PlayActivity {
     pub media_duration_ms: Option<u64>,
     #[serde(alias = "Play Duration Milliseconds")]
     pub play_duration_ms: Option<i64>,
+    #[serde(alias = "End Reason Type")]
+    pub end_reason_type: Option<String>,
 }
 
 impl PlayActivity {
@@ -31,13 +33,6 @@ impl PlayActivity {
     pub fn is_end_event(&self) -> bool {
         self.event_type.eq_ignore_ascii_case("play_end")
     }
-
-    pub fn is_skipped_by_percent(&self) -> bool {
-        self.play_duration_ms
-            .zip(self.media_duration_ms)
-            .map(|(ms_played, track_ms)| (ms_played as f64 / track_ms as f64) < 0.1)
-            .unwrap_or(false)
-    }
 }
 
 impl TryInto<Normalized> for PlayActivity {
@@ -63,7 +58,8 @@ impl TryInto<Normalized> for PlayActivity {
         Ok(Normalized {
             end_time,
             ms_played,
-            skipped: Some(self.is_skipped_by_percent()),
+            skipped: None,
+            reason_end: self.end_reason_type,
             album_name: self.album_name.map(AlbumName),
             artist_name: ArtistName(self.artist_name),
             track_name: TrackName(self.song_name),
@@ -102,6 +98,7 @@ mod tests {
             event_end_timestamp: "2022-10-07T18:39:52.592Z".to_string(),
             media_duration_ms: Some(192000),
             play_duration_ms: Some(192000),
+            end_reason_type: None,
         };
 
         let normalized: Normalized = play_activity.try_into().unwrap();
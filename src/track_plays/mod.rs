@@ -14,3 +14,6 @@ pub use parse::read_track_plays;
 pub use spotify::Spotify;
 pub use track_name::TrackName;
 pub use track_play::TrackPlay;
+
+/// The service each listen is imported from, as stored on its events.
+pub const SERVICE_HINTS: [&str; 2] = ["spotify", "apple_music"];
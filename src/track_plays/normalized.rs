@@ -1,5 +1,7 @@
 use chrono::NaiveDateTime;
 
+use crate::processing::ClassificationPolicy;
+
 use super::{AlbumName, ArtistName, TrackName};
 
 pub struct Normalized {
@@ -11,6 +13,7 @@ pub struct Normalized {
     pub ms_played: Option<u64>,
     pub track_ms: Option<u64>,
     pub skipped: Option<bool>,
+    pub reason_end: Option<String>,
 }
 
 impl Normalized {
@@ -22,15 +25,13 @@ impl Normalized {
         self.ms_played.unwrap_or_default()
     }
 
-    pub fn is_skipped(&self) -> bool {
-        self.skipped.unwrap_or_default() || self.is_skipped_by_percent()
-    }
-
-    pub fn is_skipped_by_percent(&self) -> bool {
-        self.ms_played
-            .zip(self.track_ms)
-            .map(|(ms_played, track_ms)| (ms_played as f64 / track_ms as f64) < 0.1)
-            .unwrap_or(false)
+    pub fn is_skipped_by(&self, policy: &ClassificationPolicy) -> bool {
+        policy.is_skipped(
+            self.play_time(),
+            self.track_ms,
+            self.skipped,
+            &self.reason_end,
+        )
     }
 }
 
@@ -38,7 +39,10 @@ impl Normalized {
 mod tests {
     use chrono::NaiveDateTime;
 
-    use crate::track_plays::{ArtistName, TrackName};
+    use crate::{
+        processing::ClassificationPolicy,
+        track_plays::{ArtistName, TrackName},
+    };
 
     use super::Normalized;
 
@@ -53,8 +57,9 @@ mod tests {
             ms_played: Some(330994),
             track_ms: Some(357000),
             skipped: Some(false),
+            reason_end: None,
         };
 
-        assert!(!normalized.is_skipped_by_percent());
+        assert!(!normalized.is_skipped_by(&ClassificationPolicy::default()));
     }
 }
@@ -34,15 +34,15 @@ pub fn read_track_plays(path: &PathBuf) -> Result<Vec<TrackPlay>, ReadError> {
             file_type: extension,
         }),
         None => Err(ReadError::UnsupportedFileType {
-            file_type: format!("{:?}", &path),
+            file_type: format!("{:?}", path),
         }),
     }
 }
 
 fn parse_json(path: &PathBuf) -> Result<Vec<TrackPlay>, ReadError> {
-    let contents = fs::read_to_string(format!("{}", &path.display())).map_err(|err| {
+    let contents = fs::read_to_string(format!("{}", path.display())).map_err(|err| {
         ReadError::CannotReadContents {
-            file_name: format!("{:?}", &path),
+            file_name: format!("{:?}", path),
             message: err.to_string(),
         }
     })?;
@@ -57,13 +57,13 @@ fn parse_json(path: &PathBuf) -> Result<Vec<TrackPlay>, ReadError> {
         })
         .map_err(|err| ReadError::FailedToDeserializeJson {
             message: err.to_string(),
-            file_name: format!("{:?}", &path),
+            file_name: format!("{:?}", path),
         })
 }
 
 fn parse_csv(path: &PathBuf) -> Result<Vec<TrackPlay>, ReadError> {
     let mut reader = csv::Reader::from_path(path).map_err(|err| ReadError::CannotReadContents {
-        file_name: format!("{:?}", &path),
+        file_name: format!("{:?}", path),
         message: err.to_string(),
     })?;
 
@@ -71,7 +71,7 @@ fn parse_csv(path: &PathBuf) -> Result<Vec<TrackPlay>, ReadError> {
         .headers()
         .map_err(|err| ReadError::FailedToDeserializeCsv {
             message: err.to_string(),
-            file_name: format!("{:?}", &path),
+            file_name: format!("{:?}", path),
         })
         .cloned()?;
 
@@ -98,7 +98,7 @@ fn parse_csv(path: &PathBuf) -> Result<Vec<TrackPlay>, ReadError> {
     if activities.is_empty() {
         return Err(ReadError::FailedToDeserializeCsv {
             message: "No records successfully deserialized".to_string(),
-            file_name: format!("{:?}", &path),
+            file_name: format!("{:?}", path),
         });
     }
 
@@ -121,7 +121,7 @@ mod tests {
         path.set_extension("json");
 
         let plays = read_track_plays(&path).unwrap();
-        println!("{:?}", &plays);
+        println!("{:?}", plays);
         assert_eq!(1, plays.len());
     }
 
@@ -133,7 +133,7 @@ mod tests {
         path.set_extension("json");
 
         let plays = read_track_plays(&path).unwrap();
-        println!("{:?}", &plays);
+        println!("{:?}", plays);
 
         let play = match plays.first().unwrap() {
             TrackPlay::Spotify(it) => it,
@@ -150,7 +150,7 @@ mod tests {
         path.set_extension("csv");
 
         let plays = read_track_plays(&path).unwrap();
-        println!("{:?}", &plays);
+        println!("{:?}", plays);
         assert_eq!(1, plays.len());
     }
 }
@@ -17,6 +17,7 @@ pub struct Spotify {
     #[serde(rename = "msPlayed", alias = "ms_played")]
     pub ms_played: u64,
     pub skipped: Option<bool>,
+    pub reason_end: Option<String>,
 }
 
 impl Spotify {
@@ -58,6 +59,7 @@ impl TryInto<Normalized> for Spotify {
             ms_played: Some(self.ms_played),
             track_ms: None,
             skipped: self.skipped,
+            reason_end: self.reason_end,
         })
     }
 }
@@ -79,6 +81,7 @@ mod tests {
             album_name: None,
             ms_played: 20222,
             skipped: None,
+            reason_end: None,
         };
 
         let normalized: Normalized = play.try_into().unwrap();
@@ -94,6 +97,7 @@ mod tests {
             album_name: None,
             ms_played: 20222,
             skipped: None,
+            reason_end: None,
         };
 
         let normalized: Normalized = play.try_into().unwrap();
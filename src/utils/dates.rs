@@ -0,0 +1,31 @@
+/// Every (year, month) from `first` to `last`, both included, so charts can show months
+/// without plays as zero.
+pub fn months_between(first: (i32, u32), last: (i32, u32)) -> Vec<(i32, u32)> {
+    let mut months = Vec::new();
+    let (mut year, mut month) = first;
+    while (year, month) <= last {
+        months.push((year, month));
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+    }
+
+    months
+}
+
+#[cfg(test)]
+mod tests {
+    use super::months_between;
+
+    #[test]
+    fn lists_months_across_years() {
+        assert_eq!(
+            vec![(2022, 11), (2022, 12), (2023, 1)],
+            months_between((2022, 11), (2023, 1))
+        );
+        assert!(months_between((2023, 2), (2023, 1)).is_empty());
+    }
+}
@@ -1,3 +1,10 @@
+mod dates;
 mod parse;
+mod search;
 
-pub use parse::{parse_end_time_rfc3339, parse_formatted_end_time, parse_spotify_end_time};
+pub use dates::months_between;
+pub use parse::{
+    parse_date_input, parse_end_time_rfc3339, parse_formatted_end_time, parse_relative_month,
+    parse_relative_period, parse_relative_year, parse_spotify_end_time, parse_yes_no, yes_no,
+};
+pub use search::{best_matches, fold};
@@ -1,4 +1,4 @@
-use chrono::{DateTime, NaiveDateTime, ParseResult};
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveDateTime, ParseResult};
 
 pub fn parse_formatted_end_time(end_time: &str) -> ParseResult<NaiveDateTime> {
     NaiveDateTime::parse_from_str(end_time, "%Y-%m-%d %H:%M:%S")
@@ -12,11 +12,152 @@ pub fn parse_end_time_rfc3339(end_time: &str) -> ParseResult<NaiveDateTime> {
     DateTime::parse_from_rfc3339(end_time).map(|date_time| date_time.naive_local())
 }
 
+/// Accepts `YYYY-MM-DD`, `today`, `yesterday` or `N days ago`.
+pub fn parse_date_input(text: &str, today: &NaiveDate) -> Option<NaiveDate> {
+    let lowered = text.trim().to_lowercase();
+
+    match lowered.as_str() {
+        "today" => Some(*today),
+        "yesterday" => today.pred_opt(),
+        _ => match lowered.strip_suffix("days ago") {
+            Some(days) => days
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .and_then(|days| today.checked_sub_days(Days::new(days))),
+            None => NaiveDate::parse_from_str(&lowered, "%Y-%m-%d").ok(),
+        },
+    }
+}
+
+/// Accepts `this year`, `last year`, `this month`, `last month` or `last N days`, as the
+/// first and last day of the period relative to today.
+pub fn parse_relative_period(text: &str, today: &NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    let lowered = text.trim().to_lowercase();
+    let start_of_month = today.with_day(1)?;
+    let start_of_year = start_of_month.with_month(1)?;
+
+    match lowered.as_str() {
+        "this year" => Some((start_of_year, *today)),
+        "last year" => Some((
+            start_of_year.checked_sub_months(Months::new(12))?,
+            start_of_year.pred_opt()?,
+        )),
+        "this month" => Some((start_of_month, *today)),
+        "last month" => Some((
+            start_of_month.checked_sub_months(Months::new(1))?,
+            start_of_month.pred_opt()?,
+        )),
+        _ => {
+            let days = lowered
+                .strip_prefix("last ")?
+                .strip_suffix("days")?
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .filter(|days| *days > 0)?;
+            Some((today.checked_sub_days(Days::new(days - 1))?, *today))
+        }
+    }
+}
+
+/// The year of `this year` or `last year`. Other relative periods aren't a whole year.
+pub fn parse_relative_year(text: &str, today: &NaiveDate) -> Option<i32> {
+    match text.trim().to_lowercase().as_str() {
+        "this year" | "last year" => {
+            parse_relative_period(text, today).map(|(start, _)| start.year())
+        }
+        _ => None,
+    }
+}
+
+/// The year and month of `this month` or `last month`. Other relative periods aren't a
+/// whole month.
+pub fn parse_relative_month(text: &str, today: &NaiveDate) -> Option<(i32, u32)> {
+    match text.trim().to_lowercase().as_str() {
+        "this month" | "last month" => {
+            parse_relative_period(text, today).map(|(start, _)| (start.year(), start.month()))
+        }
+        _ => None,
+    }
+}
+
+pub fn parse_yes_no(text: &str) -> Option<bool> {
+    match text.trim().to_lowercase().as_str() {
+        "y" | "yes" | "true" => Some(true),
+        "n" | "no" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use chrono::Datelike;
+    use chrono::{Datelike, NaiveDate};
+
+    use super::{
+        parse_date_input, parse_end_time_rfc3339, parse_relative_month, parse_relative_period,
+        parse_relative_year,
+    };
+
+    #[test]
+    fn date_input_parse_relative() {
+        let today = NaiveDate::from_ymd_opt(2023, 3, 31).unwrap();
+
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2022, 12, 31),
+            parse_date_input("90 days ago", &today)
+        );
+        assert_eq!(today.pred_opt(), parse_date_input("Yesterday", &today));
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2021, 6, 15),
+            parse_date_input("2021-06-15", &today)
+        );
+        assert!(parse_date_input("last week", &today).is_none());
+    }
+
+    #[test]
+    fn relative_period_parse() {
+        let today = NaiveDate::from_ymd_opt(2023, 1, 15).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(
+            Some((date(2023, 1, 1), today)),
+            parse_relative_period("This year", &today)
+        );
+        assert_eq!(
+            Some((date(2022, 12, 1), date(2022, 12, 31))),
+            parse_relative_period("last month", &today)
+        );
+        assert_eq!(
+            Some((date(2022, 1, 1), date(2022, 12, 31))),
+            parse_relative_period("last year", &today)
+        );
+        assert_eq!(
+            Some((date(2022, 12, 17), today)),
+            parse_relative_period("last 30 days", &today)
+        );
+        assert!(parse_relative_period("last 0 days", &today).is_none());
+        assert!(parse_relative_period("next month", &today).is_none());
+    }
+
+    #[test]
+    fn relative_year_and_month_parse() {
+        let today = NaiveDate::from_ymd_opt(2023, 1, 15).unwrap();
 
-    use super::parse_end_time_rfc3339;
+        assert_eq!(Some(2022), parse_relative_year("Last year", &today));
+        assert_eq!(None, parse_relative_year("last month", &today));
+        assert_eq!(Some((2022, 12)), parse_relative_month("last month", &today));
+        assert_eq!(Some((2023, 1)), parse_relative_month("this month", &today));
+        assert_eq!(None, parse_relative_month("last 30 days", &today));
+    }
 
     #[test]
     fn end_time_parse_rfc3339() {
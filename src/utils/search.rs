@@ -0,0 +1,120 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Lowercases the text and strips its diacritics, so "Mgła", "MGLA" and "mgla" all fold
+/// to the same thing.
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.nfkd().filter(|c| !is_combining_mark(*c)) {
+        match c {
+            'ł' | 'Ł' => folded.push('l'),
+            'ø' | 'Ø' => folded.push('o'),
+            'đ' | 'Đ' => folded.push('d'),
+            'ı' => folded.push('i'),
+            'æ' | 'Æ' => folded.push_str("ae"),
+            'œ' | 'Œ' => folded.push_str("oe"),
+            'ß' => folded.push_str("ss"),
+            'þ' | 'Þ' => folded.push_str("th"),
+            c => folded.extend(c.to_lowercase()),
+        }
+    }
+
+    folded
+}
+
+/// How well the query matches the candidate, higher being better, or `None` if it doesn't
+/// match at all. Whole matches beat prefixes, which beat the start of a later word, then
+/// anywhere in the name, then the query's letters appearing in order.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let query = fold(query.trim());
+    let candidate = fold(candidate);
+
+    if query.is_empty() {
+        return Some(0);
+    }
+    if candidate == query {
+        return Some(1000);
+    }
+    if candidate.starts_with(&query) {
+        return Some(800);
+    }
+    if candidate
+        .match_indices(&query)
+        .any(|(position, _)| candidate[..position].ends_with([' ', '-', '(', '/']))
+    {
+        return Some(600);
+    }
+    if candidate.contains(&query) {
+        return Some(400);
+    }
+
+    let mut gaps: u32 = 0;
+    let mut candidate_chars = candidate.chars();
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        loop {
+            match candidate_chars.next() {
+                Some(c) if c == query_char => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+
+    Some(200u32.saturating_sub(gaps).max(1))
+}
+
+/// The candidates matching the query, best first. Equally good matches are ordered by
+/// length, so the shortest name that matches comes first.
+pub fn best_matches<'a, I>(query: &str, candidates: I, limit: usize) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut matches: Vec<(u32, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| fuzzy_score(query, candidate).map(|score| (score, candidate)))
+        .collect();
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| a.chars().count().cmp(&b.chars().count()))
+            .then_with(|| a.cmp(b))
+    });
+    matches.dedup_by(|(_, a), (_, b)| a == b);
+
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_matches, fold, fuzzy_score};
+
+    #[test]
+    fn folds_diacritics() {
+        assert_eq!("mgla", fold("Mgła"));
+        assert_eq!("blue oyster cult", fold("Blue Öyster Cult"));
+        assert_eq!("sigur ros", fold("Sigur Rós"));
+        assert_eq!("aeon", fold("Æon"));
+    }
+
+    #[test]
+    fn ranks_matches() {
+        assert_eq!(Some(1000), fuzzy_score("mgla", "Mgła"));
+        assert!(fuzzy_score("ulc", "Ulcerate") > fuzzy_score("ulc", "Chulcorp"));
+        assert!(fuzzy_score("nord", "Blut Aus Nord") > fuzzy_score("nord", "Ordnordost"));
+        assert!(fuzzy_score("bln", "Blut Aus Nord").is_some());
+        assert!(fuzzy_score("xyz", "Blut Aus Nord").is_none());
+
+        let candidates = ["Ulcerate", "Blut Aus Nord", "Ulver", "Full of Hell"];
+        assert_eq!(
+            vec![
+                "Ulver".to_string(),
+                "Ulcerate".to_string(),
+                "Full of Hell".to_string()
+            ],
+            best_matches("ul", candidates, 3)
+        );
+    }
+}